@@ -0,0 +1,36 @@
+/// The processing context a node tree is run under.
+///
+/// Nodes are still constructed with a sample rate so that they are usable on
+/// their own, but a whole tree can be re-targeted at a different sample rate
+/// by calling [`Node::prepare`](crate::Node::prepare) on its root before
+/// processing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Context {
+    /// The sample rate, in samples per second.
+    pub sample_rate: u32,
+
+    /// The largest number of samples that will be processed in one block.
+    pub max_block_size: usize,
+
+    /// The number of output channels the tree will be rendered to.
+    pub channels: usize,
+}
+
+impl Context {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self {
+            sample_rate: 48000,
+            max_block_size: 1024,
+            channels: 2,
+        }
+    }
+}
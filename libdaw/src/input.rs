@@ -0,0 +1,24 @@
+//! Capturing audio to feed into nodes, from the system's input devices or
+//! from memory for testing.
+
+#[cfg(feature = "rodio")]
+pub mod cpal;
+pub mod memory;
+
+#[cfg(feature = "rodio")]
+pub use self::cpal::Cpal;
+pub use memory::Memory;
+
+pub use crate::output::Format;
+use crate::{sample::Sample, Result};
+
+/// Somewhere to capture audio from.
+pub trait Backend {
+    /// The format the backend captures at.
+    fn format(&self) -> Format;
+
+    /// Capture up to `count` frames, appending them to `frames` one sample
+    /// per frame.  Real-time backends block until the frames have been
+    /// captured.  Fewer frames than asked for means the input has ended.
+    fn read(&mut self, frames: &mut Vec<Sample>, count: usize) -> Result<()>;
+}
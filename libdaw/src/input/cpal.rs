@@ -0,0 +1,127 @@
+use super::{Backend, Format};
+use crate::{sample::Sample, Result};
+use ::rodio::cpal::{
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    BufferSize, FromSample, SampleFormat, SampleRate, SizedSample, Stream, StreamConfig,
+};
+use std::{
+    sync::mpsc::{sync_channel, Receiver, RecvTimeoutError, SyncSender},
+    time::Duration,
+};
+
+/// Frames buffered between the device and the reader.  The device drops
+/// whole frames if the reader falls this far behind.
+const BUFFERED_FRAMES: usize = 8192;
+
+/// How long a read waits for the device before giving up on it.
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Captures from the system's default input device, using cpal.
+pub struct Cpal {
+    format: Format,
+    frames: Receiver<Sample>,
+    errors: Receiver<String>,
+    _stream: Stream,
+}
+
+impl std::fmt::Debug for Cpal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cpal")
+            .field("format", &self.format)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Build an input stream of the device's native sample type.  The callback
+/// runs on the device's real-time thread, so it never blocks, and doesn't
+/// allocate for up to [`INLINE_CHANNELS`](crate::sample::INLINE_CHANNELS)
+/// channels.  Frames that don't fit in the buffer are dropped whole, so the
+/// channels stay in place.
+fn build<T>(
+    device: &::rodio::cpal::Device,
+    config: &StreamConfig,
+    frames: SyncSender<Sample>,
+    errors: SyncSender<String>,
+) -> Result<Stream>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let channels = config.channels as usize;
+    Ok(device.build_input_stream(
+        config,
+        move |data: &[T], _| {
+            for frame in data.chunks_exact(channels) {
+                let frame = frame
+                    .iter()
+                    .map(|&value| f32::from_sample_(value).into())
+                    .collect();
+                let _ = frames.try_send(frame);
+            }
+        },
+        move |error| {
+            let _ = errors.try_send(error.to_string());
+        },
+        None,
+    )?)
+}
+
+impl Cpal {
+    /// Open the default input device, capturing in the given format.
+    pub fn open(format: Format) -> Result<Self> {
+        let device = ::rodio::cpal::default_host()
+            .default_input_device()
+            .ok_or("there is no default input device")?;
+        let sample_format = device.default_input_config()?.sample_format();
+        let config = StreamConfig {
+            channels: format.channels,
+            sample_rate: SampleRate(format.sample_rate),
+            buffer_size: BufferSize::Default,
+        };
+        let (frame_sender, frames) = sync_channel(BUFFERED_FRAMES);
+        let (error_sender, errors) = sync_channel(1);
+        let stream = match sample_format {
+            SampleFormat::F32 => build::<f32>(&device, &config, frame_sender, error_sender)?,
+            SampleFormat::I16 => build::<i16>(&device, &config, frame_sender, error_sender)?,
+            SampleFormat::U16 => build::<u16>(&device, &config, frame_sender, error_sender)?,
+            SampleFormat::I32 => build::<i32>(&device, &config, frame_sender, error_sender)?,
+            other => return Err(format!("unsupported input sample format {other}").into()),
+        };
+        stream.play()?;
+        Ok(Self {
+            format,
+            frames,
+            errors,
+            _stream: stream,
+        })
+    }
+}
+
+impl Backend for Cpal {
+    fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Fails with the stream's error if it reported one, or if the device
+    /// sends nothing for a second.
+    fn read(&mut self, frames: &mut Vec<Sample>, count: usize) -> Result<()> {
+        for _ in 0..count {
+            if let Ok(error) = self.errors.try_recv() {
+                return Err(error.into());
+            }
+            match self.frames.recv_timeout(TIMEOUT) {
+                Ok(frame) => frames.push(frame),
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(match self.errors.try_recv() {
+                        Ok(error) => error.into(),
+                        Err(_) => "the input device stopped sending audio".into(),
+                    })
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err("the input stream was closed".into())
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use super::{Backend, Format};
+use crate::{sample::Sample, wav, Result};
+use std::path::Path;
+
+/// A fake device that plays back frames from memory, for deterministic
+/// tests without audio hardware.
+#[derive(Debug, Clone)]
+pub struct Memory {
+    format: Format,
+
+    /// The frames to capture, one sample per frame.
+    pub frames: Vec<Sample>,
+
+    /// The index of the next frame to be captured.
+    pub position: usize,
+}
+
+impl Memory {
+    pub fn new(format: Format, frames: Vec<Sample>) -> Self {
+        Self {
+            format,
+            frames,
+            position: 0,
+        }
+    }
+
+    /// Capture from the contents of a WAV file.
+    pub fn from_wav(path: impl AsRef<Path>) -> Result<Self> {
+        let wav = wav::read(path)?;
+        Ok(Self::new(
+            Format {
+                sample_rate: wav.sample_rate,
+                channels: wav.channels,
+            },
+            wav.samples,
+        ))
+    }
+}
+
+impl Backend for Memory {
+    fn format(&self) -> Format {
+        self.format
+    }
+
+    fn read(&mut self, frames: &mut Vec<Sample>, count: usize) -> Result<()> {
+        let end = self.frames.len().min(self.position + count);
+        frames.extend_from_slice(&self.frames[self.position..end]);
+        self.position = end;
+        Ok(())
+    }
+}
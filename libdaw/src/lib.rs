@@ -1,11 +1,18 @@
+pub mod context;
+pub mod input;
 pub mod metronome;
 pub mod nodes;
 pub mod notation;
+pub mod output;
 mod parse;
 pub mod pitch;
+pub mod realtime;
 pub mod sample;
 pub mod time;
+pub mod transport;
+pub mod wav;
 
+pub use context::Context;
 pub use sample::Sample;
 use std::fmt::Debug;
 
@@ -24,6 +31,32 @@ pub trait Node: Debug + Send {
         inputs: &'b [Sample],
         outputs: &'c mut Vec<Sample>,
     ) -> Result<()>;
+
+    /// Prepare the node to be processed under the given context, recomputing
+    /// anything that depends on the sample rate.  Nodes that contain other
+    /// nodes must prepare them as well.  The default does nothing, which is
+    /// correct for nodes that are independent of the sample rate.
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        let _ = context;
+        Ok(())
+    }
+
+    /// Return the node to its initial state, clearing any counters and
+    /// buffered audio.  Nodes that contain other nodes must reset them as
+    /// well.  The default does nothing, which is correct for stateless nodes.
+    fn reset(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Move the node to the given sample, so that the next processed sample
+    /// is that one.  Anything scheduled in time, like envelopes, tones, and
+    /// oscillator phases, is moved to where it would be; audio that depends
+    /// on earlier input, like delay buffers, is cleared.  The default resets
+    /// the node, which is correct for nodes that don't keep time.
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        let _ = sample;
+        self.reset()
+    }
 }
 
 impl Iterator for &mut dyn Node {
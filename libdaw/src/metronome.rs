@@ -1,6 +1,9 @@
 mod parse;
 
-use crate::{parse::IResult, time::Timestamp};
+use crate::{
+    parse::IResult,
+    time::{Duration, Timestamp},
+};
 use ordered_float::OrderedFloat;
 use std::{
     fmt,
@@ -252,7 +255,7 @@ struct CalculatedTempoInstruction {
 /// All beats before the first instruction use the first instruction's timings,
 /// and all instructions after the last instruction use the last instruction's
 /// timing.  The times are not extrapolated.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Metronome {
     instructions: Vec<CalculatedTempoInstruction>,
 }
@@ -293,6 +296,16 @@ impl Metronome {
         }
     }
 
+    /// The tempo instructions, in the order they apply.
+    pub fn tempo_instructions(&self) -> impl Iterator<Item = TempoInstruction> + '_ {
+        self.instructions
+            .iter()
+            .map(|instruction| TempoInstruction {
+                beat: Beat::new(instruction.beat).expect("valid"),
+                tempo: BeatsPerMinute::new(60.0 / instruction.seconds_per_beat).expect("valid"),
+            })
+    }
+
     pub fn beat_to_time(&self, beat: Beat) -> Timestamp {
         let instructions_len = self.instructions.len();
         let beat = beat.get();
@@ -333,6 +346,41 @@ impl Metronome {
         .expect("Time ended up negative or NaN")
     }
 
+    /// The beat at a given time, the inverse of [Self::beat_to_time].
+    pub fn time_to_beat(&self, time: Timestamp) -> Beat {
+        let instructions_len = self.instructions.len();
+        let time = time.seconds();
+
+        Beat::new(match instructions_len {
+            0 => time / (60.0 / 128.0),
+            1 => time / self.instructions[0].seconds_per_beat,
+            _ => {
+                // Times are in the same order as beats, but may repeat.
+                let index = self
+                    .instructions
+                    .partition_point(|instruction| instruction.time <= time);
+                match index {
+                    0 => time / self.instructions[0].seconds_per_beat,
+                    index if index == instructions_len => {
+                        let last = self.instructions[instructions_len - 1];
+                        last.beat + (time - last.time) / last.seconds_per_beat
+                    }
+                    index => {
+                        let a = self.instructions[index - 1];
+                        let b = self.instructions[index];
+                        Self::invert_integral(a, b, time)
+                    }
+                }
+            }
+        })
+        .expect("Beat ended up negative or NaN")
+    }
+
+    /// The length of time that `length` beats take, starting at `beat`.
+    pub fn beats_to_duration(&self, beat: Beat, length: Beat) -> Duration {
+        self.beat_to_time(beat + length) - self.beat_to_time(beat)
+    }
+
     // Integrate a beat between two endcap instructions to find its time.
     // b.beat must be > a.beat
     fn integrate_beat(
@@ -360,4 +408,44 @@ impl Metronome {
 
         a.time + time
     }
+
+    // Find the beat at a time between two endcap instructions, inverting
+    // integrate_beat.
+    // b.time must be > a.time
+    fn invert_integral(
+        a: CalculatedTempoInstruction,
+        b: CalculatedTempoInstruction,
+        time: f64,
+    ) -> f64 {
+        // With x = b - b1 and t = s - s1, integrate_beat reduces to
+        // t = m * x ^ 2 / 2 + spb1 * x
+        // x = (-spb1 + sqrt(spb1 ^ 2 + 2 * m * t)) / m
+        //   = 2 * t / (spb1 + sqrt(spb1 ^ 2 + 2 * m * t))
+        // The second form avoids dividing by zero for a constant tempo.
+        let spb1 = a.seconds_per_beat;
+        let m = (b.seconds_per_beat - spb1) / (b.beat - a.beat);
+        let t = time - a.time;
+        a.beat + 2.0 * t / (spb1 + (spb1.powi(2) + 2.0 * m * t).sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_to_beat_inverts_beat_to_time() {
+        let mut metronome = Metronome::new();
+        for (beat, tempo) in [(0.0, 120.0), (8.0, 60.0), (8.0, 200.0), (16.0, 90.0)] {
+            metronome.add_tempo_instruction(TempoInstruction {
+                beat: Beat::new(beat).unwrap(),
+                tempo: BeatsPerMinute::new(tempo).unwrap(),
+            });
+        }
+        for beat in [0.0, 1.5, 7.9, 8.0, 12.25, 16.0, 30.0] {
+            let time = metronome.beat_to_time(Beat::new(beat).unwrap());
+            let result = metronome.time_to_beat(time).get();
+            assert!((result - beat).abs() < 1e-9, "{beat} became {result}");
+        }
+    }
 }
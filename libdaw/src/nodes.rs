@@ -1,27 +1,55 @@
 pub mod add;
+pub mod bitcrusher;
+pub mod channels;
 pub mod constant_value;
+pub mod convolution;
 pub mod delay;
+pub mod delay_line;
 pub mod detune;
+pub mod dynamics;
 pub mod envelope;
 pub mod explode;
+pub mod feedback_delay;
 pub mod filters;
 pub mod gain;
+pub mod granular;
 pub mod graph;
 pub mod implode;
 pub mod instrument;
+pub mod karplus_strong;
+pub mod lfo;
+pub mod mixer;
+pub mod modulation;
 pub mod multiply;
 pub mod oscillators;
+pub mod oversampling;
 pub mod passthrough;
+pub mod resampler;
+pub mod reverb;
+pub mod tap;
+pub mod waveshaper;
 
 pub use add::Add;
+pub use bitcrusher::Bitcrusher;
 pub use constant_value::ConstantValue;
+pub use convolution::Convolution;
 pub use delay::Delay;
 pub use detune::Detune;
 pub use envelope::Envelope;
 pub use explode::Explode;
+pub use feedback_delay::FeedbackDelay;
 pub use gain::Gain;
+pub use granular::Granular;
 pub use graph::Graph;
 pub use implode::Implode;
 pub use instrument::Instrument;
+pub use karplus_strong::KarplusStrong;
+pub use lfo::Lfo;
+pub use mixer::Mixer;
 pub use multiply::Multiply;
+pub use oversampling::Oversample;
 pub use passthrough::Passthrough;
+pub use resampler::Resampler;
+pub use reverb::Reverb;
+pub use tap::Tap;
+pub use waveshaper::Waveshaper;
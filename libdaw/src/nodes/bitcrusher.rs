@@ -0,0 +1,68 @@
+use crate::{sample::Sample, Context, Node, Result};
+
+/// Reduces the bit depth and sample rate of each input stream.
+#[derive(Debug)]
+pub struct Bitcrusher {
+    /// The bit depth to quantize to.  May be fractional.
+    pub bits: f64,
+
+    /// The reduced sample rate, in Hz.  Each sample is held until the next
+    /// one at this rate.
+    pub rate: f64,
+
+    sample_rate: f64,
+
+    /// Ramps from 0 to 1 per held sample.
+    ramp: f64,
+    held: Vec<Sample>,
+}
+
+impl Bitcrusher {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            bits: 8.0,
+            rate: sample_rate as f64 / 4.0,
+            sample_rate: sample_rate as f64,
+            ramp: 1.0,
+            held: Vec::new(),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+}
+
+impl Node for Bitcrusher {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        if self.ramp >= 1.0 || self.held.len() != inputs.len() {
+            self.ramp = self.ramp.fract();
+            let levels = 2.0f64.powf(self.bits - 1.0).max(1.0);
+            self.held.clear();
+            self.held.extend(inputs.iter().map(|input| {
+                input
+                    .iter()
+                    .map(|channel| (channel * levels).round() / levels)
+                    .collect()
+            }));
+        }
+        self.ramp += self.rate / self.sample_rate;
+        outputs.extend_from_slice(&self.held);
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.sample_rate = context.sample_rate as f64;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.ramp = 1.0;
+        self.held.clear();
+        Ok(())
+    }
+}
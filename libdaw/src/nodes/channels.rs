@@ -0,0 +1,13 @@
+//! Panning and channel layout utilities.
+
+pub mod matrix;
+pub mod mono;
+pub mod pan;
+pub mod stereo_width;
+pub mod upmix;
+
+pub use matrix::Matrix;
+pub use mono::Mono;
+pub use pan::{Pan, PanLaw};
+pub use stereo_width::StereoWidth;
+pub use upmix::Upmix;
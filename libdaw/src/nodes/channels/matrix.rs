@@ -0,0 +1,52 @@
+use crate::{sample::Sample, Node, Result};
+
+/// Routes input channels to output channels with a matrix of gains.
+///
+/// The channels of all input streams are concatenated in order, and a single
+/// output stream is produced.
+#[derive(Debug, Default, Clone)]
+pub struct Matrix {
+    /// One row per output channel, each holding the gain of every input
+    /// channel.  Missing gains are zero.
+    pub gains: Vec<Vec<f64>>,
+}
+
+impl Matrix {
+    pub fn new(gains: Vec<Vec<f64>>) -> Self {
+        Self { gains }
+    }
+
+    /// Pass `channels` channels through unchanged.
+    pub fn identity(channels: usize) -> Self {
+        Self::new(
+            (0..channels)
+                .map(|output| {
+                    (0..channels)
+                        .map(|input| if input == output { 1.0 } else { 0.0 })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Node for Matrix {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        outputs.push(
+            self.gains
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .zip(inputs.iter().flatten())
+                        .map(|(gain, channel)| gain * channel)
+                        .sum()
+                })
+                .collect(),
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{sample::Sample, Node, Result};
+
+/// Sums all channels of each input stream into a single channel.
+#[derive(Debug, Clone, Copy)]
+pub struct Mono {
+    /// Average the channels rather than summing them, keeping the level of
+    /// correlated channels unchanged.
+    pub average: bool,
+}
+
+impl Default for Mono {
+    fn default() -> Self {
+        Self { average: true }
+    }
+}
+
+impl Node for Mono {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        outputs.extend(inputs.iter().map(|input| {
+            let sum: f64 = input.iter().sum();
+            if self.average && !input.is_empty() {
+                sum / input.len() as f64
+            } else {
+                sum
+            }
+            .into()
+        }));
+        Ok(())
+    }
+}
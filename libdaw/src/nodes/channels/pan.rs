@@ -0,0 +1,122 @@
+use crate::{sample::Sample, Node, Result};
+use std::f64::consts::FRAC_PI_2;
+
+/// How the gain of each side changes with the pan position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PanLaw {
+    /// Gains fall linearly, -6 dB at the center.
+    Linear,
+
+    /// The summed power is constant, -3 dB at the center.
+    #[default]
+    ConstantPower,
+
+    /// Halfway between linear and constant power, -4.5 dB at the center.
+    Compromise,
+}
+
+impl PanLaw {
+    /// The left and right gains for a position from -1 (left) to 1 (right).
+    pub fn gains(self, position: f64) -> (f64, f64) {
+        let right = (position.clamp(-1.0, 1.0) + 1.0) / 2.0;
+        let left = 1.0 - right;
+        match self {
+            PanLaw::Linear => (left, right),
+            PanLaw::ConstantPower => ((right * FRAC_PI_2).cos(), (right * FRAC_PI_2).sin()),
+            PanLaw::Compromise => (
+                (left * (right * FRAC_PI_2).cos()).sqrt(),
+                (right * (right * FRAC_PI_2).sin()).sqrt(),
+            ),
+        }
+    }
+
+    /// Pan a sample.  A mono sample is spread into two channels.  A stereo
+    /// sample is balanced instead, kept at unity at the center with the
+    /// opposite side fading out along the law.
+    pub fn apply(self, position: f64, input: &Sample) -> Sample {
+        let (left, right) = self.gains(position);
+        match input.channels.as_slice() {
+            [] => Sample::zeroed(2),
+            &[mono] => [mono * left, mono * right].into(),
+            &[l, r, ..] => {
+                let (center, _) = self.gains(0.0);
+                [l * (left / center).min(1.0), r * (right / center).min(1.0)].into()
+            }
+        }
+    }
+}
+
+/// Pans the first input stream.
+///
+/// A mono input is spread into two channels.  A stereo input is balanced
+/// instead, kept at unity at the center with the opposite side fading out
+/// along the law.  The second input stream, if present, overrides the
+/// position.
+#[derive(Debug, Default)]
+pub struct Pan {
+    /// The position from -1 (left) to 1 (right).
+    pub position: f64,
+
+    pub law: PanLaw,
+}
+
+impl Pan {
+    pub fn new(position: f64, law: PanLaw) -> Self {
+        Self { position, law }
+    }
+}
+
+impl Node for Pan {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let Some(input) = inputs.first() else {
+            return Ok(());
+        };
+        let position = inputs
+            .get(1)
+            .and_then(|input| input.first().copied())
+            .unwrap_or(self.position);
+        outputs.push(self.law.apply(position, input));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    fn pan(position: f64, input: Sample) -> Vec<f64> {
+        let mut outputs = Vec::new();
+        Pan::new(position, PanLaw::ConstantPower)
+            .process(&[input], &mut outputs)
+            .unwrap();
+        outputs[0].channels.to_vec()
+    }
+
+    fn assert_close(actual: Vec<f64>, expected: [f64; 2]) {
+        for (actual, expected) in actual.into_iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-12, "{actual} != {expected}");
+        }
+    }
+
+    #[test]
+    fn spreads_mono() {
+        assert_close(pan(0.0, Sample::from(1.0)), [FRAC_1_SQRT_2, FRAC_1_SQRT_2]);
+    }
+
+    /// Unlike a mono input, a centered stereo input is left at unity.
+    #[test]
+    fn balances_stereo() {
+        let stereo = || Sample::from(vec![1.0, 1.0]);
+        assert_close(pan(0.0, stereo()), [1.0, 1.0]);
+        assert_close(pan(-1.0, stereo()), [1.0, 0.0]);
+        assert_close(
+            pan(0.5, stereo()),
+            [(0.75 * FRAC_PI_2).cos() / FRAC_1_SQRT_2, 1.0],
+        );
+    }
+}
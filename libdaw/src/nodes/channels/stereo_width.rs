@@ -0,0 +1,63 @@
+use crate::{sample::Sample, Node, Result};
+
+/// Adjusts the width and balance of a stereo signal through mid-side
+/// processing.
+///
+/// A mono input is treated as stereo with identical sides.  The second and
+/// third input streams, if present, override the balance and width.
+#[derive(Debug)]
+pub struct StereoWidth {
+    /// The side level relative to the mid.  0 is mono, 1 leaves the signal
+    /// unchanged, and higher values widen it.
+    pub width: f64,
+
+    /// Attenuates the opposite side, from -1 (left only) to 1 (right only).
+    pub balance: f64,
+}
+
+impl Default for StereoWidth {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            balance: 0.0,
+        }
+    }
+}
+
+impl StereoWidth {
+    pub fn new(width: f64, balance: f64) -> Self {
+        Self { width, balance }
+    }
+}
+
+impl Node for StereoWidth {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let Some(input) = inputs.first() else {
+            return Ok(());
+        };
+        let control = |stream: usize, default: f64| {
+            inputs
+                .get(stream)
+                .and_then(|input| input.first().copied())
+                .unwrap_or(default)
+        };
+        let balance = control(1, self.balance).clamp(-1.0, 1.0);
+        let width = control(2, self.width);
+        let (left, right) = match input.channels.as_slice() {
+            [] => (0.0, 0.0),
+            &[mono] => (mono, mono),
+            &[left, right, ..] => (left, right),
+        };
+        let mid = (left + right) / 2.0;
+        let side = (left - right) / 2.0 * width;
+        outputs.push(Sample::from([
+            (mid + side) * (1.0 - balance.max(0.0)),
+            (mid - side) * (1.0 + balance.min(0.0)),
+        ]));
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{sample::Sample, Node, Result};
+
+/// Expands each input stream to a fixed number of channels, repeating the
+/// input channels in order.  A mono input is copied to every channel.
+#[derive(Debug, Clone, Copy)]
+pub struct Upmix {
+    pub channels: usize,
+}
+
+impl Upmix {
+    pub fn new(channels: usize) -> Self {
+        Self { channels }
+    }
+}
+
+impl Node for Upmix {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        outputs.extend(inputs.iter().map(|input| {
+            if input.is_empty() {
+                Sample::zeroed(self.channels)
+            } else {
+                input.iter().copied().cycle().take(self.channels).collect()
+            }
+        }));
+        Ok(())
+    }
+}
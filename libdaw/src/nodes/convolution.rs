@@ -0,0 +1,464 @@
+use crate::{sample::Sample, wav, Context, Node, Result};
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+use std::{fmt, path::Path, sync::Arc};
+
+/// Impulse responses this long or shorter are convolved directly, with no
+/// latency.
+const DIRECT_LIMIT: usize = 64;
+
+/// A single convolution from an input channel through an impulse response
+/// channel into an output channel.
+#[derive(Debug, Clone, Copy)]
+struct Route {
+    input: usize,
+    kernel: usize,
+    output: usize,
+}
+
+/// Direct-form convolution state.
+#[derive(Debug)]
+struct Direct {
+    /// Per impulse response channel.
+    kernels: Vec<Box<[f64]>>,
+
+    /// Input history per input channel, as ring buffers.
+    history: Vec<Box<[f64]>>,
+    write: usize,
+}
+
+/// Uniformly partitioned overlap-save convolution state.
+struct Partitioned {
+    block_size: usize,
+    forward: Arc<dyn Fft<f64>>,
+    inverse: Arc<dyn Fft<f64>>,
+
+    /// Partition spectra per impulse response channel.
+    kernels: Vec<Vec<Box<[Complex<f64>]>>>,
+
+    /// The last two blocks of input per input channel.
+    inputs: Vec<Box<[f64]>>,
+
+    /// Frequency-domain delay line of past input spectra per input channel,
+    /// as ring buffers of the same length as the kernels.
+    spectra: Vec<Vec<Box<[Complex<f64>]>>>,
+    head: usize,
+
+    /// The block of output currently being played per output channel.
+    outputs: Vec<Box<[f64]>>,
+    position: usize,
+
+    accumulator: Box<[Complex<f64>]>,
+    scratch: Box<[Complex<f64>]>,
+}
+
+impl fmt::Debug for Partitioned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Partitioned")
+            .field("block_size", &self.block_size)
+            .field("partitions", &self.kernels.first().map(Vec::len))
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+enum Engine {
+    Direct(Direct),
+    Partitioned(Partitioned),
+}
+
+/// Convolves its input with an impulse response, such as the recording of a
+/// room or a speaker cabinet.
+///
+/// Long impulse responses use uniformly partitioned FFT convolution, which
+/// delays the output by one block.  Short ones are convolved directly with
+/// no latency.
+///
+/// The channels of the impulse response determine the routing:
+///
+/// * 1 channel: each input channel is convolved with it separately.
+/// * 2 channels: the left and right input channels are each convolved with
+///   the matching impulse response channel.
+/// * 4 channels: true stereo, with the channels being left to left, left to
+///   right, right to left, and right to right.
+///
+/// For stereo and true stereo, a mono input is used for both sides.
+#[derive(Debug)]
+pub struct Convolution {
+    engine: Engine,
+    routes: Vec<Route>,
+    kernel_channels: usize,
+    input_channels: usize,
+    output_channels: usize,
+
+    /// Kept as given, so the convolution can be described and rebuilt.
+    impulse_response: Box<[Sample]>,
+    block_size: usize,
+
+    /// The sample rate of the impulse response, if it is known.
+    sample_rate: Option<u32>,
+}
+
+impl Convolution {
+    /// Construct a convolution with the given impulse response, one sample
+    /// per frame.  `block_size` is the partition size, which is also the
+    /// latency in samples for long impulse responses.
+    pub fn new(impulse_response: &[Sample], block_size: usize) -> Result<Self> {
+        if block_size == 0 {
+            return Err("block_size must be positive".into());
+        }
+        let kernel_channels = impulse_response
+            .iter()
+            .map(|sample| sample.len())
+            .max()
+            .unwrap_or(0);
+        let (routes, input_channels, output_channels) = match kernel_channels {
+            1 => (Vec::new(), 0, 0),
+            2 => (
+                vec![
+                    Route {
+                        input: 0,
+                        kernel: 0,
+                        output: 0,
+                    },
+                    Route {
+                        input: 1,
+                        kernel: 1,
+                        output: 1,
+                    },
+                ],
+                2,
+                2,
+            ),
+            4 => (
+                vec![
+                    Route {
+                        input: 0,
+                        kernel: 0,
+                        output: 0,
+                    },
+                    Route {
+                        input: 0,
+                        kernel: 1,
+                        output: 1,
+                    },
+                    Route {
+                        input: 1,
+                        kernel: 2,
+                        output: 0,
+                    },
+                    Route {
+                        input: 1,
+                        kernel: 3,
+                        output: 1,
+                    },
+                ],
+                2,
+                2,
+            ),
+            0 => return Err("impulse response may not be empty".into()),
+            channels => {
+                return Err(format!(
+                    "impulse response must have 1, 2, or 4 channels, not {channels}"
+                )
+                .into())
+            }
+        };
+        let kernels: Vec<Vec<f64>> = (0..kernel_channels)
+            .map(|channel| {
+                impulse_response
+                    .iter()
+                    .map(|sample| sample.get(channel).copied().unwrap_or(0.0))
+                    .collect()
+            })
+            .collect();
+
+        let engine = if impulse_response.len() <= DIRECT_LIMIT {
+            Engine::Direct(Direct {
+                kernels: kernels.into_iter().map(Into::into).collect(),
+                history: Vec::new(),
+                write: 0,
+            })
+        } else {
+            let mut planner = FftPlanner::new();
+            let fft_size = block_size * 2;
+            let forward = planner.plan_fft_forward(fft_size);
+            let inverse = planner.plan_fft_inverse(fft_size);
+            let kernels = kernels
+                .iter()
+                .map(|kernel| {
+                    kernel
+                        .chunks(block_size)
+                        .map(|partition| {
+                            let mut spectrum = vec![Complex::default(); fft_size];
+                            for (bin, &value) in spectrum.iter_mut().zip(partition) {
+                                bin.re = value;
+                            }
+                            forward.process(&mut spectrum);
+                            spectrum.into_boxed_slice()
+                        })
+                        .collect()
+                })
+                .collect();
+            Engine::Partitioned(Partitioned {
+                block_size,
+                kernels,
+                inputs: Vec::new(),
+                spectra: Vec::new(),
+                head: 0,
+                outputs: Vec::new(),
+                position: 0,
+                accumulator: vec![Complex::default(); fft_size].into(),
+                scratch: vec![Complex::default(); forward.get_inplace_scratch_len()].into(),
+                forward,
+                inverse,
+            })
+        };
+        Ok(Self {
+            engine,
+            routes,
+            kernel_channels,
+            input_channels,
+            output_channels,
+            impulse_response: impulse_response.into(),
+            block_size,
+            sample_rate: None,
+        })
+    }
+
+    /// Load the impulse response from a WAV file, which must match the
+    /// sample rate.
+    pub fn from_wav(sample_rate: u32, path: impl AsRef<Path>, block_size: usize) -> Result<Self> {
+        let wav = wav::read(path)?;
+        if wav.sample_rate != sample_rate {
+            return Err(format!(
+                "impulse response sample rate {} does not match {sample_rate}",
+                wav.sample_rate
+            )
+            .into());
+        }
+        let mut convolution = Self::new(&wav.samples, block_size)?;
+        convolution.sample_rate = Some(wav.sample_rate);
+        Ok(convolution)
+    }
+
+    /// The delay added by the convolution, in samples.
+    pub fn latency(&self) -> usize {
+        match &self.engine {
+            Engine::Direct(_) => 0,
+            Engine::Partitioned(partitioned) => partitioned.block_size,
+        }
+    }
+
+    pub fn impulse_response(&self) -> &[Sample] {
+        &self.impulse_response
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Grow the routing and state to fit the input channels, for mono
+    /// impulse responses, which route each input channel separately.
+    fn resize(&mut self, channels: usize) {
+        if self.kernel_channels == 1 && channels > self.input_channels {
+            self.routes
+                .extend((self.input_channels..channels).map(|channel| Route {
+                    input: channel,
+                    kernel: 0,
+                    output: channel,
+                }));
+            self.input_channels = channels;
+            self.output_channels = channels;
+        }
+        let inputs = self.input_channels;
+        let outputs = self.output_channels;
+        match &mut self.engine {
+            Engine::Direct(direct) => {
+                let length = direct.kernels[0].len();
+                direct
+                    .history
+                    .resize_with(inputs, || vec![0.0; length].into());
+            }
+            Engine::Partitioned(partitioned) => {
+                let block_size = partitioned.block_size;
+                let partitions = partitioned.kernels[0].len();
+                partitioned
+                    .inputs
+                    .resize_with(inputs, || vec![0.0; block_size * 2].into());
+                partitioned.spectra.resize_with(inputs, || {
+                    vec![vec![Complex::default(); block_size * 2].into(); partitions]
+                });
+                partitioned
+                    .outputs
+                    .resize_with(outputs, || vec![0.0; block_size].into());
+            }
+        }
+    }
+}
+
+impl Partitioned {
+    /// Convolve the completed input block, filling the next output block.
+    fn process_block(&mut self, routes: &[Route]) {
+        let block_size = self.block_size;
+        let partitions = self.kernels[0].len();
+        self.head = (self.head + 1) % partitions;
+        for (input, spectra) in self.inputs.iter_mut().zip(&mut self.spectra) {
+            let spectrum = &mut spectra[self.head];
+            for (bin, &value) in spectrum.iter_mut().zip(input.iter()) {
+                *bin = Complex::new(value, 0.0);
+            }
+            self.forward
+                .process_with_scratch(spectrum, &mut self.scratch);
+            // Slide the current block into the previous block's place.
+            input.copy_within(block_size.., 0);
+        }
+
+        let scale = 1.0 / (block_size * 2) as f64;
+        for (channel, output) in self.outputs.iter_mut().enumerate() {
+            self.accumulator.fill(Complex::default());
+            for route in routes.iter().filter(|route| route.output == channel) {
+                let spectra = &self.spectra[route.input];
+                for (partition, kernel) in self.kernels[route.kernel].iter().enumerate() {
+                    let spectrum = &spectra[(self.head + partitions - partition) % partitions];
+                    for ((accumulator, &x), &h) in self
+                        .accumulator
+                        .iter_mut()
+                        .zip(spectrum.iter())
+                        .zip(kernel.iter())
+                    {
+                        *accumulator += x * h;
+                    }
+                }
+            }
+            self.inverse
+                .process_with_scratch(&mut self.accumulator, &mut self.scratch);
+            // Overlap-save: only the second half is free of wraparound.
+            for (output, value) in output.iter_mut().zip(&self.accumulator[block_size..]) {
+                *output = value.re * scale;
+            }
+        }
+    }
+}
+
+impl Node for Convolution {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let Some(input) = inputs.first() else {
+            return Ok(());
+        };
+        self.resize(input.len());
+        let channel = |channel: usize| {
+            input
+                .get(channel)
+                .or_else(|| input.first())
+                .copied()
+                .unwrap_or(0.0)
+        };
+        let mut output = Sample::zeroed(self.output_channels);
+        match &mut self.engine {
+            Engine::Direct(direct) => {
+                let length = direct.kernels[0].len();
+                for (index, history) in direct.history.iter_mut().enumerate() {
+                    history[direct.write] = channel(index);
+                }
+                for route in &self.routes {
+                    let history = &direct.history[route.input];
+                    output[route.output] += direct.kernels[route.kernel]
+                        .iter()
+                        .enumerate()
+                        .map(|(delay, &h)| h * history[(direct.write + length - delay) % length])
+                        .sum::<f64>();
+                }
+                direct.write = (direct.write + 1) % length;
+            }
+            Engine::Partitioned(partitioned) => {
+                let block_size = partitioned.block_size;
+                let position = partitioned.position;
+                for (index, input) in partitioned.inputs.iter_mut().enumerate() {
+                    input[block_size + position] = channel(index);
+                }
+                for (value, block) in output.iter_mut().zip(&partitioned.outputs) {
+                    *value = block[position];
+                }
+                partitioned.position += 1;
+                if partitioned.position == block_size {
+                    partitioned.position = 0;
+                    partitioned.process_block(&self.routes);
+                }
+            }
+        }
+        outputs.push(output);
+        Ok(())
+    }
+
+    /// The impulse response is not resampled, so this fails if it was
+    /// loaded at a different sample rate.
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        match self.sample_rate {
+            Some(sample_rate) if sample_rate != context.sample_rate => Err(format!(
+                "impulse response sample rate {sample_rate} does not match {}",
+                context.sample_rate
+            )
+            .into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        // The buffers are recreated, zeroed, on the next sample.
+        match &mut self.engine {
+            Engine::Direct(direct) => {
+                direct.history.clear();
+                direct.write = 0;
+            }
+            Engine::Partitioned(partitioned) => {
+                partitioned.inputs.clear();
+                partitioned.spectra.clear();
+                partitioned.outputs.clear();
+                partitioned.head = 0;
+                partitioned.position = 0;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(convolution: &mut Convolution, input: &[f64]) -> Vec<f64> {
+        input
+            .iter()
+            .map(|&value| {
+                let mut outputs = Vec::new();
+                convolution.process(&[value.into()], &mut outputs).unwrap();
+                outputs[0][0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn partitioned_matches_direct() {
+        let impulse_response: Vec<Sample> = (0..200)
+            .map(|i| (((i * 7919) % 13) as f64 / 6.0 - 1.0).into())
+            .collect();
+        let input: Vec<f64> = (0..1000)
+            .map(|i| ((i * 31) % 17) as f64 / 8.0 - 1.0)
+            .collect();
+        let mut convolution = Convolution::new(&impulse_response, 32).unwrap();
+        assert_eq!(convolution.latency(), 32);
+        let output = run(&mut convolution, &input);
+        for (n, &value) in output.iter().enumerate().skip(32) {
+            let m = n - 32;
+            let expected: f64 = (0..=m.min(199))
+                .map(|j| impulse_response[j][0] * input[m - j])
+                .sum();
+            assert!((value - expected).abs() < 1e-9);
+        }
+    }
+}
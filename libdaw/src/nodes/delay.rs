@@ -1,4 +1,4 @@
-use crate::{sample::Sample, time::Duration, Node, Result};
+use crate::{sample::Sample, time::Duration, Context, Node, Result};
 use std::collections::VecDeque;
 
 #[derive(Debug)]
@@ -13,18 +13,29 @@ type Buffer = VecDeque<DelaySample>;
 pub struct Delay {
     buffers: Vec<Buffer>,
     sample: u64,
+    sample_rate: u32,
+    duration: Duration,
     delay: u64,
 }
 
 impl Delay {
     pub fn new(sample_rate: u32, delay: Duration) -> Self {
-        let delay = (delay.seconds() * sample_rate as f64) as u64;
         Self {
             buffers: Default::default(),
             sample: Default::default(),
-            delay,
+            sample_rate,
+            duration: delay,
+            delay: (delay.seconds() * sample_rate as f64) as u64,
         }
     }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn delay(&self) -> Duration {
+        self.duration
+    }
 }
 
 impl Node for Delay {
@@ -66,4 +77,20 @@ impl Node for Delay {
         }
         Ok(())
     }
+    /// Changing the sample rate drops any audio that is still buffered.
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.sample_rate = context.sample_rate;
+        let delay = (self.duration.seconds() * context.sample_rate as f64) as u64;
+        if delay != self.delay {
+            self.delay = delay;
+            self.buffers.clear();
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.buffers.clear();
+        self.sample = 0;
+        Ok(())
+    }
 }
@@ -0,0 +1,123 @@
+//! A fractional delay line, as a building block for delay-based nodes.
+
+/// How to read between samples of a delay line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    #[default]
+    Linear,
+
+    /// Four-point cubic Hermite interpolation.
+    Cubic,
+
+    /// First-order all-pass interpolation.  This has a flat frequency
+    /// response, but smears when the delay time changes quickly, so it is
+    /// best for fixed or slowly changing delays.
+    AllPass,
+}
+
+/// A ring buffer of single-channel values that can be read at fractional
+/// delays.
+#[derive(Debug, Clone)]
+pub struct DelayLine {
+    buffer: Box<[f64]>,
+    write: usize,
+    allpass_output: f64,
+}
+
+impl DelayLine {
+    /// Make a delay line able to delay up to `max_delay` samples.
+    pub fn new(max_delay: usize) -> Self {
+        Self {
+            // Room for the cubic interpolation's extra lookahead point.
+            buffer: vec![0.0; max_delay + 3].into(),
+            write: 0,
+            allpass_output: 0.0,
+        }
+    }
+
+    /// The longest delay this line can be read at.
+    pub fn max_delay(&self) -> f64 {
+        (self.buffer.len() - 3) as f64
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.buffer[self.write] = value;
+        self.write = (self.write + 1) % self.buffer.len();
+    }
+
+    /// Get the value pushed `delay` samples ago, where 1 is the most recent
+    /// push.
+    fn get(&self, delay: usize) -> f64 {
+        let len = self.buffer.len();
+        self.buffer[(self.write + len - delay) % len]
+    }
+
+    /// Read the line `delay` samples back, where 1 is the most recently
+    /// pushed value.  The delay is clamped to between 1 and `max_delay`.
+    pub fn read(&mut self, delay: f64, interpolation: Interpolation) -> f64 {
+        let delay = delay.clamp(1.0, self.max_delay());
+        let index = delay.floor() as usize;
+        let fraction = delay - index as f64;
+        match interpolation {
+            Interpolation::Linear => {
+                let a = self.get(index);
+                let b = self.get(index + 1);
+                a + (b - a) * fraction
+            }
+            Interpolation::Cubic => {
+                let y0 = self.get(index.saturating_sub(1).max(1));
+                let y1 = self.get(index);
+                let y2 = self.get(index + 1);
+                let y3 = self.get(index + 2);
+                let c1 = 0.5 * (y2 - y0);
+                let c2 = y0 - 2.5 * y1 + 2.0 * y2 - 0.5 * y3;
+                let c3 = 0.5 * (y3 - y0) + 1.5 * (y1 - y2);
+                ((c3 * fraction + c2) * fraction + c1) * fraction + y1
+            }
+            Interpolation::AllPass => {
+                let coefficient = (1.0 - fraction) / (1.0 + fraction);
+                let output = coefficient * self.get(index) + self.get(index + 1)
+                    - coefficient * self.allpass_output;
+                self.allpass_output = output;
+                output
+            }
+        }
+    }
+
+    /// Silence the line.
+    pub fn clear(&mut self) {
+        self.buffer.fill(0.0);
+        self.allpass_output = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp_line() -> DelayLine {
+        let mut line = DelayLine::new(16);
+        for i in 0..16 {
+            line.push(i as f64);
+        }
+        line
+    }
+
+    #[test]
+    fn integer_delay() {
+        for interpolation in [Interpolation::Linear, Interpolation::Cubic] {
+            let mut line = ramp_line();
+            assert_eq!(line.read(1.0, interpolation), 15.0);
+            assert_eq!(line.read(4.0, interpolation), 12.0);
+        }
+    }
+
+    #[test]
+    fn fractional_delay() {
+        // Both linear and cubic interpolation are exact on a ramp.
+        for interpolation in [Interpolation::Linear, Interpolation::Cubic] {
+            let mut line = ramp_line();
+            assert!((line.read(4.25, interpolation) - 11.75).abs() < 1e-12);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Node, Result, Sample};
+use crate::{Node, Result};
 
 /// Detunes the input frequency by the amount given.  If no input comes in, just
 /// passes out the detune multiplier.
@@ -26,9 +26,7 @@ impl Node for Detune {
             .and_then(|input| input.get(0).cloned())
             .unwrap_or(1.0);
         let detune_pow2 = 2.0f64.powf(self.detune);
-        outputs.push(Sample {
-            channels: vec![input * detune_pow2],
-        });
+        outputs.push((input * detune_pow2).into());
         Ok(())
     }
 }
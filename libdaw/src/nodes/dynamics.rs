@@ -0,0 +1,104 @@
+//! Dynamics processors.
+//!
+//! Each of these takes the audio as the first input stream.  A second input
+//! stream, if present, is the sidechain: its level is detected instead of the
+//! audio's, so one signal can duck or gate another.  All channels are linked,
+//! with the loudest channel driving the gain of every channel.
+
+pub mod compressor;
+pub mod gate;
+pub mod limiter;
+
+pub use compressor::Compressor;
+pub use gate::Gate;
+pub use limiter::Limiter;
+
+use crate::{sample::Sample, time::Duration};
+
+/// The lowest level reported in decibels, standing in for silence.
+const FLOOR: f64 = -200.0;
+
+/// The averaging time of RMS detection, in seconds.
+const RMS_WINDOW: f64 = 0.01;
+
+/// How the level of the signal is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Detection {
+    /// The instantaneous peak of the signal.
+    Peak,
+
+    /// The root mean square of the signal over a short window.
+    #[default]
+    Rms,
+}
+
+/// Convert a linear amplitude to decibels.
+pub fn to_decibels(amplitude: f64) -> f64 {
+    (20.0 * amplitude.abs().log10()).max(FLOOR)
+}
+
+/// Convert decibels to a linear amplitude.
+pub fn from_decibels(decibels: f64) -> f64 {
+    10.0f64.powf(decibels / 20.0)
+}
+
+/// The one-pole smoothing coefficient that moves about 63% of the way to the
+/// target in the given time.
+fn coefficient(time: Duration, sample_rate: f64) -> f64 {
+    let samples = time.seconds() * sample_rate;
+    if samples > 0.0 {
+        (-1.0 / samples).exp()
+    } else {
+        0.0
+    }
+}
+
+/// Tracks the level of the detection signal.
+#[derive(Debug)]
+struct Detector {
+    sample_rate: f64,
+    mean_square: f64,
+}
+
+impl Detector {
+    fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate: sample_rate as f64,
+            mean_square: 0.0,
+        }
+    }
+
+    /// Measure the next sample, returning its level in decibels.
+    fn level(&mut self, detection: Detection, input: &Sample) -> f64 {
+        let peak = input
+            .iter()
+            .fold(0.0f64, |peak, channel| peak.max(channel.abs()));
+        match detection {
+            Detection::Peak => to_decibels(peak),
+            Detection::Rms => {
+                let coefficient = coefficient(
+                    Duration::from_seconds(RMS_WINDOW).expect("valid"),
+                    self.sample_rate,
+                );
+                self.mean_square =
+                    coefficient * self.mean_square + (1.0 - coefficient) * peak * peak;
+                to_decibels(self.mean_square.sqrt())
+            }
+        }
+    }
+}
+
+/// Moves a gain in decibels toward its target, with separate times for
+/// falling and rising.
+#[derive(Debug, Default)]
+struct Ballistics {
+    gain: f64,
+}
+
+impl Ballistics {
+    fn next(&mut self, target: f64, falling: f64, rising: f64) -> f64 {
+        let coefficient = if target < self.gain { falling } else { rising };
+        self.gain = coefficient * self.gain + (1.0 - coefficient) * target;
+        self.gain
+    }
+}
@@ -0,0 +1,100 @@
+use super::{coefficient, from_decibels, Ballistics, Detection, Detector};
+use crate::{sample::Sample, time::Duration, Context, Node, Result};
+
+/// A feed-forward compressor, turning down the signal as it rises above the
+/// threshold.
+#[derive(Debug)]
+pub struct Compressor {
+    /// The level at which compression begins, in decibels.
+    pub threshold: f64,
+
+    /// How many decibels of input above the threshold produce one decibel of
+    /// output.
+    pub ratio: f64,
+
+    /// The width of the soft knee around the threshold, in decibels.  Zero
+    /// is a hard knee.
+    pub knee: f64,
+
+    /// How quickly the gain falls as the level rises.
+    pub attack: Duration,
+
+    /// How quickly the gain recovers as the level falls.
+    pub release: Duration,
+
+    /// Gain applied after compression, in decibels.
+    pub makeup: f64,
+
+    pub detection: Detection,
+
+    sample_rate: f64,
+    detector: Detector,
+    ballistics: Ballistics,
+}
+
+impl Compressor {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            threshold: -20.0,
+            ratio: 4.0,
+            knee: 6.0,
+            attack: Duration::from_seconds(0.01).expect("valid"),
+            release: Duration::from_seconds(0.1).expect("valid"),
+            makeup: 0.0,
+            detection: Detection::default(),
+            sample_rate: sample_rate as f64,
+            detector: Detector::new(sample_rate),
+            ballistics: Ballistics::default(),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    /// The static gain change for an input level, both in decibels.
+    pub fn gain(&self, level: f64) -> f64 {
+        let over = level - self.threshold;
+        let slope = 1.0 / self.ratio.max(1.0) - 1.0;
+        if 2.0 * over <= -self.knee {
+            0.0
+        } else if 2.0 * over.abs() < self.knee {
+            slope * (over + self.knee / 2.0).powi(2) / (2.0 * self.knee)
+        } else {
+            slope * over
+        }
+    }
+}
+
+impl Node for Compressor {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let Some(input) = inputs.first() else {
+            return Ok(());
+        };
+        let sidechain = inputs.get(1).unwrap_or(input);
+        let level = self.detector.level(self.detection, sidechain);
+        let gain = self.ballistics.next(
+            self.gain(level),
+            coefficient(self.attack, self.sample_rate),
+            coefficient(self.release, self.sample_rate),
+        );
+        outputs.push(input * from_decibels(gain + self.makeup));
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.sample_rate = context.sample_rate as f64;
+        self.detector.sample_rate = self.sample_rate;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.detector.mean_square = 0.0;
+        self.ballistics = Ballistics::default();
+        Ok(())
+    }
+}
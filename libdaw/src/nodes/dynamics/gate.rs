@@ -0,0 +1,118 @@
+use super::{coefficient, from_decibels, Ballistics, Detection, Detector};
+use crate::{sample::Sample, time::Duration, Context, Node, Result};
+
+/// A noise gate or downward expander, turning down the signal as it falls
+/// below the threshold.
+#[derive(Debug)]
+pub struct Gate {
+    /// The level below which the signal is attenuated, in decibels.
+    pub threshold: f64,
+
+    /// How many decibels of output each decibel of input below the threshold
+    /// produces.  Infinity closes the gate entirely.
+    pub ratio: f64,
+
+    /// The most attenuation applied, in decibels.  Should be negative.
+    pub range: f64,
+
+    /// How quickly the gate opens as the level rises.
+    pub attack: Duration,
+
+    /// How long the gate stays open after the level falls.
+    pub hold: Duration,
+
+    /// How quickly the gate closes after the hold.
+    pub release: Duration,
+
+    pub detection: Detection,
+
+    sample_rate: f64,
+    held: f64,
+    detector: Detector,
+    ballistics: Ballistics,
+}
+
+impl Gate {
+    /// A gate that fully closes below the threshold.
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            threshold: -40.0,
+            ratio: f64::INFINITY,
+            range: -80.0,
+            attack: Duration::from_seconds(0.001).expect("valid"),
+            hold: Duration::from_seconds(0.01).expect("valid"),
+            release: Duration::from_seconds(0.1).expect("valid"),
+            detection: Detection::Peak,
+            sample_rate: sample_rate as f64,
+            held: 0.0,
+            detector: Detector::new(sample_rate),
+            ballistics: Ballistics { gain: -80.0 },
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    /// A downward expander with the given ratio.
+    pub fn expander(sample_rate: u32, ratio: f64) -> Self {
+        Self {
+            ratio,
+            detection: Detection::Rms,
+            ..Self::new(sample_rate)
+        }
+    }
+
+    /// The static gain change for an input level, both in decibels.
+    pub fn gain(&self, level: f64) -> f64 {
+        let under = level - self.threshold;
+        if under >= 0.0 {
+            0.0
+        } else if self.ratio.is_infinite() {
+            self.range
+        } else {
+            (under * (self.ratio - 1.0)).max(self.range)
+        }
+    }
+}
+
+impl Node for Gate {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let Some(input) = inputs.first() else {
+            return Ok(());
+        };
+        let sidechain = inputs.get(1).unwrap_or(input);
+        let level = self.detector.level(self.detection, sidechain);
+        let target = self.gain(level);
+        let gain = if target >= self.ballistics.gain {
+            self.held = self.hold.seconds() * self.sample_rate;
+            self.ballistics
+                .next(target, 0.0, coefficient(self.attack, self.sample_rate))
+        } else if self.held > 0.0 {
+            self.held -= 1.0;
+            self.ballistics.gain
+        } else {
+            self.ballistics
+                .next(target, coefficient(self.release, self.sample_rate), 0.0)
+        };
+        outputs.push(input * from_decibels(gain));
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.sample_rate = context.sample_rate as f64;
+        self.detector.sample_rate = self.sample_rate;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.held = 0.0;
+        self.detector.mean_square = 0.0;
+        self.ballistics = Ballistics { gain: -80.0 };
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+use super::{coefficient, from_decibels};
+use crate::{sample::Sample, time::Duration, Context, Node, Result};
+use std::collections::VecDeque;
+
+/// A brickwall look-ahead limiter, which keeps the signal from ever
+/// exceeding the ceiling.
+///
+/// The audio is delayed by the look-ahead time, so the gain can be lowered
+/// smoothly before a peak arrives rather than clipping it.
+#[derive(Debug)]
+pub struct Limiter {
+    /// The highest level the output may reach, in decibels.
+    pub ceiling: f64,
+
+    /// How quickly the gain recovers after a peak.
+    pub release: Duration,
+
+    sample_rate: f64,
+    lookahead_time: Duration,
+    lookahead: usize,
+    sample: usize,
+
+    /// Pairs of sample index and required gain, ascending in both, for a
+    /// running minimum over the look-ahead window.
+    minimum: VecDeque<(usize, f64)>,
+    released: f64,
+
+    /// The last look-ahead window of gains, for smoothing the attack.
+    window: Box<[f64]>,
+    window_index: usize,
+    window_sum: f64,
+
+    delayed: VecDeque<Sample>,
+}
+
+impl Limiter {
+    pub fn new(sample_rate: u32, lookahead: Duration) -> Self {
+        let lookahead_time = lookahead;
+        let lookahead = ((lookahead.seconds() * sample_rate as f64).round() as usize).max(1);
+        Self {
+            ceiling: -1.0,
+            release: Duration::from_seconds(0.05).expect("valid"),
+            sample_rate: sample_rate as f64,
+            lookahead_time,
+            lookahead,
+            sample: 0,
+            minimum: VecDeque::with_capacity(lookahead + 1),
+            released: 1.0,
+            window: vec![1.0; lookahead].into(),
+            window_index: 0,
+            window_sum: lookahead as f64,
+            delayed: VecDeque::with_capacity(lookahead + 1),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    pub fn lookahead(&self) -> Duration {
+        self.lookahead_time
+    }
+
+    /// The delay added by the look-ahead, in samples.
+    pub fn latency(&self) -> usize {
+        self.lookahead
+    }
+}
+
+impl Node for Limiter {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let Some(input) = inputs.first() else {
+            return Ok(());
+        };
+        let sidechain = inputs.get(1).unwrap_or(input);
+        let ceiling = from_decibels(self.ceiling);
+        let peak = sidechain
+            .iter()
+            .fold(0.0f64, |peak, channel| peak.max(channel.abs()));
+        let required = if peak > ceiling { ceiling / peak } else { 1.0 };
+
+        // The lowest gain required by any sample still in the look-ahead.
+        while self
+            .minimum
+            .back()
+            .is_some_and(|&(_, gain)| gain >= required)
+        {
+            self.minimum.pop_back();
+        }
+        self.minimum.push_back((self.sample, required));
+        while self
+            .minimum
+            .front()
+            .is_some_and(|&(sample, _)| sample + self.lookahead < self.sample)
+        {
+            self.minimum.pop_front();
+        }
+        let held = self.minimum.front().expect("just pushed").1;
+        self.sample += 1;
+
+        let release = coefficient(self.release, self.sample_rate);
+        self.released = held.min(release * self.released + (1.0 - release) * held);
+
+        // Averaging over the look-ahead ramps the gain down ahead of each
+        // peak while never exceeding the gain it requires.
+        self.window_sum += self.released - self.window[self.window_index];
+        self.window[self.window_index] = self.released;
+        self.window_index = (self.window_index + 1) % self.window.len();
+        if self.window_index == 0 {
+            self.window_sum = self.window.iter().sum();
+        }
+        let gain = self.window_sum / self.window.len() as f64;
+
+        self.delayed.push_back(input.clone());
+        let output = if self.delayed.len() > self.lookahead {
+            self.delayed.pop_front().expect("not empty")
+        } else {
+            Sample::zeroed(input.len())
+        };
+        outputs.push(
+            output
+                .into_iter()
+                .map(|channel| (channel * gain).clamp(-ceiling, ceiling))
+                .collect(),
+        );
+        Ok(())
+    }
+
+    /// Changing the sample rate drops any audio that is still delayed.
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        if context.sample_rate as f64 != self.sample_rate {
+            *self = Self {
+                ceiling: self.ceiling,
+                release: self.release,
+                ..Self::new(context.sample_rate, self.lookahead_time)
+            };
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        *self = Self {
+            ceiling: self.ceiling,
+            release: self.release,
+            ..Self::new(self.sample_rate as u32, self.lookahead_time)
+        };
+        Ok(())
+    }
+}
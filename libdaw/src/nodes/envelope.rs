@@ -1,7 +1,7 @@
 use crate::{
     sample::Sample,
     time::{Duration, Time},
-    Node, Result,
+    Context, Node, Result,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -60,12 +60,67 @@ impl Ord for CalculatedPoint {
 }
 
 /// A frequency node wrapper that applies a volume envelope to the node.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Envelope {
+    sample_rate: u32,
+    length: Duration,
+    points: Box<[Point]>,
     envelope: Box<[CalculatedPoint]>,
     sample: u64,
 }
 
+/// Turn the envelope points into concrete sample offsets for the given
+/// sample rate.
+fn calculate(sample_rate: u32, length: Duration, points: &[Point]) -> Box<[CalculatedPoint]> {
+    let sample_time = 1.0 / sample_rate as f64;
+    let sample_length = (sample_rate as f64 * length.seconds()) as u64;
+    let mut envelope: Vec<CalculatedPoint> = points
+        .iter()
+        .flat_map(move |point| {
+            let length = length.seconds();
+            // The end point for whence, so a whence of 1 ends up at the
+            // last sample, rather than one past the end.
+            let end = length - sample_time;
+            let whence = end * point.whence;
+            let time = match point.offset {
+                Offset::Time(offset) => whence + offset.seconds(),
+                Offset::Ratio(offset) => {
+                    let offset = length * offset;
+                    whence + offset
+                }
+            };
+            if !(0.0..=length).contains(&time) {
+                return None;
+            }
+            let sample = (time * (sample_rate) as f64) as u64;
+            if sample < sample_length {
+                Some(CalculatedPoint {
+                    sample,
+                    volume: point.volume,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // Filter points such that all points placed at the same time or later
+    // than a later-in-order point will be removed.
+    let mut min_sample = u64::MAX;
+    envelope.reverse();
+    envelope.retain(move |point| {
+        if point.sample < min_sample {
+            min_sample = point.sample;
+            true
+        } else {
+            false
+        }
+    });
+
+    envelope.reverse();
+    envelope.into()
+}
+
 impl Envelope {
     /// Construct the envelope.  If you give zero envelope points, this will
     /// effectively be a PassthroughNode.
@@ -74,59 +129,77 @@ impl Envelope {
         length: Duration,
         envelope: impl IntoIterator<Item = Point>,
     ) -> Self {
-        let sample_time = 1.0 / sample_rate as f64;
-        let sample_length = (sample_rate as f64 * length.seconds()) as u64;
-        let mut envelope: Vec<CalculatedPoint> = envelope
-            .into_iter()
-            .flat_map(move |point| {
-                let length = length.seconds();
-                // The end point for whence, so a whence of 1 ends up at the
-                // last sample, rather than one past the end.
-                let end = length - sample_time;
-                let whence = end * point.whence;
-                let time = match point.offset {
-                    Offset::Time(offset) => whence + offset.seconds(),
-                    Offset::Ratio(offset) => {
-                        let offset = length * offset;
-                        whence + offset
-                    }
-                };
-                if !(0.0..=length).contains(&time) {
-                    return None;
-                }
-                let sample = (time * (sample_rate) as f64) as u64;
-                if sample < sample_length {
-                    Some(CalculatedPoint {
-                        sample,
-                        volume: point.volume,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        // Filter points such that all points placed at the same time or later
-        // than a later-in-order point will be removed.
-        let mut min_sample = u64::MAX;
-        envelope.reverse();
-        envelope.retain(move |point| {
-            if point.sample < min_sample {
-                min_sample = point.sample;
-                true
-            } else {
-                false
-            }
-        });
-
-        envelope.reverse();
-        envelope.shrink_to_fit();
-
+        let points: Box<[Point]> = envelope.into_iter().collect();
         Self {
-            envelope: envelope.into(),
+            sample_rate,
+            length,
+            envelope: calculate(sample_rate, length, &points),
+            points,
             sample: 0,
         }
     }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn length(&self) -> Duration {
+        self.length
+    }
+
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    /// The next sample to be processed.
+    pub fn sample(&self) -> u64 {
+        self.sample
+    }
+
+    /// Get the volume for the current sample and advance to the next one.
+    /// Returns None if there are no envelope points.
+    pub(crate) fn next_volume(&mut self) -> Option<f64> {
+        let envelope_len = self.envelope.len();
+        match envelope_len {
+            0 => None,
+            1 => Some(self.envelope[0].volume),
+            _ => {
+                let sample = self.sample;
+                self.sample += 1;
+                Some(
+                    match self
+                        .envelope
+                        .binary_search_by_key(&sample, |point| point.sample)
+                    {
+                        Ok(index) => self.envelope[index].volume,
+                        Err(index) => {
+                            // Find the interpolaton points based on the insertion.
+                            let (a, b) = if index == 0 {
+                                // Before beginning; extrapolate backward.
+                                (&self.envelope[0], &self.envelope[1])
+                            } else if index == envelope_len {
+                                // After end; extrapolate forward.
+                                (
+                                    &self.envelope[envelope_len - 2],
+                                    &self.envelope[envelope_len - 1],
+                                )
+                            } else {
+                                // Between two points; interpolate.
+                                (&self.envelope[index - 1], &self.envelope[index])
+                            };
+                            let sample = sample as f64;
+                            let a_sample = a.sample as f64;
+                            let b_sample = b.sample as f64;
+                            // Lerp, given x as a time scale and y as volume.
+                            a.volume
+                                + (sample - a_sample) * (b.volume - a.volume)
+                                    / (b_sample - a_sample)
+                        }
+                    },
+                )
+            }
+        }
+    }
 }
 
 impl Node for Envelope {
@@ -137,46 +210,33 @@ impl Node for Envelope {
     ) -> Result<()> {
         outputs.extend_from_slice(inputs);
 
-        let envelope_len = self.envelope.len();
-        let volume = match envelope_len {
-            0 => return Ok(()),
-            1 => self.envelope[0].volume,
-            _ => {
-                let sample = self.sample;
-                self.sample += 1;
-                match self
-                    .envelope
-                    .binary_search_by_key(&sample, |point| point.sample)
-                {
-                    Ok(index) => self.envelope[index].volume,
-                    Err(index) => {
-                        // Find the interpolaton points based on the insertion.
-                        let (a, b) = if index == 0 {
-                            // Before beginning; extrapolate backward.
-                            (&self.envelope[0], &self.envelope[1])
-                        } else if index == envelope_len {
-                            // After end; extrapolate forward.
-                            (
-                                &self.envelope[envelope_len - 2],
-                                &self.envelope[envelope_len - 1],
-                            )
-                        } else {
-                            // Between two points; interpolate.
-                            (&self.envelope[index - 1], &self.envelope[index])
-                        };
-                        let sample = sample as f64;
-                        let a_sample = a.sample as f64;
-                        let b_sample = b.sample as f64;
-                        // Lerp, given x as a time scale and y as volume.
-                        a.volume
-                            + (sample - a_sample) * (b.volume - a.volume) / (b_sample - a_sample)
-                    }
-                }
-            }
+        let Some(volume) = self.next_volume() else {
+            return Ok(());
         };
         for output in outputs {
             *output *= volume;
         }
         Ok(())
     }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        if context.sample_rate != self.sample_rate {
+            // Keep the current position in time rather than in samples.
+            self.sample =
+                (self.sample as f64 * context.sample_rate as f64 / self.sample_rate as f64) as u64;
+            self.sample_rate = context.sample_rate;
+            self.envelope = calculate(self.sample_rate, self.length, &self.points);
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.sample = 0;
+        Ok(())
+    }
+
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.sample = sample;
+        Ok(())
+    }
 }
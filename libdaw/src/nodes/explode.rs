@@ -12,12 +12,7 @@ impl Node for Explode {
         inputs: &'b [Sample],
         outputs: &'c mut Vec<Sample>,
     ) -> Result<()> {
-        outputs.extend(inputs.into_iter().flatten().copied().map(|channel| {
-            let mut vec = Vec::new();
-            vec.reserve_exact(1);
-            vec.push(channel);
-            vec.into()
-        }));
+        outputs.extend(inputs.iter().flatten().copied().map(Sample::from));
         Ok(())
     }
 }
@@ -0,0 +1,153 @@
+use crate::{
+    metronome::{Beat, Metronome},
+    nodes::delay_line::{DelayLine, Interpolation},
+    sample::Sample,
+    time::Duration,
+    Context, Node, Result,
+};
+use std::sync::{Arc, Mutex};
+
+/// A delay line with feedback and a dry/wet mix.
+///
+/// The first input stream is the audio to be delayed.  The second input
+/// stream, if present, overrides the delay time in seconds using its first
+/// channel, and may be changed smoothly for modulation.
+#[derive(Debug)]
+pub struct FeedbackDelay {
+    /// The delay time if no delay stream comes in.  This is clamped to the
+    /// maximum delay.
+    pub delay: Duration,
+
+    /// How much of the delayed signal is fed back into the line.  This
+    /// should stay below 1 for the echoes to die away.
+    pub feedback: f64,
+
+    /// The ratio of delayed signal in the output, from 0 (fully dry) to 1
+    /// (fully wet).
+    pub mix: f64,
+
+    pub interpolation: Interpolation,
+
+    /// An optional node that the feedback path is run through, such as a
+    /// low pass filter for a tape or analog style delay.
+    pub filter: Option<Arc<Mutex<dyn Node>>>,
+
+    sample_rate: f64,
+    max_delay_time: Duration,
+    max_delay: usize,
+
+    /// One line per channel.
+    lines: Vec<DelayLine>,
+    filter_output: Vec<Sample>,
+}
+
+impl FeedbackDelay {
+    pub fn new(sample_rate: u32, max_delay: Duration, delay: Duration) -> Self {
+        Self {
+            delay,
+            feedback: 0.0,
+            mix: 0.5,
+            interpolation: Interpolation::default(),
+            filter: None,
+            sample_rate: sample_rate as f64,
+            max_delay_time: max_delay,
+            max_delay: (max_delay.seconds() * sample_rate as f64).ceil() as usize,
+            lines: Vec::new(),
+            filter_output: Vec::new(),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    pub fn max_delay(&self) -> Duration {
+        self.max_delay_time
+    }
+
+    /// Set the delay time to `length` beats, at the tempo in effect at
+    /// `beat`.
+    pub fn sync(&mut self, metronome: &Metronome, beat: Beat, length: Beat) {
+        self.delay = metronome.beats_to_duration(beat, length);
+    }
+}
+
+impl Node for FeedbackDelay {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let Some(input) = inputs.first() else {
+            return Ok(());
+        };
+        let delay = inputs
+            .get(1)
+            .and_then(|input| input.first().copied())
+            .unwrap_or(self.delay.seconds())
+            * self.sample_rate;
+        if self.lines.len() < input.len() {
+            let max_delay = self.max_delay;
+            self.lines
+                .resize_with(input.len(), || DelayLine::new(max_delay));
+        }
+
+        let interpolation = self.interpolation;
+        let delayed: Sample = self
+            .lines
+            .iter_mut()
+            .map(|line| line.read(delay, interpolation))
+            .collect();
+
+        let feedback = match &self.filter {
+            Some(filter) => {
+                self.filter_output.clear();
+                filter
+                    .lock()
+                    .expect("poisoned")
+                    .process(std::slice::from_ref(&delayed), &mut self.filter_output)?;
+                self.filter_output.first().unwrap_or(&delayed)
+            }
+            None => &delayed,
+        };
+
+        for (channel, line) in self.lines.iter_mut().enumerate() {
+            let dry = input.get(channel).copied().unwrap_or(0.0);
+            let fed_back = feedback.get(channel).copied().unwrap_or(0.0);
+            line.push(dry + self.feedback * fed_back);
+        }
+
+        outputs.push(input * (1.0 - self.mix) + delayed * self.mix);
+        Ok(())
+    }
+
+    /// Changing the sample rate drops any audio that is still delayed.
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        let sample_rate = context.sample_rate as f64;
+        if sample_rate != self.sample_rate {
+            self.sample_rate = sample_rate;
+            self.max_delay = (self.max_delay_time.seconds() * sample_rate).ceil() as usize;
+            self.lines.clear();
+        }
+        if let Some(filter) = &self.filter {
+            filter.lock().expect("poisoned").prepare(context)?;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.lines.clear();
+        if let Some(filter) = &self.filter {
+            filter.lock().expect("poisoned").reset()?;
+        }
+        Ok(())
+    }
+
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.lines.clear();
+        if let Some(filter) = &self.filter {
+            filter.lock().expect("poisoned").seek(sample)?;
+        }
+        Ok(())
+    }
+}
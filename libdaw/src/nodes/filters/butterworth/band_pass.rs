@@ -1,4 +1,4 @@
-use crate::{sample::Sample, Node, Result};
+use crate::{sample::Sample, Context, Node, Result};
 use std::f64::consts::PI;
 
 /// A butterworth band pass filter
@@ -10,6 +10,12 @@ pub struct BandPass {
     d: Vec<[f64; 4]>,
     // Stream, Channel, Order
     w: Vec<Vec<Vec<[f64; 5]>>>,
+
+    // Design parameters, kept to recompute the coefficients on prepare.
+    sample_rate: u32,
+    order: usize,
+    low_frequency: f64,
+    high_frequency: f64,
 }
 
 impl BandPass {
@@ -45,12 +51,32 @@ impl BandPass {
             d[3] = -(b_2 - 2.0 * b * r + 1.0) / s;
         }
         Ok(Self {
+            sample_rate,
             n,
             a: self_a,
             d,
             w: Vec::new(),
+            order,
+            low_frequency,
+            high_frequency,
         })
     }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    pub fn low_frequency(&self) -> f64 {
+        self.low_frequency
+    }
+
+    pub fn high_frequency(&self) -> f64 {
+        self.high_frequency
+    }
 }
 
 impl Node for BandPass {
@@ -90,4 +116,21 @@ impl Node for BandPass {
         }
         Ok(())
     }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        let w = std::mem::take(&mut self.w);
+        *self = Self::new(
+            context.sample_rate,
+            self.order,
+            self.low_frequency,
+            self.high_frequency,
+        )?;
+        self.w = w;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.w.clear();
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{sample::Sample, Node, Result};
+use crate::{sample::Sample, Context, Node, Result};
 use std::f64::consts::PI;
 
 /// A butterworth band pass filter
@@ -10,6 +10,12 @@ pub struct BandStop {
     d: Vec<[f64; 4]>,
     // Stream, Channel, Order
     w: Vec<Vec<Vec<[f64; 5]>>>,
+
+    // Design parameters, kept to recompute the coefficients on prepare.
+    sample_rate: u32,
+    order: usize,
+    low_frequency: f64,
+    high_frequency: f64,
     r: f64,
     s: f64,
 }
@@ -49,14 +55,34 @@ impl BandStop {
         let r = 4.0 * a;
         let s = 4.0 * a_2 + 2.0;
         Ok(Self {
+            sample_rate,
             n,
             a: self_a,
             d,
             w: Vec::new(),
+            order,
+            low_frequency,
+            high_frequency,
             r,
             s,
         })
     }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    pub fn low_frequency(&self) -> f64 {
+        self.low_frequency
+    }
+
+    pub fn high_frequency(&self) -> f64 {
+        self.high_frequency
+    }
 }
 
 impl Node for BandStop {
@@ -96,4 +122,21 @@ impl Node for BandStop {
         }
         Ok(())
     }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        let w = std::mem::take(&mut self.w);
+        *self = Self::new(
+            context.sample_rate,
+            self.order,
+            self.low_frequency,
+            self.high_frequency,
+        )?;
+        self.w = w;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.w.clear();
+        Ok(())
+    }
 }
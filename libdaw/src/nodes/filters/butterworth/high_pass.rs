@@ -1,4 +1,4 @@
-use crate::{sample::Sample, Node, Result};
+use crate::{sample::Sample, Context, Node, Result};
 use std::f64::consts::PI;
 
 /// A butterworth high pass filter
@@ -10,6 +10,11 @@ pub struct HighPass {
     d: Vec<[f64; 2]>,
     // Stream, Channel, Order
     w: Vec<Vec<Vec<[f64; 3]>>>,
+
+    // Design parameters, kept to recompute the coefficients on prepare.
+    sample_rate: u32,
+    order: usize,
+    frequency: f64,
 }
 
 impl HighPass {
@@ -30,12 +35,27 @@ impl HighPass {
             d[1] = -(a_2 - 2.0 * a * r + 1.0) / s;
         }
         Ok(Self {
+            sample_rate,
             n,
             a: self_a,
             d,
             w: Vec::new(),
+            order,
+            frequency,
         })
     }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
 }
 
 impl Node for HighPass {
@@ -75,4 +95,16 @@ impl Node for HighPass {
         }
         Ok(())
     }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        let w = std::mem::take(&mut self.w);
+        *self = Self::new(context.sample_rate, self.order, self.frequency)?;
+        self.w = w;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.w.clear();
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{sample::Sample, Node, Result};
+use crate::{sample::Sample, Context, Node, Result};
 use std::f64::consts::PI;
 
 /// A butterworth band pass filter
@@ -11,6 +11,13 @@ pub struct BandPass {
     d: Vec<[f64; 4]>,
     // Stream, Channel, Order
     w: Vec<Vec<Vec<[f64; 5]>>>,
+
+    // Design parameters, kept to recompute the coefficients on prepare.
+    sample_rate: u32,
+    n: usize,
+    epsilon: f64,
+    low_frequency: f64,
+    high_frequency: f64,
 }
 
 impl BandPass {
@@ -54,13 +61,38 @@ impl BandPass {
         }
         let ep = 2.0 / epsilon;
         Ok(Self {
+            sample_rate,
             m,
             ep,
             a: self_a,
             d,
             w: Vec::new(),
+            n,
+            epsilon,
+            low_frequency,
+            high_frequency,
         })
     }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    pub fn low_frequency(&self) -> f64 {
+        self.low_frequency
+    }
+
+    pub fn high_frequency(&self) -> f64 {
+        self.high_frequency
+    }
 }
 
 impl Node for BandPass {
@@ -100,4 +132,22 @@ impl Node for BandPass {
         }
         Ok(())
     }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        let w = std::mem::take(&mut self.w);
+        *self = Self::new(
+            context.sample_rate,
+            self.n,
+            self.epsilon,
+            self.low_frequency,
+            self.high_frequency,
+        )?;
+        self.w = w;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.w.clear();
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{sample::Sample, Node, Result};
+use crate::{sample::Sample, Context, Node, Result};
 use std::f64::consts::PI;
 
 /// A chebyshev low pass filter
@@ -10,6 +10,12 @@ pub struct LowPass {
     d: Vec<[f64; 2]>,
     // Stream, Channel, Order
     w: Vec<Vec<Vec<[f64; 3]>>>,
+
+    // Design parameters, kept to recompute the coefficients on prepare.
+    sample_rate: u32,
+    n: usize,
+    epsilon: f64,
+    frequency: f64,
 }
 
 impl LowPass {
@@ -37,13 +43,33 @@ impl LowPass {
         }
         let ep = 2.0 / epsilon;
         Ok(Self {
+            sample_rate,
             m,
             ep,
             a: self_a,
             d,
             w: Vec::new(),
+            n,
+            epsilon,
+            frequency,
         })
     }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
 }
 
 impl Node for LowPass {
@@ -83,4 +109,16 @@ impl Node for LowPass {
         }
         Ok(())
     }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        let w = std::mem::take(&mut self.w);
+        *self = Self::new(context.sample_rate, self.n, self.epsilon, self.frequency)?;
+        self.w = w;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.w.clear();
+        Ok(())
+    }
 }
@@ -1,10 +1,12 @@
-use crate::{sample::Sample, time::Duration, Node, Result};
+use crate::{sample::Sample, time::Duration, Context, Node, Result};
 use std::collections::VecDeque;
 
 /// Simple averaging low pass filter.  Keeps a buffer of the length of the
 /// passed-in frequency and averages that buffer for each new input sample.
 #[derive(Debug)]
 pub struct MovingAverage {
+    sample_rate: u32,
+    window: Duration,
     buffer_size: usize,
     buffers: Vec<VecDeque<Sample>>,
 
@@ -14,11 +16,21 @@ pub struct MovingAverage {
 impl MovingAverage {
     pub fn new(sample_rate: u32, window: Duration) -> Self {
         Self {
+            sample_rate,
+            window,
             buffer_size: (sample_rate as f64 * window.seconds()) as usize,
             buffers: Vec::new(),
             averages: Vec::new(),
         }
     }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
 }
 
 impl Node for MovingAverage {
@@ -54,4 +66,16 @@ impl Node for MovingAverage {
         }
         Ok(())
     }
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        // Any excess is evicted from the buffers on the next sample.
+        self.sample_rate = context.sample_rate;
+        self.buffer_size = (context.sample_rate as f64 * self.window.seconds()) as usize;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.buffers.clear();
+        self.averages.clear();
+        Ok(())
+    }
 }
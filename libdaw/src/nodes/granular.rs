@@ -0,0 +1,279 @@
+use crate::{sample::Sample, time::Duration, wav, Context, Node, Result};
+use std::{f64::consts::PI, path::Path};
+
+/// The most grains that may play at once.  Spawning is skipped while this
+/// many are playing.
+const MAX_GRAINS: usize = 256;
+
+/// The amplitude envelope shape of each grain.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    Rectangle,
+    Triangle,
+    #[default]
+    Hann,
+
+    /// A Tukey window with a 50% taper, which has a flat top.
+    Tukey,
+}
+
+impl Window {
+    /// The amplitude at the given point of the grain, from 0 to 1.
+    pub fn amplitude(self, x: f64) -> f64 {
+        match self {
+            Window::Rectangle => 1.0,
+            Window::Triangle => 1.0 - (2.0 * x - 1.0).abs(),
+            Window::Hann => 0.5 - 0.5 * (2.0 * PI * x).cos(),
+            Window::Tukey => {
+                let x = x.min(1.0 - x);
+                if x >= 0.25 {
+                    1.0
+                } else {
+                    0.5 - 0.5 * (4.0 * PI * x).cos()
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Grain {
+    /// Read position in frames of the buffer.
+    position: f64,
+
+    /// How many buffer frames to advance per output sample.
+    step: f64,
+    age: usize,
+    length: usize,
+}
+
+/// A granular engine, spawning grains from an in-memory buffer.
+///
+/// Every control may be driven by an input stream, using the first channel.
+/// Streams that aren't present fall back to the field values:
+///
+/// 0. density, in grains per second
+/// 1. grain size, in seconds
+/// 2. position, as a ratio of the buffer length
+/// 3. position jitter, as a ratio of the buffer length
+/// 4. pitch, as a playback speed ratio
+/// 5. audio to capture into the buffer while `recording` is set
+#[derive(Debug)]
+pub struct Granular {
+    /// Grains spawned per second.
+    pub density: f64,
+
+    /// The length of each grain.
+    pub grain_size: Duration,
+
+    /// The position at which grains start, as a ratio of the buffer length.
+    pub position: f64,
+
+    /// Random offset applied to each grain's start position, as a ratio of
+    /// the buffer length.
+    pub jitter: f64,
+
+    /// The playback speed of each grain, as a ratio.
+    pub pitch: f64,
+
+    pub window: Window,
+
+    /// Whether the capture input stream is being written into the buffer.
+    pub recording: bool,
+
+    sample_rate: f64,
+    buffer: Vec<Sample>,
+    buffer_sample_rate: f64,
+    channels: usize,
+    grains: Vec<Grain>,
+
+    /// Accumulates density until a grain should spawn.
+    spawn: f64,
+    write: usize,
+
+    /// The seed the random generator starts again from when reset.
+    seed: u64,
+    rng: fastrand::Rng,
+}
+
+impl Granular {
+    /// Construct a granular engine playing from the given buffer, which was
+    /// recorded at `buffer_sample_rate`.
+    pub fn new(sample_rate: u32, buffer: Vec<Sample>, buffer_sample_rate: u32) -> Self {
+        let channels = buffer.iter().map(|sample| sample.len()).max().unwrap_or(1);
+        let seed = fastrand::u64(..);
+        Self {
+            density: 20.0,
+            grain_size: Duration::from_seconds(0.05).expect("valid"),
+            position: 0.0,
+            jitter: 0.0,
+            pitch: 1.0,
+            window: Window::default(),
+            recording: false,
+            sample_rate: sample_rate as f64,
+            buffer,
+            buffer_sample_rate: buffer_sample_rate as f64,
+            channels,
+            grains: Vec::with_capacity(MAX_GRAINS),
+            spawn: 0.0,
+            write: 0,
+            seed,
+            rng: fastrand::Rng::with_seed(seed),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    /// Construct a granular engine from a WAV file.
+    pub fn from_wav(sample_rate: u32, path: impl AsRef<Path>) -> Result<Self> {
+        let wav = wav::read(path)?;
+        Ok(Self::new(sample_rate, wav.samples, wav.sample_rate))
+    }
+
+    /// Construct a granular engine with a silent buffer of the given length,
+    /// to be filled by recording the capture stream.
+    pub fn capture(sample_rate: u32, length: Duration, channels: usize) -> Self {
+        let frames = (length.seconds() * sample_rate as f64) as usize;
+        let mut granular = Self::new(
+            sample_rate,
+            vec![Sample::zeroed(channels); frames],
+            sample_rate,
+        );
+        granular.recording = true;
+        granular
+    }
+
+    /// Seed the random generator used for position jitter, for reproducible
+    /// output.  Resetting starts it again from this seed.
+    pub fn seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng.seed(seed);
+    }
+
+    pub fn buffer(&self) -> &[Sample] {
+        &self.buffer
+    }
+
+    pub fn buffer_sample_rate(&self) -> u32 {
+        self.buffer_sample_rate as u32
+    }
+
+    /// Read the buffer at a fractional frame, with linear interpolation and
+    /// wrapping around the end.
+    fn read(&self, position: f64, channel: usize) -> f64 {
+        let len = self.buffer.len();
+        let position = position.rem_euclid(len as f64);
+        let index = position.floor() as usize % len;
+        let next = (index + 1) % len;
+        let fraction = position.fract();
+        let a = self.buffer[index].get(channel).copied().unwrap_or(0.0);
+        let b = self.buffer[next].get(channel).copied().unwrap_or(0.0);
+        a + (b - a) * fraction
+    }
+}
+
+impl Node for Granular {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let control = |stream: usize, default: f64| {
+            inputs
+                .get(stream)
+                .and_then(|input| input.first().copied())
+                .unwrap_or(default)
+        };
+        let density = control(0, self.density);
+        let grain_size = control(1, self.grain_size.seconds());
+        let position = control(2, self.position);
+        let jitter = control(3, self.jitter);
+        let pitch = control(4, self.pitch);
+
+        let len = self.buffer.len();
+        if len == 0 {
+            outputs.push(Sample::zeroed(self.channels));
+            return Ok(());
+        }
+
+        if self.recording {
+            if let Some(capture) = inputs.get(5) {
+                self.buffer[self.write] = capture.clone();
+                self.write = (self.write + 1) % len;
+            }
+        }
+
+        self.spawn += density.max(0.0) / self.sample_rate;
+        while self.spawn >= 1.0 {
+            self.spawn -= 1.0;
+            let length = (grain_size * self.sample_rate) as usize;
+            if length == 0 || self.grains.len() >= MAX_GRAINS {
+                continue;
+            }
+            let offset = (self.rng.f64() * 2.0 - 1.0) * jitter;
+            self.grains.push(Grain {
+                position: (position + offset) * len as f64,
+                step: pitch * self.buffer_sample_rate / self.sample_rate,
+                age: 0,
+                length,
+            });
+        }
+
+        let mut output = Sample::zeroed(self.channels);
+        for grain in &self.grains {
+            let amplitude = self
+                .window
+                .amplitude(grain.age as f64 / grain.length as f64);
+            for (channel, value) in output.iter_mut().enumerate() {
+                *value += amplitude * self.read(grain.position, channel);
+            }
+        }
+        for grain in &mut self.grains {
+            grain.position += grain.step;
+            grain.age += 1;
+        }
+        self.grains.retain(|grain| grain.age < grain.length);
+
+        outputs.push(output);
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.sample_rate = context.sample_rate as f64;
+        Ok(())
+    }
+
+    /// Resetting stops all grains and re-seeds the jitter, but keeps the
+    /// buffer.
+    fn reset(&mut self) -> Result<()> {
+        self.grains.clear();
+        self.spawn = 0.0;
+        self.write = 0;
+        self.rng.seed(self.seed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jitters_the_same_after_reset() {
+        let buffer = (0..4800).map(|index| Sample::from(index as f64)).collect();
+        let mut granular = Granular::new(48000, buffer, 48000);
+        granular.jitter = 0.5;
+        let render = |granular: &mut Granular| {
+            let mut outputs = Vec::new();
+            for _ in 0..4800 {
+                granular.process(&[], &mut outputs).unwrap();
+            }
+            outputs.iter().map(|sample| sample[0]).collect::<Vec<_>>()
+        };
+        let first = render(&mut granular);
+        granular.reset().unwrap();
+        assert_eq!(render(&mut granular), first);
+    }
+}
@@ -1,6 +1,6 @@
 mod strong;
 
-use crate::{nodes::Passthrough, sample::Sample, Node};
+use crate::{nodes::Passthrough, sample::Sample, Context, Node};
 use nohash_hasher::{IntMap, IntSet};
 use std::sync::{Arc, Mutex};
 use strong::Strong;
@@ -11,6 +11,15 @@ struct Input {
     stream: Option<usize>,
 }
 
+/// A connection within a graph.  A source of `None` is the graph's input,
+/// and a destination of `None` is its output.
+#[derive(Debug, Clone)]
+pub struct Connection {
+    pub source: Option<Arc<Mutex<dyn Node>>>,
+    pub destination: Option<Arc<Mutex<dyn Node>>>,
+    pub stream: Option<usize>,
+}
+
 #[derive(Debug, Default)]
 struct Slot {
     output: Vec<Sample>,
@@ -30,6 +39,13 @@ pub struct Graph {
     input: Strong,
     output: Strong,
     process_list: ProcessList,
+
+    /// Reused between nodes and samples, so processing doesn't allocate.
+    input_buffer: Vec<Sample>,
+
+    /// The context the graph was last prepared with, which nodes are
+    /// prepared with as they are connected.
+    context: Option<Context>,
 }
 
 impl Default for Graph {
@@ -45,6 +61,8 @@ impl Default for Graph {
             input,
             output,
             process_list: Default::default(),
+            input_buffer: Vec::new(),
+            context: None,
         };
         graph
     }
@@ -55,6 +73,22 @@ impl Graph {
         self.nodes.entry(node).or_default()
     }
 
+    /// Prepare the node with the graph's context if it isn't in the graph
+    /// yet.
+    fn prepare_if_new(&self, node: &Strong) -> crate::Result<()> {
+        match &self.context {
+            Some(context) if !self.nodes.contains_key(node) => {
+                node.lock().expect("poisoned").prepare(context)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The context the graph was last prepared with, if any.
+    pub fn context(&self) -> Option<&Context> {
+        self.context.as_ref()
+    }
+
     pub fn remove(&mut self, node: Arc<Mutex<dyn Node>>) -> bool {
         let node = Strong { inner: node };
         if let Some((node, _)) = self.nodes.remove_entry(&node) {
@@ -68,19 +102,26 @@ impl Graph {
         }
     }
 
+    /// Connect the given output of the source to the destination.  The same
+    /// output may be attached multiple times. `None` will attach all outputs.
+    /// Nodes new to the graph are prepared with its context, if it has been
+    /// prepared.
     pub fn connect(
         &mut self,
         source: Arc<Mutex<dyn Node>>,
         destination: Arc<Mutex<dyn Node>>,
         stream: Option<usize>,
-    ) {
-        self.process_list.needs_rebuild = true;
+    ) -> crate::Result<()> {
         let source = Strong { inner: source };
         let destination = Strong { inner: destination };
+        self.prepare_if_new(&source)?;
+        self.prepare_if_new(&destination)?;
+        self.process_list.needs_rebuild = true;
         self.add_if_needed(source.clone());
         self.add_if_needed(destination)
             .inputs
             .push(Input { source, stream });
+        Ok(())
     }
 
     fn remove_if_unneeded(&mut self, node: Strong) {
@@ -139,8 +180,12 @@ impl Graph {
     /// Connect the given output of the initial input to the destination.  The
     /// same output may be attached multiple times. `None` will attach all
     /// outputs.
-    pub fn input(&mut self, destination: Arc<Mutex<dyn Node>>, stream: Option<usize>) {
-        self.connect(self.input.clone().inner, destination, stream);
+    pub fn input(
+        &mut self,
+        destination: Arc<Mutex<dyn Node>>,
+        stream: Option<usize>,
+    ) -> crate::Result<()> {
+        self.connect(self.input.clone().inner, destination, stream)
     }
 
     /// Disconnect the last-added matching connection from the destination,
@@ -156,8 +201,12 @@ impl Graph {
     /// Connect the given output of the source to the final destinaton.  The
     /// same output may be attached multiple times. `None` will attach all
     /// outputs.
-    pub fn output(&mut self, source: Arc<Mutex<dyn Node>>, stream: Option<usize>) {
-        self.connect(source, self.output.clone().inner, stream);
+    pub fn output(
+        &mut self,
+        source: Arc<Mutex<dyn Node>>,
+        stream: Option<usize>,
+    ) -> crate::Result<()> {
+        self.connect(source, self.output.clone().inner, stream)
     }
 
     /// Disconnect the last-added matching connection from the source, returning
@@ -166,9 +215,34 @@ impl Graph {
         self.disconnect(source, self.output.clone().inner, stream)
     }
 
+    /// The output streams the node produced on the last processed sample, or
+    /// None if it is not in the graph.  Nodes that don't lead to the graph's
+    /// output are never processed, so they have no output.
+    pub fn output_of(&self, node: &Arc<Mutex<dyn Node>>) -> Option<&[Sample]> {
+        let node = Strong {
+            inner: node.clone(),
+        };
+        self.nodes.get(&node).map(|slot| slot.output.as_slice())
+    }
+
+    /// Every connection in the graph.  The connections into each
+    /// destination are in the order they were made, which is the order of
+    /// its input streams.
+    pub fn connections(&self) -> impl Iterator<Item = Connection> + '_ {
+        self.nodes.iter().flat_map(move |(destination, slot)| {
+            slot.inputs.iter().map(move |input| Connection {
+                source: (input.source != self.input).then(|| input.source.inner.clone()),
+                destination: (*destination != self.output).then(|| destination.inner.clone()),
+                stream: input.stream,
+            })
+        })
+    }
+
+    /// Add the node to the process list after all of its sources, so that
+    /// nodes shared by several destinations are processed before any of
+    /// them.
     fn walk_node(&mut self, node: Strong) {
         if self.process_list.memo.insert(node.clone()) {
-            self.process_list.list.push(node.clone());
             let sources: Vec<_> = self
                 .nodes
                 .get(&node)
@@ -179,6 +253,7 @@ impl Graph {
             for source in sources {
                 self.walk_node(source);
             }
+            self.process_list.list.push(node);
         }
     }
 
@@ -201,9 +276,9 @@ impl Node for Graph {
         outputs: &'c mut Vec<Sample>,
     ) -> crate::Result<()> {
         self.check_process_list();
-        let mut input_buffer = Vec::new();
-        // First process all process-needing nodes in reverse order.
-        for node in self.process_list.list.iter().rev() {
+        let mut input_buffer = std::mem::take(&mut self.input_buffer);
+        // Process all process-needing nodes, sources first.
+        for node in self.process_list.list.iter() {
             input_buffer.clear();
             if *node == self.input {
                 // The input node just gets the inputs from the outside world.
@@ -235,9 +310,87 @@ impl Node for Graph {
                 .expect("poisoned")
                 .process(&input_buffer, &mut slot.output)?;
         }
+        input_buffer.clear();
+        self.input_buffer = input_buffer;
         if let Some(slot) = self.nodes.get_mut(&self.output) {
             outputs.extend_from_slice(&slot.output);
         }
         Ok(())
     }
+
+    /// Prepare every node in the graph.  The context is kept, and nodes
+    /// connected afterward are prepared with it as they are added.
+    fn prepare(&mut self, context: &Context) -> crate::Result<()> {
+        self.context = Some(*context);
+        for node in self.nodes.keys() {
+            node.lock().expect("poisoned").prepare(context)?;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> crate::Result<()> {
+        for (node, slot) in &mut self.nodes {
+            slot.output.clear();
+            node.lock().expect("poisoned").reset()?;
+        }
+        Ok(())
+    }
+
+    fn seek(&mut self, sample: u64) -> crate::Result<()> {
+        for (node, slot) in &mut self.nodes {
+            slot.output.clear();
+            node.lock().expect("poisoned").seek(sample)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::{oscillators::Sine, Gain};
+
+    #[test]
+    fn shared_source_is_processed_first() {
+        let mut graph = Graph::default();
+        let source: Arc<Mutex<dyn Node>> = Arc::new(Mutex::new(Gain::new(1.0)));
+        let branch: Arc<Mutex<dyn Node>> = Arc::new(Mutex::new(Gain::new(2.0)));
+        graph.input(source.clone(), None).unwrap();
+        graph.connect(source.clone(), branch.clone(), None).unwrap();
+        graph.output(source, None).unwrap();
+        graph.output(branch, None).unwrap();
+        let mut outputs = Vec::new();
+        graph.process(&[Sample::from(1.0)], &mut outputs).unwrap();
+        let outputs: Vec<f64> = outputs.iter().map(|sample| sample[0]).collect();
+        assert_eq!(outputs, [1.0, 2.0]);
+    }
+
+    #[test]
+    fn lists_connections() {
+        let mut graph = Graph::default();
+        let node: Arc<Mutex<dyn Node>> = Arc::new(Mutex::new(Gain::new(1.0)));
+        graph.input(node.clone(), Some(1)).unwrap();
+        graph.output(node.clone(), None).unwrap();
+        let mut connections: Vec<_> = graph
+            .connections()
+            .map(|connection| {
+                (
+                    connection.source.is_some(),
+                    connection.destination.is_some(),
+                    connection.stream,
+                )
+            })
+            .collect();
+        connections.sort();
+        assert_eq!(connections, [(false, true, Some(1)), (true, false, None)]);
+    }
+
+    #[test]
+    fn prepares_nodes_connected_later() {
+        let mut graph = Graph::default();
+        graph.prepare(&Context::new(96000)).unwrap();
+        let sine = Arc::new(Mutex::new(Sine::new(48000, 440.0)));
+        graph.output(sine.clone(), None).unwrap();
+        assert_eq!(sine.lock().unwrap().sample_rate(), 96000);
+    }
 }
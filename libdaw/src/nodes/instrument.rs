@@ -1,15 +1,24 @@
 use crate::{
-    nodes::{ConstantValue, Graph},
+    sample::Sample,
     time::{Duration, Timestamp},
-    Node, Result,
+    Context, Node, Result,
 };
 use std::{
     cmp::Reverse,
-    collections::BinaryHeap,
-    fmt,
-    sync::{Arc, Mutex},
+    fmt, mem,
+    sync::{
+        mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+        Arc, Mutex,
+    },
+    thread,
 };
 
+/// How long before its tone starts a voice is asked for, in seconds.
+const LOOKAHEAD: f64 = 0.5;
+
+/// The most voices that may be waiting to be built at once.
+const IN_FLIGHT: usize = 64;
+
 /// A single tone definition.  Defined by frequency, not note name, to not tie
 /// it to any particular tuning or scale.
 /// Detuning and pitch bend should be done to the underlying frequency node.
@@ -20,152 +29,566 @@ pub struct Tone {
     pub frequency: f64,
 }
 
-#[derive(Debug, Clone, Copy)]
-struct QueuedTone {
-    start_sample: u64,
-    end_sample: u64,
-    tone: Tone,
-}
-impl PartialOrd for QueuedTone {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.start_sample.partial_cmp(&other.start_sample)
-    }
-}
+/// Work for the builder thread.
+enum Request {
+    Build {
+        index: usize,
+        generation: u64,
+        tone: Tone,
+    },
 
-impl Ord for QueuedTone {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.start_sample.cmp(&other.start_sample)
-    }
-}
-impl PartialEq for QueuedTone {
-    fn eq(&self, other: &Self) -> bool {
-        self.start_sample.eq(&other.start_sample)
-    }
-}
-impl Eq for QueuedTone {}
+    /// Prepare every voice built from here on with this context.
+    Prepare(Context),
 
-#[derive(Debug)]
-struct PlayingTone {
-    end_sample: u64,
-    graph: Arc<Mutex<Graph>>,
+    /// Drop a voice that has finished playing.
+    Free(Arc<Mutex<dyn Node>>),
 }
 
-impl PartialOrd for PlayingTone {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.end_sample.partial_cmp(&other.end_sample)
-    }
+/// A voice from the builder thread, and the context it was prepared with.
+struct Built {
+    index: usize,
+    generation: u64,
+    context: Option<Context>,
+    voice: Result<Arc<Mutex<dyn Node>>>,
 }
 
-impl Ord for PlayingTone {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.end_sample.cmp(&other.end_sample)
-    }
+enum State {
+    Idle,
+    Building,
+    Ready(Arc<Mutex<dyn Node>>),
 }
-impl PartialEq for PlayingTone {
-    fn eq(&self, other: &Self) -> bool {
-        self.end_sample.eq(&other.end_sample)
-    }
+
+/// A tone's voice, which only exists from shortly before the tone starts
+/// until it ends.
+struct Voice {
+    /// The first sample of the tone, and the sample after its last, or None
+    /// if it is too short to play any samples.
+    span: Option<(u64, u64)>,
+
+    state: State,
 }
-impl Eq for PlayingTone {}
 
 /// A node that can play a sequence of tones from a node creator.
+///
+/// The node creator runs on a thread of its own, which builds each tone's
+/// voice shortly before the tone starts and frees it after the tone ends,
+/// so processing neither builds nor frees nodes, and only as many voices
+/// exist as are sounding or about to.  If a voice isn't built by the time
+/// its tone starts, processing waits for it.  Each voice is given the
+/// tone's frequency as its only input stream, and the output streams of all
+/// sounding voices are the output of the instrument.
 pub struct Instrument {
-    node_creator: Box<dyn FnMut(Tone) -> Result<Arc<Mutex<dyn Node>>> + Send>,
-    graph: Graph,
-    queue: BinaryHeap<Reverse<QueuedTone>>,
-    playing: BinaryHeap<Reverse<PlayingTone>>,
+    /// Every tone added, and its voice at the same index.
+    tones: Vec<Tone>,
+    voices: Vec<Voice>,
+
+    /// Tones waiting to start, latest first so the next is at the end.
+    queue: Vec<usize>,
+
+    /// Sounding tones, in the order they started.  This always has room for
+    /// every tone.
+    playing: Vec<usize>,
+
+    requests: SyncSender<Request>,
+    built: Receiver<Built>,
+
+    /// How many voices have been asked for and not yet received.
+    building: usize,
+
+    /// Bumped whenever the tones are cleared, so that voices built for the
+    /// old tones are thrown away.
+    generation: u64,
+
+    /// Free requests that didn't fit in the channel yet.
+    retired: Vec<Request>,
+
     sample_rate: u32,
     sample: u64,
+
+    /// The context the instrument was last prepared with, which new voices
+    /// are prepared with.
+    context: Option<Context>,
 }
 
 impl fmt::Debug for Instrument {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Instrument")
-            .field("graph", &self.graph)
+            .field("tones", &self.tones)
             .field("queue", &self.queue)
             .field("playing", &self.playing)
+            .field("building", &self.building)
+            .field("generation", &self.generation)
             .field("sample_rate", &self.sample_rate)
             .field("sample", &self.sample)
-            .finish()
+            .field("context", &self.context)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Run the node creator for the instrument until the instrument is dropped.
+fn build(
+    mut node_creator: impl FnMut(Tone) -> Result<Arc<Mutex<dyn Node>>>,
+    requests: Receiver<Request>,
+    built: SyncSender<Built>,
+) {
+    let mut context = None;
+    for request in requests {
+        match request {
+            Request::Build {
+                index,
+                generation,
+                tone,
+            } => {
+                let voice = node_creator(tone).and_then(|voice| {
+                    if let Some(context) = &context {
+                        voice.lock().expect("poisoned").prepare(context)?;
+                    }
+                    Ok(voice)
+                });
+                let built = built.send(Built {
+                    index,
+                    generation,
+                    context,
+                    voice,
+                });
+                if built.is_err() {
+                    break;
+                }
+            }
+            Request::Prepare(new) => context = Some(new),
+            Request::Free(voice) => drop(voice),
+        }
+    }
+}
+
+/// Send a finished voice to be freed, or keep it to send later if the
+/// builder is busy.
+fn retire(requests: &SyncSender<Request>, retired: &mut Vec<Request>, voice: Arc<Mutex<dyn Node>>) {
+    if let Err(TrySendError::Full(request)) = requests.try_send(Request::Free(voice)) {
+        retired.push(request);
     }
 }
 
+fn stopped<T>(_: T) -> crate::Error {
+    "the instrument's voice builder stopped".into()
+}
+
 impl Instrument {
     pub fn new(
         sample_rate: u32,
         frequency_node_creator: impl 'static + FnMut(Tone) -> Result<Arc<Mutex<dyn Node>>> + Send,
     ) -> Self {
+        // The builder never blocks on a reply, because at most IN_FLIGHT
+        // builds are outstanding.
+        let (requests, builder_requests) = sync_channel(IN_FLIGHT * 2);
+        let (builder_built, built) = sync_channel(IN_FLIGHT);
+        thread::spawn(move || build(frequency_node_creator, builder_requests, builder_built));
         Self {
             sample_rate,
-            node_creator: Box::new(frequency_node_creator),
-            graph: Default::default(),
+            tones: Default::default(),
+            voices: Default::default(),
             queue: Default::default(),
             playing: Default::default(),
+            requests,
+            built,
+            building: 0,
+            generation: 0,
+            retired: Default::default(),
             sample: Default::default(),
+            context: None,
         }
     }
 
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// The next sample to be processed.
+    pub fn sample(&self) -> u64 {
+        self.sample
+    }
+
+    /// Every tone added, in the order they were added.
+    pub fn tones(&self) -> &[Tone] {
+        &self.tones
+    }
+
+    /// Add a tone.  Its voice is built once the tone is about to start.
     pub fn add_tone(&mut self, tone: Tone) {
+        let index = self.voices.len();
+        let span = self.span(tone);
+        self.tones.push(tone);
+        self.voices.push(Voice {
+            span,
+            state: State::Idle,
+        });
+        if span.is_some_and(|(_, end)| self.sample < end) {
+            self.enqueue(index);
+        }
+        // Seeking requeues every tone, and any may play at once.
+        let tones = self.tones.len();
+        self.queue.reserve(tones - self.queue.len());
+        self.playing.reserve(tones - self.playing.len());
+        self.retired.reserve(tones - self.retired.len());
+    }
+
+    /// Remove every tone, silencing any that are playing.
+    pub fn clear_tones(&mut self) -> Result<()> {
+        self.generation += 1;
+        self.tones.clear();
+        self.voices.clear();
+        self.queue.clear();
+        self.playing.clear();
+        self.retired.clear();
+        Ok(())
+    }
+
+    /// Calculate the sample positions of a tone, returning None if it is
+    /// too short to play any samples.
+    fn span(&self, tone: Tone) -> Option<(u64, u64)> {
         let start_sample = (tone.start.seconds() * self.sample_rate as f64) as u64;
         let end = tone.start + tone.length;
         let end_sample = (end.seconds() * self.sample_rate as f64) as u64;
-        if end_sample > start_sample {
-            self.queue.push(Reverse(QueuedTone {
-                start_sample,
-                end_sample,
-                tone,
-            }));
+        (end_sample > start_sample).then_some((start_sample, end_sample))
+    }
+
+    /// The order tones start in, earliest first.
+    fn order(&self, index: usize) -> (u64, usize) {
+        (self.voices[index].span.map_or(0, |(start, _)| start), index)
+    }
+
+    /// Queue a tone to start.
+    fn enqueue(&mut self, index: usize) {
+        let order = self.order(index);
+        let position = self
+            .queue
+            .partition_point(|&queued| self.order(queued) > order);
+        self.queue.insert(position, index);
+    }
+
+    fn build_request(&self, index: usize) -> Request {
+        Request::Build {
+            index,
+            generation: self.generation,
+            tone: self.tones[index],
         }
     }
+
+    /// Take a voice from the builder thread.
+    fn receive(&mut self, built: Built) -> Result<()> {
+        self.building -= 1;
+        let current = built.generation == self.generation
+            && matches!(self.voices[built.index].state, State::Building);
+        if current {
+            self.voices[built.index].state = State::Idle;
+        }
+        let voice = built.voice?;
+        let wanted = current
+            && self.voices[built.index]
+                .span
+                .is_some_and(|(_, end)| self.sample < end);
+        if !wanted {
+            retire(&self.requests, &mut self.retired, voice);
+            return Ok(());
+        }
+        if built.context != self.context {
+            if let Some(context) = &self.context {
+                voice.lock().expect("poisoned").prepare(context)?;
+            }
+        }
+        self.voices[built.index].state = State::Ready(voice);
+        Ok(())
+    }
+
+    /// Ask for the voices of tones starting soon, without blocking.
+    fn look_ahead(&mut self) {
+        let horizon = self.sample + (LOOKAHEAD * self.sample_rate as f64) as u64;
+        for position in (0..self.queue.len()).rev() {
+            if self.building >= IN_FLIGHT {
+                break;
+            }
+            let index = self.queue[position];
+            let (start, _) = self.voices[index]
+                .span
+                .expect("only playable tones are queued");
+            if start > horizon {
+                break;
+            }
+            if !matches!(self.voices[index].state, State::Idle) {
+                continue;
+            }
+            if self.requests.try_send(self.build_request(index)).is_err() {
+                break;
+            }
+            self.voices[index].state = State::Building;
+            self.building += 1;
+        }
+    }
+
+    /// Block until a tone's voice is built.
+    fn wait_for(&mut self, index: usize) -> Result<()> {
+        loop {
+            match self.voices[index].state {
+                State::Ready(_) => return Ok(()),
+                State::Idle if self.building < IN_FLIGHT => {
+                    self.requests
+                        .send(self.build_request(index))
+                        .map_err(stopped)?;
+                    self.voices[index].state = State::Building;
+                    self.building += 1;
+                }
+                _ => {
+                    let built = self.built.recv().map_err(stopped)?;
+                    self.receive(built)?;
+                }
+            }
+        }
+    }
+
+    /// Start a tone at the current sample, which may be partway through it.
+    fn start(&mut self, index: usize) -> Result<()> {
+        self.wait_for(index)?;
+        let voice = &self.voices[index];
+        let (start, _) = voice.span.expect("only playable tones are started");
+        if let State::Ready(node) = &voice.state {
+            if self.sample > start {
+                node.lock().expect("poisoned").seek(self.sample - start)?;
+            }
+        }
+        self.playing.push(index);
+        Ok(())
+    }
 }
 
 impl Node for Instrument {
     fn process<'a, 'b, 'c>(
         &'a mut self,
-        inputs: &'b [crate::sample::Sample],
-        outputs: &'c mut Vec<crate::sample::Sample>,
+        _: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
     ) -> Result<()> {
+        while let Ok(built) = self.built.try_recv() {
+            self.receive(built)?;
+        }
+        while let Some(request) = self.retired.pop() {
+            if let Err(TrySendError::Full(request)) = self.requests.try_send(request) {
+                self.retired.push(request);
+                break;
+            }
+        }
+        self.look_ahead();
+
         let sample = self.sample;
+        while let Some(&index) = self.queue.last() {
+            let (start, _) = self.voices[index]
+                .span
+                .expect("only playable tones are queued");
+            if sample < start {
+                break;
+            }
+            self.queue.pop();
+            self.start(index)?;
+        }
+
+        let (voices, requests, retired) = (&mut self.voices, &self.requests, &mut self.retired);
+        self.playing.retain(|&index| {
+            let voice = &mut voices[index];
+            if voice.span.is_some_and(|(_, end)| sample < end) {
+                return true;
+            }
+            if let State::Ready(node) = mem::replace(&mut voice.state, State::Idle) {
+                retire(requests, retired, node);
+            }
+            false
+        });
+
+        for &index in &self.playing {
+            if let State::Ready(node) = &self.voices[index].state {
+                let frequency = [self.tones[index].frequency.into()];
+                node.lock()
+                    .expect("poisoned")
+                    .process(&frequency, outputs)?;
+            }
+        }
         self.sample += 1;
+        Ok(())
+    }
 
-        if self.queue.is_empty() && self.playing.is_empty() {
-            return Ok(());
+    /// Prepare every built voice, and every voice built from here on.
+    /// Sounding tones carry on, unless the sample rate changed, which moves
+    /// every tone and so seeks them all to the new position.
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.requests
+            .send(Request::Prepare(*context))
+            .map_err(stopped)?;
+        self.context = Some(*context);
+        let resampled = context.sample_rate != self.sample_rate;
+        if resampled {
+            let ratio = context.sample_rate as f64 / self.sample_rate as f64;
+            self.sample_rate = context.sample_rate;
+            self.sample = (self.sample as f64 * ratio) as u64;
+            for index in 0..self.voices.len() {
+                self.voices[index].span = self.span(self.tones[index]);
+            }
+        }
+        for voice in &self.voices {
+            if let State::Ready(node) = &voice.state {
+                node.lock().expect("poisoned").prepare(context)?;
+            }
+        }
+        if resampled {
+            self.seek(self.sample)?;
         }
+        Ok(())
+    }
 
-        // Spawn all ready queued tones.
-        loop {
-            let Some(tone) = self.queue.peek() else {
-                break;
-            };
-            if sample < tone.0.start_sample {
-                break;
+    fn reset(&mut self) -> Result<()> {
+        self.seek(0)
+    }
+
+    /// Every built voice is reset, or freed if its tone is over, so tones
+    /// that overlap the seek point start partway through.
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.sample = sample;
+        self.queue.clear();
+        self.playing.clear();
+        for index in 0..self.voices.len() {
+            let voice = &mut self.voices[index];
+            match voice.span {
+                Some((start, end)) if sample < end => {
+                    if let State::Ready(node) = &voice.state {
+                        if start >= sample {
+                            node.lock().expect("poisoned").reset()?;
+                        }
+                    }
+                    self.queue.push(index);
+                }
+                _ => {
+                    if let State::Ready(node) = mem::replace(&mut voice.state, State::Idle) {
+                        retire(&self.requests, &mut self.retired, node);
+                    }
+                }
             }
+        }
+        let mut queue = mem::take(&mut self.queue);
+        queue.sort_unstable_by_key(|&index| Reverse(self.order(index)));
+        self.queue = queue;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::oscillators::Sine;
+    use std::time::Instant;
 
-            let tone = self.queue.pop().unwrap().0;
-            let constant_value = Arc::new(Mutex::new(ConstantValue::new(tone.tone.frequency)));
-            let node = (self.node_creator)(tone.tone)?;
+    type Voices = Arc<Mutex<Vec<Arc<Mutex<Sine>>>>>;
+
+    /// An instrument of sine voices, and every voice it has built.
+    fn sines() -> (Instrument, Voices) {
+        let voices: Voices = Default::default();
+        let created = voices.clone();
+        let instrument = Instrument::new(48000, move |tone| {
+            let voice = Arc::new(Mutex::new(Sine::new(48000, tone.frequency)));
+            created.lock().expect("poisoned").push(voice.clone());
+            Ok(voice as Arc<Mutex<dyn Node>>)
+        });
+        (instrument, voices)
+    }
 
-            let mut graph = Graph::default();
-            graph.connect(constant_value.clone(), node.clone(), None);
-            graph.output(node.clone(), None);
-            let graph = Arc::new(Mutex::new(graph));
-            self.graph.output(graph.clone(), None);
-            self.playing.push(Reverse(PlayingTone {
-                end_sample: tone.end_sample,
-                graph,
-            }));
+    fn tone(start: f64, length: f64) -> Tone {
+        Tone {
+            start: Timestamp::from_seconds(start).unwrap(),
+            length: Duration::from_seconds(length).unwrap(),
+            frequency: 440.0,
         }
+    }
+
+    #[test]
+    fn prepares_tones_after_seeking() {
+        let (mut instrument, voices) = sines();
+        instrument.add_tone(tone(0.0, 1.0));
+        instrument.prepare(&Context::new(96000)).unwrap();
+        instrument.seek(0).unwrap();
+        instrument.add_tone(tone(0.0, 0.5));
+        let mut outputs: Vec<Sample> = Vec::new();
+        instrument.process(&[], &mut outputs).unwrap();
+        let voices = voices.lock().expect("poisoned");
+        assert_eq!(voices.len(), 2);
+        for voice in voices.iter() {
+            assert_eq!(voice.lock().expect("poisoned").sample_rate(), 96000);
+        }
+    }
 
-        while self
-            .playing
-            .peek()
-            .is_some_and(|tone| sample >= tone.0.end_sample)
-        {
-            let tone = self.playing.pop().unwrap().0;
-            self.graph.remove(tone.graph);
+    /// Counts how often it has been reset or sought.
+    #[derive(Debug)]
+    struct Restarts(Arc<Mutex<usize>>);
+
+    impl Node for Restarts {
+        fn process<'a, 'b, 'c>(
+            &'a mut self,
+            _: &'b [Sample],
+            outputs: &'c mut Vec<Sample>,
+        ) -> Result<()> {
+            outputs.push(Sample::from(1.0));
+            Ok(())
+        }
+
+        fn reset(&mut self) -> Result<()> {
+            *self.0.lock().expect("poisoned") += 1;
+            Ok(())
         }
 
-        // Play graph
-        self.graph.process(inputs, outputs)
+        fn seek(&mut self, _: u64) -> Result<()> {
+            self.reset()
+        }
+    }
+
+    #[test]
+    fn keeps_sounding_tones_when_prepared_again() {
+        let restarts = Arc::new(Mutex::new(0));
+        let counter = restarts.clone();
+        let mut instrument = Instrument::new(48000, move |_| {
+            Ok(Arc::new(Mutex::new(Restarts(counter.clone()))) as Arc<Mutex<dyn Node>>)
+        });
+        instrument.add_tone(tone(0.0, 1.0));
+        instrument.prepare(&Context::new(48000)).unwrap();
+        let mut outputs = Vec::new();
+        for _ in 0..100 {
+            instrument.process(&[], &mut outputs).unwrap();
+        }
+        let before = *restarts.lock().expect("poisoned");
+        instrument.prepare(&Context::new(48000)).unwrap();
+        outputs.clear();
+        instrument.process(&[], &mut outputs).unwrap();
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(*restarts.lock().expect("poisoned"), before);
+    }
+
+    #[test]
+    fn frees_voices_after_their_tones_end() {
+        let (mut instrument, voices) = sines();
+        instrument.add_tone(tone(0.0, 0.5));
+        instrument.add_tone(tone(1.0, 0.5));
+        instrument.add_tone(tone(100.0, 0.5));
+        assert!(voices.lock().expect("poisoned").is_empty());
+        let mut sounding = Vec::new();
+        let mut outputs = Vec::new();
+        for _ in 0..96000 {
+            outputs.clear();
+            instrument.process(&[], &mut outputs).unwrap();
+            sounding.push(outputs.len());
+        }
+        assert_eq!(sounding[0], 1);
+        assert_eq!(sounding[24000], 0);
+        assert_eq!(sounding[48000], 1);
+        assert_eq!(sounding[72000], 0);
+        let voices = voices.lock().expect("poisoned");
+        assert_eq!(voices.len(), 2);
+        let deadline = Instant::now() + std::time::Duration::from_secs(5);
+        while voices.iter().any(|voice| Arc::strong_count(voice) > 1) {
+            assert!(Instant::now() < deadline, "voices were not freed");
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
     }
 }
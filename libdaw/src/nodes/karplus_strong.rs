@@ -0,0 +1,232 @@
+use crate::{sample::Sample, Context, Node, Result};
+use std::collections::VecDeque;
+
+/// The lowest frequency the string is able to be tuned to.
+const MIN_FREQUENCY: f64 = 20.0;
+
+/// An extended Karplus-Strong plucked string.
+///
+/// The first input stream is the frequency, and the second is an excitation
+/// trigger, which plucks the string whenever it rises above zero.  If there
+/// is no trigger stream, the string is plucked on the first sample, so this
+/// works directly as an instrument voice.
+#[derive(Debug)]
+pub struct KarplusStrong {
+    /// The frequency if no input comes in.
+    pub frequency: f64,
+
+    /// The decay stretch factor, from 0 to 1.  0.5 is the classic
+    /// Karplus-Strong averaging filter.  Values away from 0.5 decay more
+    /// slowly and sound brighter.
+    pub stretch: f64,
+
+    /// A loss multiplier applied on every trip around the loop, from 0 to 1.
+    /// Lower values decay faster.
+    pub loss: f64,
+
+    /// The pick position as a ratio of the length of the string, from 0 to
+    /// 1.  0 disables the pick position filter.
+    pub pick_position: f64,
+
+    sample_rate: f64,
+
+    /// The past outputs of the string, as a ring buffer.
+    buffer: Box<[f64]>,
+    write: usize,
+
+    /// The excitation waiting to be fed into the string.
+    excitation: VecDeque<f64>,
+
+    /// Scratch space for generating the excitation.
+    noise: Vec<f64>,
+
+    /// The state of the fractional delay all-pass filter.
+    allpass_input: f64,
+    allpass_output: f64,
+
+    trigger: f64,
+    started: bool,
+
+    /// The seed the noise generator starts again from when reset.
+    seed: u64,
+    rng: fastrand::Rng,
+}
+
+impl KarplusStrong {
+    pub fn new(sample_rate: u32, frequency: f64) -> Self {
+        let capacity = (sample_rate as f64 / MIN_FREQUENCY).ceil() as usize + 4;
+        let seed = fastrand::u64(..);
+        Self {
+            frequency,
+            stretch: 0.5,
+            loss: 0.996,
+            pick_position: 0.0,
+            sample_rate: sample_rate as f64,
+            buffer: vec![0.0; capacity].into(),
+            write: 0,
+            excitation: VecDeque::with_capacity(capacity),
+            noise: Vec::with_capacity(capacity),
+            allpass_input: 0.0,
+            allpass_output: 0.0,
+            trigger: 0.0,
+            started: false,
+            seed,
+            rng: fastrand::Rng::with_seed(seed),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    /// Seed the noise generator used for excitation, for reproducible
+    /// output.  Resetting starts it again from this seed.
+    pub fn seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng.seed(seed);
+    }
+
+    /// The period of the given frequency in samples, clamped to what the
+    /// delay line can hold.
+    fn period(&self, frequency: f64) -> f64 {
+        let max = (self.buffer.len() - 3) as f64;
+        if frequency > 0.0 {
+            (self.sample_rate / frequency).clamp(2.0, max)
+        } else {
+            max
+        }
+    }
+
+    /// Excite the string with a burst of noise one period long, filtered by
+    /// the pick position comb.
+    pub fn pluck(&mut self) {
+        let period = self.period(self.frequency).round() as usize;
+        self.pluck_period(period);
+    }
+
+    fn pluck_period(&mut self, period: usize) {
+        self.noise.clear();
+        self.noise
+            .extend((0..period).map(|_| self.rng.f64() * 2.0 - 1.0));
+        let noise = &self.noise;
+        let pick = (self.pick_position.clamp(0.0, 1.0) * period as f64).round() as usize;
+        self.excitation.clear();
+        self.excitation
+            .extend(noise.iter().enumerate().map(|(i, &value)| {
+                if pick > 0 && i >= pick {
+                    (value - noise[i - pick]) * 0.5
+                } else {
+                    value
+                }
+            }));
+    }
+
+    fn read(&self, delay: usize) -> f64 {
+        let len = self.buffer.len();
+        self.buffer[(self.write + len - delay) % len]
+    }
+}
+
+impl KarplusStrong {
+    /// Recreate the string at the given sample rate, keeping the settings.
+    fn rebuild(&mut self, sample_rate: u32) {
+        *self = Self {
+            stretch: self.stretch,
+            loss: self.loss,
+            pick_position: self.pick_position,
+            seed: self.seed,
+            rng: self.rng.clone(),
+            ..Self::new(sample_rate, self.frequency)
+        };
+    }
+}
+
+impl Node for KarplusStrong {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let frequency = inputs
+            .first()
+            .and_then(|input| input.first().copied())
+            .unwrap_or(self.frequency);
+        let period = self.period(frequency);
+
+        match inputs.get(1).and_then(|input| input.first().copied()) {
+            Some(trigger) => {
+                if trigger > 0.0 && self.trigger <= 0.0 {
+                    self.pluck_period(period.round() as usize);
+                }
+                self.trigger = trigger;
+            }
+            None => {
+                if !self.started {
+                    self.pluck_period(period.round() as usize);
+                }
+            }
+        }
+        self.started = true;
+
+        // The loop filter delays by `stretch` samples, and the all-pass
+        // filter makes up the fractional remainder.  The all-pass delay is
+        // kept between 0.1 and 1.1 samples, where it behaves well.
+        let stretch = self.stretch.clamp(0.0, 1.0);
+        let integer_delay = (period - stretch - 0.1).floor().max(1.0);
+        let fraction = period - stretch - integer_delay;
+        let coefficient = (1.0 - fraction) / (1.0 + fraction);
+        let integer_delay = integer_delay as usize;
+
+        let filtered = self.loss
+            * ((1.0 - stretch) * self.read(integer_delay) + stretch * self.read(integer_delay + 1));
+        let allpassed =
+            coefficient * filtered + self.allpass_input - coefficient * self.allpass_output;
+        self.allpass_input = filtered;
+        self.allpass_output = allpassed;
+
+        let output = allpassed + self.excitation.pop_front().unwrap_or(0.0);
+        self.buffer[self.write] = output;
+        self.write = (self.write + 1) % self.buffer.len();
+        outputs.push(output.into());
+        Ok(())
+    }
+
+    /// Changing the sample rate silences the string until the next pluck.
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        if context.sample_rate as f64 != self.sample_rate {
+            let (trigger, started) = (self.trigger, self.started);
+            self.rebuild(context.sample_rate);
+            self.trigger = trigger;
+            self.started = started;
+        }
+        Ok(())
+    }
+
+    /// Resetting plucks the string again on the next sample, unless a
+    /// trigger stream is present, with the same noise as the first time.
+    fn reset(&mut self) -> Result<()> {
+        self.rebuild(self.sample_rate as u32);
+        self.rng.seed(self.seed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plucks_the_same_after_reset() {
+        let mut string = KarplusStrong::new(48000, 440.0);
+        let render = |string: &mut KarplusStrong| {
+            let mut outputs = Vec::new();
+            for _ in 0..1000 {
+                string.process(&[], &mut outputs).unwrap();
+            }
+            outputs.iter().map(|sample| sample[0]).collect::<Vec<_>>()
+        };
+        let first = render(&mut string);
+        string.reset().unwrap();
+        assert_eq!(render(&mut string), first);
+    }
+}
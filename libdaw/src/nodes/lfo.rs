@@ -0,0 +1,219 @@
+use crate::{
+    metronome::{Beat, Metronome},
+    sample::Sample,
+    time::Timestamp,
+    Context, Node, Result,
+};
+use std::f64::consts::{PI, TAU};
+
+/// The waveform of a low frequency oscillator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Shape {
+    #[default]
+    Sine,
+    Triangle,
+
+    /// A rising ramp.
+    Saw,
+    Square,
+
+    /// A new random value each cycle, held until the next.
+    SampleAndHold,
+
+    /// A new random value each cycle, glided to from the previous one.
+    SmoothRandom,
+}
+
+/// How fast a low frequency oscillator runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rate {
+    Hertz(f64),
+
+    /// The length of one cycle in beats, following the oscillator's
+    /// metronome.
+    Beats(Beat),
+}
+
+/// A low frequency oscillator for control signals.
+///
+/// The first input stream, if present, overrides the rate in Hz.  The second
+/// input stream, if present, is a gate which restarts the cycle each time it
+/// rises above zero.
+#[derive(Debug)]
+pub struct Lfo {
+    pub shape: Shape,
+    pub rate: Rate,
+
+    /// Resolves the rate when it is in beats.
+    pub metronome: Metronome,
+
+    /// The phase offset, from 0 to 1.
+    pub phase: f64,
+
+    /// Output from 0 to 1 instead of -1 to 1.
+    pub unipolar: bool,
+
+    sample_rate: f64,
+
+    /// Samples since the start, for resolving beats.
+    samples: u64,
+
+    /// Cycles since the last restart, when running in Hz.
+    cycles: f64,
+
+    /// The beat of the last restart, when running in beats.
+    restart_beat: f64,
+
+    cycle: f64,
+    gate: bool,
+    rng: fastrand::Rng,
+    previous: f64,
+    target: f64,
+}
+
+impl Lfo {
+    pub fn new(sample_rate: u32, shape: Shape, rate: Rate) -> Self {
+        let mut rng = fastrand::Rng::new();
+        let target = rng.f64() * 2.0 - 1.0;
+        Self {
+            shape,
+            rate,
+            metronome: Metronome::default(),
+            phase: 0.0,
+            unipolar: false,
+            sample_rate: sample_rate as f64,
+            samples: 0,
+            cycles: 0.0,
+            restart_beat: 0.0,
+            cycle: 0.0,
+            gate: false,
+            rng,
+            previous: target,
+            target,
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    /// The next sample to be processed.
+    pub fn sample(&self) -> u64 {
+        self.samples
+    }
+
+    /// Reseed the random shapes.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng.seed(seed);
+    }
+
+    /// Restart the cycle from the phase offset.
+    pub fn retrigger(&mut self) {
+        self.cycles = 0.0;
+        self.restart_beat = self.beat();
+        self.cycle = 0.0;
+    }
+
+    fn beat(&self) -> f64 {
+        let time =
+            Timestamp::from_seconds(self.samples as f64 / self.sample_rate).expect("valid time");
+        self.metronome.time_to_beat(time).get()
+    }
+}
+
+impl Node for Lfo {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let gate = inputs
+            .get(1)
+            .and_then(|input| input.first().copied())
+            .is_some_and(|gate| gate > 0.0);
+        if gate && !self.gate {
+            self.retrigger();
+        }
+        self.gate = gate;
+
+        let override_rate = inputs.first().and_then(|input| input.first().copied());
+        let cycles = match (override_rate, self.rate) {
+            (Some(_), _) | (None, Rate::Hertz(_)) => self.cycles,
+            (None, Rate::Beats(length)) => (self.beat() - self.restart_beat) / length.get(),
+        };
+        let position = cycles + self.phase;
+        let cycle = position.floor();
+        if cycle != self.cycle {
+            self.cycle = cycle;
+            self.previous = self.target;
+            self.target = self.rng.f64() * 2.0 - 1.0;
+        }
+        let t = position - cycle;
+        let value = match self.shape {
+            Shape::Sine => (t * TAU).sin(),
+            Shape::Triangle => {
+                if t < 0.25 {
+                    4.0 * t
+                } else if t < 0.75 {
+                    2.0 - 4.0 * t
+                } else {
+                    4.0 * t - 4.0
+                }
+            }
+            Shape::Saw => 2.0 * t - 1.0,
+            Shape::Square => {
+                if t < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Shape::SampleAndHold => self.target,
+            Shape::SmoothRandom => {
+                let blend = (1.0 - (t * PI).cos()) / 2.0;
+                self.previous + (self.target - self.previous) * blend
+            }
+        };
+        outputs.push(
+            if self.unipolar {
+                (value + 1.0) / 2.0
+            } else {
+                value
+            }
+            .into(),
+        );
+
+        self.samples += 1;
+        match (override_rate, self.rate) {
+            (Some(frequency), _) | (None, Rate::Hertz(frequency)) => {
+                self.cycles += frequency / self.sample_rate;
+            }
+            (None, Rate::Beats(_)) => (),
+        }
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        let sample_rate = context.sample_rate as f64;
+        // Keep the elapsed time, which tempo-synced rates depend on.
+        self.samples = (self.samples as f64 * sample_rate / self.sample_rate) as u64;
+        self.sample_rate = sample_rate;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.seek(0)
+    }
+
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.samples = sample;
+        self.cycles = match self.rate {
+            Rate::Hertz(frequency) => frequency * sample as f64 / self.sample_rate,
+            Rate::Beats(_) => 0.0,
+        };
+        self.restart_beat = 0.0;
+        self.cycle = 0.0;
+        self.gate = false;
+        Ok(())
+    }
+}
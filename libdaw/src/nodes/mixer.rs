@@ -0,0 +1,224 @@
+//! A mixing console built on a [Graph], with channel strips, auxiliary
+//! buses, and a master bus.
+
+pub mod strip;
+
+pub use strip::{AuxSend, Strip};
+
+use crate::{nodes::Graph, sample::Sample, Context, Node, Result};
+use std::sync::{Arc, Mutex};
+
+/// A mixer node.
+///
+/// Each input stream feeds the channel strip at the same index, in the order
+/// the channels were added.  Every channel and bus feeds the master bus,
+/// whose output is the mixer's only output stream.  When any channel is
+/// soloed, all channels that are not soloed are silenced; buses are
+/// unaffected.
+#[derive(Debug)]
+pub struct Mixer {
+    channels: Vec<(String, Arc<Mutex<Strip>>)>,
+    buses: Vec<(String, Arc<Mutex<Strip>>)>,
+    master: Arc<Mutex<Strip>>,
+    graph: Graph,
+}
+
+impl Default for Mixer {
+    fn default() -> Self {
+        let mut mixer = Self {
+            channels: Vec::new(),
+            buses: Vec::new(),
+            master: Default::default(),
+            graph: Graph::default(),
+        };
+        mixer
+            .rebuild()
+            .expect("an unprepared graph always connects");
+        mixer
+    }
+}
+
+fn find<'a>(
+    strips: &'a [(String, Arc<Mutex<Strip>>)],
+    name: &str,
+) -> Option<&'a Arc<Mutex<Strip>>> {
+    strips
+        .iter()
+        .find(|(strip_name, _)| strip_name == name)
+        .map(|(_, strip)| strip)
+}
+
+fn remove(strips: &mut Vec<(String, Arc<Mutex<Strip>>)>, name: &str) -> bool {
+    let length = strips.len();
+    strips.retain(|(strip_name, _)| strip_name != name);
+    strips.len() != length
+}
+
+impl Mixer {
+    /// Rewire the internal graph to match the strips and sends.  The new
+    /// graph keeps the old one's context, so new strips are prepared.
+    fn rebuild(&mut self) -> Result<()> {
+        let mut graph = Graph::default();
+        if let Some(context) = self.graph.context() {
+            graph.prepare(context)?;
+        }
+        let master: Arc<Mutex<dyn Node>> = self.master.clone();
+        for (index, (_, channel)) in self.channels.iter().enumerate() {
+            graph.input(channel.clone(), Some(index))?;
+            graph.connect(channel.clone(), master.clone(), Some(0))?;
+            let sends = channel.lock().expect("poisoned").sends.clone();
+            for (stream, send) in sends.iter().enumerate() {
+                if let Some(bus) = find(&self.buses, &send.bus) {
+                    graph.connect(channel.clone(), bus.clone(), Some(stream + 1))?;
+                }
+            }
+        }
+        for (_, bus) in &self.buses {
+            graph.connect(bus.clone(), master.clone(), Some(0))?;
+        }
+        graph.output(master, Some(0))?;
+        self.graph = graph;
+        Ok(())
+    }
+
+    /// Add a channel strip, fed by the next input stream.
+    pub fn add_channel(&mut self, name: impl Into<String>) -> Result<Arc<Mutex<Strip>>> {
+        let name = name.into();
+        if find(&self.channels, &name).is_some() {
+            return Err(format!("channel {name:?} already exists").into());
+        }
+        let strip = Arc::new(Mutex::new(Strip::default()));
+        self.channels.push((name, strip.clone()));
+        self.rebuild()?;
+        Ok(strip)
+    }
+
+    /// Remove a channel strip.  Later channels move to the previous input
+    /// stream.
+    pub fn remove_channel(&mut self, name: &str) -> Result<bool> {
+        let removed = remove(&mut self.channels, name);
+        if removed {
+            self.rebuild()?;
+        }
+        Ok(removed)
+    }
+
+    /// Add an auxiliary bus.
+    pub fn add_bus(&mut self, name: impl Into<String>) -> Result<Arc<Mutex<Strip>>> {
+        let name = name.into();
+        if find(&self.buses, &name).is_some() {
+            return Err(format!("bus {name:?} already exists").into());
+        }
+        let strip = Arc::new(Mutex::new(Strip::default()));
+        self.buses.push((name, strip.clone()));
+        self.rebuild()?;
+        Ok(strip)
+    }
+
+    /// Remove an auxiliary bus, along with all sends to it.
+    pub fn remove_bus(&mut self, name: &str) -> Result<bool> {
+        let removed = remove(&mut self.buses, name);
+        if removed {
+            for (_, channel) in &self.channels {
+                channel
+                    .lock()
+                    .expect("poisoned")
+                    .sends
+                    .retain(|send| send.bus != name);
+            }
+            self.rebuild()?;
+        }
+        Ok(removed)
+    }
+
+    pub fn channel(&self, name: &str) -> Option<Arc<Mutex<Strip>>> {
+        find(&self.channels, name).cloned()
+    }
+
+    pub fn bus(&self, name: &str) -> Option<Arc<Mutex<Strip>>> {
+        find(&self.buses, name).cloned()
+    }
+
+    pub fn master(&self) -> Arc<Mutex<Strip>> {
+        self.master.clone()
+    }
+
+    /// The channel names, in input stream order.
+    pub fn channel_names(&self) -> impl Iterator<Item = &str> {
+        self.channels.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub fn bus_names(&self) -> impl Iterator<Item = &str> {
+        self.buses.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Send a channel to a bus, or update the existing send between them.
+    pub fn send(&mut self, channel: &str, bus: &str, level: f64, pre_fader: bool) -> Result<()> {
+        let Some(strip) = find(&self.channels, channel) else {
+            return Err(format!("no channel named {channel:?}").into());
+        };
+        if find(&self.buses, bus).is_none() {
+            return Err(format!("no bus named {bus:?}").into());
+        }
+        let mut strip = strip.lock().expect("poisoned");
+        if let Some(send) = strip.sends.iter_mut().find(|send| send.bus == bus) {
+            send.level = level;
+            send.pre_fader = pre_fader;
+        } else {
+            strip.sends.push(AuxSend {
+                bus: bus.into(),
+                level,
+                pre_fader,
+            });
+            drop(strip);
+            self.rebuild()?;
+        }
+        Ok(())
+    }
+
+    /// Remove the send from a channel to a bus.
+    pub fn remove_send(&mut self, channel: &str, bus: &str) -> Result<bool> {
+        let Some(strip) = find(&self.channels, channel) else {
+            return Ok(false);
+        };
+        let mut strip = strip.lock().expect("poisoned");
+        let length = strip.sends.len();
+        strip.sends.retain(|send| send.bus != bus);
+        let removed = strip.sends.len() != length;
+        drop(strip);
+        if removed {
+            self.rebuild()?;
+        }
+        Ok(removed)
+    }
+}
+
+impl Node for Mixer {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let soloed = self
+            .channels
+            .iter()
+            .any(|(_, channel)| channel.lock().expect("poisoned").solo);
+        for (_, channel) in &self.channels {
+            let mut channel = channel.lock().expect("poisoned");
+            channel.silenced = soloed && !channel.solo;
+        }
+        self.graph.process(inputs, outputs)
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.graph.prepare(context)
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.graph.reset()
+    }
+
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.graph.seek(sample)
+    }
+}
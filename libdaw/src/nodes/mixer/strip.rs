@@ -0,0 +1,117 @@
+use crate::{nodes::channels::PanLaw, sample::Sample, Context, Node, Result};
+use std::sync::{Arc, Mutex};
+
+/// A send from a channel strip to an auxiliary bus.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuxSend {
+    pub bus: String,
+    pub level: f64,
+
+    /// Tap the signal before the strip's gain and pan rather than after.
+    pub pre_fader: bool,
+}
+
+/// A mixer channel strip, used for channels, buses, and the master bus.
+///
+/// All input streams are summed and run through the inserts in order.  The
+/// first output stream is the signal after gain and pan, followed by one
+/// stream per send.
+#[derive(Debug)]
+pub struct Strip {
+    pub gain: f64,
+
+    /// The pan position, from -1 (left) to 1 (right).
+    pub pan: f64,
+
+    pub law: PanLaw,
+    pub mute: bool,
+    pub solo: bool,
+
+    /// Nodes processed in series before the fader.  Each receives the strip
+    /// signal as its only input stream, and its output streams are summed.
+    pub inserts: Vec<Arc<Mutex<dyn Node>>>,
+
+    pub(super) sends: Vec<AuxSend>,
+
+    /// Muted because another strip is soloed.
+    pub(super) silenced: bool,
+
+    buffer: Vec<Sample>,
+}
+
+impl Default for Strip {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            pan: 0.0,
+            law: PanLaw::default(),
+            mute: false,
+            solo: false,
+            inserts: Vec::new(),
+            sends: Vec::new(),
+            silenced: false,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl Strip {
+    /// The sends of this strip, in output stream order after the first.
+    pub fn sends(&self) -> &[AuxSend] {
+        &self.sends
+    }
+}
+
+impl Node for Strip {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        if self.mute || self.silenced {
+            outputs.extend(std::iter::repeat_with(Sample::default).take(1 + self.sends.len()));
+            return Ok(());
+        }
+        let mut signal: Sample = inputs.iter().sum();
+        for insert in &self.inserts {
+            self.buffer.clear();
+            insert
+                .lock()
+                .expect("poisoned")
+                .process(std::slice::from_ref(&signal), &mut self.buffer)?;
+            signal = self.buffer.iter().sum();
+        }
+        let post = outputs.len();
+        outputs.push(self.law.apply(self.pan, &(&signal * self.gain)));
+        for send in &self.sends {
+            let source = if send.pre_fader {
+                &signal
+            } else {
+                &outputs[post]
+            };
+            outputs.push(source * send.level);
+        }
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        for insert in &self.inserts {
+            insert.lock().expect("poisoned").prepare(context)?;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        for insert in &self.inserts {
+            insert.lock().expect("poisoned").reset()?;
+        }
+        Ok(())
+    }
+
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        for insert in &self.inserts {
+            insert.lock().expect("poisoned").seek(sample)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+//! Modulation effects driven by an internal low frequency oscillator.
+//!
+//! Each of these takes the audio as the first input stream.  A second input
+//! stream, if present, replaces the internal oscillator with its first
+//! channel, which should range from -1 to 1.
+
+pub mod chorus;
+pub mod flanger;
+mod modulated_delay;
+pub mod phaser;
+
+pub use chorus::Chorus;
+pub use flanger::Flanger;
+pub use phaser::Phaser;
+
+use crate::{
+    nodes::{
+        lfo::{Rate, Shape},
+        Lfo,
+    },
+    sample::Sample,
+    Context, Node as _, Result,
+};
+
+/// A sine [`Lfo`] per channel, each `stereo_phase` of a cycle ahead of the
+/// one before.
+#[derive(Debug)]
+struct Oscillators {
+    sample_rate: u32,
+
+    /// The next sample, which the oscillators of new channels start from.
+    sample: u64,
+
+    lfos: Vec<Lfo>,
+    values: Vec<f64>,
+    outputs: Vec<Sample>,
+}
+
+impl Oscillators {
+    fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            sample: 0,
+            lfos: Vec::new(),
+            values: Vec::new(),
+            outputs: Vec::with_capacity(1),
+        }
+    }
+
+    /// Run the oscillators of at least `channels` channels for one sample,
+    /// returning each channel's value, from -1 to 1.
+    fn process(&mut self, channels: usize, rate: f64, stereo_phase: f64) -> Result<&[f64]> {
+        while self.lfos.len() < channels {
+            let mut lfo = Lfo::new(self.sample_rate, Shape::Sine, Rate::Hertz(rate));
+            lfo.seek(self.sample)?;
+            self.lfos.push(lfo);
+        }
+        self.values.clear();
+        for (channel, lfo) in self.lfos.iter_mut().enumerate() {
+            lfo.rate = Rate::Hertz(rate);
+            lfo.phase = channel as f64 * stereo_phase;
+            self.outputs.clear();
+            lfo.process(&[], &mut self.outputs)?;
+            self.values.push(self.outputs[0][0]);
+        }
+        self.sample += 1;
+        Ok(&self.values)
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.sample =
+            (self.sample as f64 * context.sample_rate as f64 / self.sample_rate as f64) as u64;
+        self.sample_rate = context.sample_rate;
+        for lfo in &mut self.lfos {
+            lfo.prepare(context)?;
+        }
+        Ok(())
+    }
+
+    /// Move to where the oscillators would be after `sample` samples at
+    /// `rate`.
+    fn seek(&mut self, rate: f64, sample: u64) -> Result<()> {
+        self.sample = sample;
+        for lfo in &mut self.lfos {
+            lfo.rate = Rate::Hertz(rate);
+            lfo.seek(sample)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use super::modulated_delay::{ModulatedDelay, Settings};
+use crate::{sample::Sample, time::Duration, Context, Node, Result};
+
+/// The longest delay plus depth a chorus can reach, in seconds.
+const MAX_DELAY: f64 = 0.1;
+
+/// A chorus, mixing in a copy of the signal with a slowly wavering delay.
+#[derive(Debug)]
+pub struct Chorus {
+    /// The oscillator rate, in Hz.
+    pub rate: f64,
+
+    /// The center delay time.
+    pub delay: Duration,
+
+    /// How far the delay time swings from the center.
+    pub depth: Duration,
+
+    pub feedback: f64,
+
+    /// The ratio of delayed signal in the output, from 0 to 1.
+    pub mix: f64,
+
+    /// The oscillator phase offset between adjacent channels, from 0 to 1.
+    pub stereo_phase: f64,
+
+    delay_line: ModulatedDelay,
+}
+
+impl Chorus {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            rate: 0.8,
+            delay: Duration::from_seconds(0.02).expect("valid"),
+            depth: Duration::from_seconds(0.005).expect("valid"),
+            feedback: 0.0,
+            mix: 0.5,
+            stereo_phase: 0.25,
+            delay_line: ModulatedDelay::new(sample_rate, MAX_DELAY),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.delay_line.sample_rate()
+    }
+}
+
+impl Node for Chorus {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        outputs.extend(self.delay_line.process(
+            inputs,
+            Settings {
+                rate: self.rate,
+                delay: self.delay.seconds(),
+                depth: self.depth.seconds(),
+                feedback: self.feedback,
+                mix: self.mix,
+                stereo_phase: self.stereo_phase,
+            },
+        )?);
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.delay_line.prepare(context)
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.seek(0)
+    }
+
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.delay_line.seek(self.rate, sample)
+    }
+}
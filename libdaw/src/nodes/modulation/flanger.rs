@@ -0,0 +1,81 @@
+use super::modulated_delay::{ModulatedDelay, Settings};
+use crate::{sample::Sample, time::Duration, Context, Node, Result};
+
+/// The longest delay plus depth a flanger can reach, in seconds.
+const MAX_DELAY: f64 = 0.02;
+
+/// A flanger, mixing in a copy of the signal with a very short sweeping
+/// delay, with feedback to deepen the comb filtering.
+#[derive(Debug)]
+pub struct Flanger {
+    /// The oscillator rate, in Hz.
+    pub rate: f64,
+
+    /// The center delay time.
+    pub delay: Duration,
+
+    /// How far the delay time swings from the center.
+    pub depth: Duration,
+
+    /// How much of the delayed signal is fed back, from -1 to 1.
+    pub feedback: f64,
+
+    /// The ratio of delayed signal in the output, from 0 to 1.
+    pub mix: f64,
+
+    /// The oscillator phase offset between adjacent channels, from 0 to 1.
+    pub stereo_phase: f64,
+
+    delay_line: ModulatedDelay,
+}
+
+impl Flanger {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            rate: 0.25,
+            delay: Duration::from_seconds(0.0025).expect("valid"),
+            depth: Duration::from_seconds(0.002).expect("valid"),
+            feedback: 0.5,
+            mix: 0.5,
+            stereo_phase: 0.25,
+            delay_line: ModulatedDelay::new(sample_rate, MAX_DELAY),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.delay_line.sample_rate()
+    }
+}
+
+impl Node for Flanger {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        outputs.extend(self.delay_line.process(
+            inputs,
+            Settings {
+                rate: self.rate,
+                delay: self.delay.seconds(),
+                depth: self.depth.seconds(),
+                feedback: self.feedback,
+                mix: self.mix,
+                stereo_phase: self.stereo_phase,
+            },
+        )?);
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.delay_line.prepare(context)
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.seek(0)
+    }
+
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.delay_line.seek(self.rate, sample)
+    }
+}
@@ -0,0 +1,93 @@
+use super::Oscillators;
+use crate::{
+    nodes::delay_line::{DelayLine, Interpolation},
+    sample::Sample,
+    Context, Result,
+};
+
+/// The settings for one sample of a modulated delay.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Settings {
+    pub rate: f64,
+
+    /// The center delay, in seconds.
+    pub delay: f64,
+
+    /// How far the delay swings from the center, in seconds.
+    pub depth: f64,
+    pub feedback: f64,
+    pub mix: f64,
+    pub stereo_phase: f64,
+}
+
+/// A delay line per channel whose delay time follows an oscillator, the
+/// shared core of the chorus and flanger.
+#[derive(Debug)]
+pub(super) struct ModulatedDelay {
+    sample_rate: f64,
+    max_delay_time: f64,
+    max_delay: usize,
+    lines: Vec<DelayLine>,
+    oscillators: Oscillators,
+}
+
+impl ModulatedDelay {
+    pub fn new(sample_rate: u32, max_delay: f64) -> Self {
+        Self {
+            sample_rate: sample_rate as f64,
+            max_delay_time: max_delay,
+            max_delay: (max_delay * sample_rate as f64).ceil() as usize,
+            lines: Vec::new(),
+            oscillators: Oscillators::new(sample_rate),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    /// Switch to a new sample rate, dropping the delayed audio if it changed.
+    pub fn prepare(&mut self, context: &Context) -> Result<()> {
+        let sample_rate = context.sample_rate as f64;
+        if sample_rate != self.sample_rate {
+            self.sample_rate = sample_rate;
+            self.max_delay = (self.max_delay_time * self.sample_rate).ceil() as usize;
+            self.lines.clear();
+        }
+        self.oscillators.prepare(context)
+    }
+
+    /// Silence the lines and move the oscillators to `sample`.
+    pub fn seek(&mut self, rate: f64, sample: u64) -> Result<()> {
+        self.lines.clear();
+        self.oscillators.seek(rate, sample)
+    }
+
+    pub fn process(&mut self, inputs: &[Sample], settings: Settings) -> Result<Option<Sample>> {
+        let Some(input) = inputs.first() else {
+            return Ok(None);
+        };
+        let modulation = inputs.get(1).and_then(|input| input.first().copied());
+        if self.lines.len() < input.len() {
+            let max_delay = self.max_delay;
+            self.lines
+                .resize_with(input.len(), || DelayLine::new(max_delay));
+        }
+        let oscillators =
+            self.oscillators
+                .process(input.len(), settings.rate, settings.stereo_phase)?;
+        let output = input
+            .iter()
+            .zip(&mut self.lines)
+            .zip(oscillators)
+            .map(|((&dry, line), &oscillator)| {
+                let modulation = modulation.unwrap_or(oscillator);
+                let delay = (settings.delay + settings.depth * modulation) * self.sample_rate;
+                let wet = line.read(delay, Interpolation::Cubic);
+                line.push(dry + settings.feedback * wet);
+                dry * (1.0 - settings.mix) + wet * settings.mix
+            })
+            .collect();
+        Ok(Some(output))
+    }
+}
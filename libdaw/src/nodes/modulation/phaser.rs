@@ -0,0 +1,144 @@
+use super::Oscillators;
+use crate::{sample::Sample, Context, Node, Result};
+use std::f64::consts::PI;
+
+/// The state of a single first-order all-pass stage.
+#[derive(Debug, Default, Clone, Copy)]
+struct Stage {
+    input: f64,
+    output: f64,
+}
+
+impl Stage {
+    fn process(&mut self, input: f64, coefficient: f64) -> f64 {
+        let output = coefficient * input + self.input - coefficient * self.output;
+        self.input = input;
+        self.output = output;
+        output
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct Channel {
+    stages: Vec<Stage>,
+    feedback: f64,
+}
+
+/// A phaser, mixing in the signal passed through a chain of all-pass filters
+/// whose break frequency sweeps between `min_frequency` and `max_frequency`.
+#[derive(Debug)]
+pub struct Phaser {
+    /// The oscillator rate, in Hz.
+    pub rate: f64,
+
+    /// The lowest all-pass break frequency, in Hz.
+    pub min_frequency: f64,
+
+    /// The highest all-pass break frequency, in Hz.
+    pub max_frequency: f64,
+
+    /// How much of the all-pass output is fed back, from -1 to 1.
+    pub feedback: f64,
+
+    /// The ratio of phased signal in the output, from 0 to 1.
+    pub mix: f64,
+
+    /// The oscillator phase offset between adjacent channels, from 0 to 1.
+    pub stereo_phase: f64,
+
+    sample_rate: f64,
+    stages: usize,
+    channels: Vec<Channel>,
+    oscillators: Oscillators,
+}
+
+impl Phaser {
+    /// Construct a phaser with the given number of all-pass stages.  Each
+    /// two stages make one notch.
+    pub fn new(sample_rate: u32, stages: usize) -> Self {
+        Self {
+            rate: 0.5,
+            min_frequency: 200.0,
+            max_frequency: 2000.0,
+            feedback: 0.5,
+            mix: 0.5,
+            stereo_phase: 0.25,
+            sample_rate: sample_rate as f64,
+            stages,
+            channels: Vec::new(),
+            oscillators: Oscillators::new(sample_rate),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    pub fn stages(&self) -> usize {
+        self.stages
+    }
+}
+
+impl Node for Phaser {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let Some(input) = inputs.first() else {
+            return Ok(());
+        };
+        let modulation = inputs.get(1).and_then(|input| input.first().copied());
+        if self.channels.len() < input.len() {
+            let stages = self.stages;
+            self.channels.resize_with(input.len(), || Channel {
+                stages: vec![Stage::default(); stages],
+                feedback: 0.0,
+            });
+        }
+
+        // Sweep logarithmically, so the sweep sounds even.
+        let min = self.min_frequency.max(1.0);
+        let max = self.max_frequency.max(min);
+        let nyquist = self.sample_rate / 2.0;
+        let oscillators = self
+            .oscillators
+            .process(input.len(), self.rate, self.stereo_phase)?;
+        let output = input
+            .iter()
+            .zip(&mut self.channels)
+            .zip(oscillators)
+            .map(|((&dry, channel), &oscillator)| {
+                let modulation = modulation.unwrap_or(oscillator);
+                let position = (modulation + 1.0) / 2.0;
+                let frequency = (min * (max / min).powf(position)).min(nyquist * 0.99);
+                let tan = (PI * frequency / self.sample_rate).tan();
+                let coefficient = (tan - 1.0) / (tan + 1.0);
+                let wet = channel
+                    .stages
+                    .iter_mut()
+                    .fold(dry + self.feedback * channel.feedback, |value, stage| {
+                        stage.process(value, coefficient)
+                    });
+                channel.feedback = wet;
+                dry * (1.0 - self.mix) + wet * self.mix
+            })
+            .collect();
+        outputs.push(output);
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.sample_rate = context.sample_rate as f64;
+        self.oscillators.prepare(context)
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.seek(0)
+    }
+
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.channels.clear();
+        self.oscillators.seek(self.rate, sample)
+    }
+}
@@ -1,8 +1,10 @@
+pub mod additive;
 pub mod sawtooth;
 pub mod sine;
 pub mod square;
 pub mod triangle;
 
+pub use additive::{Additive, Partial};
 pub use sawtooth::Sawtooth;
 pub use sine::Sine;
 pub use square::Square;
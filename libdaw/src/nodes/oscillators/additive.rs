@@ -0,0 +1,173 @@
+use crate::{nodes::Envelope, sample::Sample, Context, Node, Result};
+use std::f64;
+
+/// A single sine partial of an additive oscillator.
+#[derive(Debug, Clone)]
+pub struct Partial {
+    /// The frequency of this partial as a ratio of the fundamental.
+    pub ratio: f64,
+
+    /// The amplitude of this partial.
+    pub amplitude: f64,
+
+    /// The starting phase of this partial, from 0 to 1 per period.
+    pub phase: f64,
+
+    /// An optional volume envelope that applies only to this partial.
+    pub envelope: Option<Envelope>,
+}
+
+impl Partial {
+    pub fn new(ratio: f64, amplitude: f64) -> Self {
+        Self {
+            ratio,
+            amplitude,
+            phase: 0.0,
+            envelope: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PartialState {
+    partial: Partial,
+
+    /// Ramps from 0 to 1 per period
+    ramp: f64,
+}
+
+/// An additive oscillator bank, summing a set of sine partials over a
+/// fundamental frequency.  Partials that would land at or above the Nyquist
+/// frequency are skipped, as they would only alias.
+#[derive(Debug)]
+pub struct Additive {
+    /// The fundamental frequency if no input comes in.
+    pub frequency: f64,
+
+    sample_rate: f64,
+
+    /// The next sample to be processed, counting from the last reset or
+    /// seek.
+    position: u64,
+    partials: Vec<PartialState>,
+}
+
+impl Additive {
+    pub fn new(
+        sample_rate: u32,
+        frequency: f64,
+        partials: impl IntoIterator<Item = Partial>,
+    ) -> Self {
+        Self {
+            frequency,
+            sample_rate: sample_rate as f64,
+            position: 0,
+            partials: partials
+                .into_iter()
+                .map(|partial| PartialState {
+                    ramp: partial.phase.rem_euclid(1.0),
+                    partial,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    /// The next sample to be processed.
+    pub fn sample(&self) -> u64 {
+        self.position
+    }
+
+    pub fn partials(&self) -> impl Iterator<Item = &Partial> {
+        self.partials.iter().map(|state| &state.partial)
+    }
+
+    /// A set of harmonic partials, where partial `n` has a ratio of `n` and
+    /// an amplitude of `amplitude(n)`.
+    pub fn harmonics(
+        sample_rate: u32,
+        frequency: f64,
+        count: u32,
+        mut amplitude: impl FnMut(u32) -> f64,
+    ) -> Self {
+        Self::new(
+            sample_rate,
+            frequency,
+            (1..=count).map(|n| Partial::new(n as f64, amplitude(n))),
+        )
+    }
+}
+
+impl Node for Additive {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let frequency = inputs
+            .first()
+            .and_then(|input| input.first().copied())
+            .unwrap_or(self.frequency);
+        let nyquist = self.sample_rate / 2.0;
+        let mut sample = 0.0;
+        for state in &mut self.partials {
+            // Envelopes always advance, so a partial that drops out above
+            // Nyquist stays in time with the rest when it comes back.
+            let volume = state
+                .partial
+                .envelope
+                .as_mut()
+                .and_then(Envelope::next_volume)
+                .unwrap_or(1.0);
+            let partial_frequency = frequency * state.partial.ratio;
+            if partial_frequency.abs() >= nyquist {
+                continue;
+            }
+            sample += state.partial.amplitude * volume * (state.ramp * f64::consts::TAU).sin();
+            state.ramp = (state.ramp + partial_frequency / self.sample_rate).rem_euclid(1.0);
+        }
+        outputs.push(sample.into());
+        self.position += 1;
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.position =
+            (self.position as f64 * context.sample_rate as f64 / self.sample_rate) as u64;
+        self.sample_rate = context.sample_rate as f64;
+        for state in &mut self.partials {
+            if let Some(envelope) = &mut state.partial.envelope {
+                envelope.prepare(context)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.position = 0;
+        for state in &mut self.partials {
+            state.ramp = state.partial.phase.rem_euclid(1.0);
+            if let Some(envelope) = &mut state.partial.envelope {
+                envelope.reset()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Seeking assumes the frequency has stayed at `frequency`.
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.position = sample;
+        let seconds = sample as f64 / self.sample_rate;
+        for state in &mut self.partials {
+            let cycles = self.frequency * state.partial.ratio * seconds;
+            state.ramp = (state.partial.phase + cycles).rem_euclid(1.0);
+            if let Some(envelope) = &mut state.partial.envelope {
+                envelope.seek(sample)?;
+            }
+        }
+        Ok(())
+    }
+}
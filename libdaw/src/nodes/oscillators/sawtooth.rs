@@ -1,4 +1,4 @@
-use crate::{sample::Sample, Node, Result};
+use crate::{sample::Sample, Context, Node, Result};
 
 #[derive(Debug)]
 pub struct Sawtooth {
@@ -6,6 +6,10 @@ pub struct Sawtooth {
     pub frequency: f64,
 
     sample_rate: f64,
+
+    /// The next sample to be processed, counting from the last reset or
+    /// seek.
+    position: u64,
     sample: f64,
 }
 
@@ -15,8 +19,18 @@ impl Sawtooth {
             frequency,
             sample: Default::default(),
             sample_rate: sample_rate as f64,
+            position: 0,
         }
     }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    /// The next sample to be processed.
+    pub fn sample(&self) -> u64 {
+        self.position
+    }
 }
 
 impl Node for Sawtooth {
@@ -34,6 +48,28 @@ impl Node for Sawtooth {
         // 2.0 range.
         let delta = frequency * 2.0 / self.sample_rate;
         self.sample = (self.sample + delta + 1.0f64) % 2.0f64 - 1.0f64;
+        self.position += 1;
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.position =
+            (self.position as f64 * context.sample_rate as f64 / self.sample_rate) as u64;
+        self.sample_rate = context.sample_rate as f64;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.position = 0;
+        self.sample = 0.0;
+        Ok(())
+    }
+
+    /// Seeking assumes the frequency has stayed at `frequency`.
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.position = sample;
+        let offset = self.frequency * 2.0 * sample as f64 / self.sample_rate;
+        self.sample = (offset + 1.0).rem_euclid(2.0) - 1.0;
         Ok(())
     }
 }
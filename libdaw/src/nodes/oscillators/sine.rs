@@ -1,4 +1,4 @@
-use crate::{sample::Sample, Node, Result};
+use crate::{sample::Sample, Context, Node, Result};
 use std::f64;
 
 #[derive(Debug)]
@@ -7,6 +7,10 @@ pub struct Sine {
     pub frequency: f64,
 
     sample_rate: f64,
+
+    /// The next sample to be processed, counting from the last reset or
+    /// seek.
+    position: u64,
     /// Ramps from 0 to TAU per period
     ramp: f64,
 }
@@ -17,8 +21,18 @@ impl Sine {
             frequency,
             ramp: Default::default(),
             sample_rate: sample_rate as f64,
+            position: 0,
         }
     }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    /// The next sample to be processed.
+    pub fn sample(&self) -> u64 {
+        self.position
+    }
 }
 
 impl Node for Sine {
@@ -34,6 +48,27 @@ impl Node for Sine {
         let delta = frequency / self.sample_rate;
         outputs.push((self.ramp * f64::consts::TAU).sin().into());
         self.ramp = (self.ramp + delta) % 1.0;
+        self.position += 1;
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.position =
+            (self.position as f64 * context.sample_rate as f64 / self.sample_rate) as u64;
+        self.sample_rate = context.sample_rate as f64;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.position = 0;
+        self.ramp = 0.0;
+        Ok(())
+    }
+
+    /// Seeking assumes the frequency has stayed at `frequency`.
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.position = sample;
+        self.ramp = (self.frequency * sample as f64 / self.sample_rate).rem_euclid(1.0);
         Ok(())
     }
 }
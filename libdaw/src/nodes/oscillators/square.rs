@@ -1,5 +1,5 @@
 use crate::sample::Sample;
-use crate::{Node, Result};
+use crate::{Context, Node, Result};
 
 #[derive(Debug)]
 pub struct Square {
@@ -9,6 +9,10 @@ pub struct Square {
     samples_since_switch: f64,
     sample_rate: f64,
     sample: f64,
+
+    /// The next sample to be processed, counting from the last reset or
+    /// seek.
+    position: u64,
 }
 
 impl Square {
@@ -18,8 +22,18 @@ impl Square {
             samples_since_switch: Default::default(),
             sample: 1.0,
             sample_rate: sample_rate as f64,
+            position: 0,
         }
     }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    /// The next sample to be processed.
+    pub fn sample(&self) -> u64 {
+        self.position
+    }
 }
 
 impl Node for Square {
@@ -42,6 +56,38 @@ impl Node for Square {
             self.sample = -self.sample;
         }
         self.samples_since_switch += 1.0;
+        self.position += 1;
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.position =
+            (self.position as f64 * context.sample_rate as f64 / self.sample_rate) as u64;
+        self.sample_rate = context.sample_rate as f64;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.position = 0;
+        self.samples_since_switch = 0.0;
+        self.sample = 1.0;
+        Ok(())
+    }
+
+    /// Seeking assumes the frequency has stayed at `frequency`.
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.position = sample;
+        let samples_per_switch = self.sample_rate / (self.frequency * 2.0);
+        let sample = sample as f64;
+        let switches = (sample / samples_per_switch).floor();
+        if switches.is_finite() {
+            self.samples_since_switch = sample - switches * samples_per_switch;
+            self.sample = if switches % 2.0 == 0.0 { 1.0 } else { -1.0 };
+        } else {
+            // A frequency of zero never switches.
+            self.samples_since_switch = sample;
+            self.sample = 1.0;
+        }
         Ok(())
     }
 }
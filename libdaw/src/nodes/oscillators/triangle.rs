@@ -1,5 +1,5 @@
 use crate::sample::Sample;
-use crate::{Node, Result};
+use crate::{Context, Node, Result};
 
 #[derive(Debug)]
 pub struct Triangle {
@@ -7,6 +7,10 @@ pub struct Triangle {
     pub frequency: f64,
 
     sample_rate: f64,
+
+    /// The next sample to be processed, counting from the last reset or
+    /// seek.
+    position: u64,
     /// Ramps from 0 to 1 per period
     ramp: f64,
 }
@@ -17,8 +21,18 @@ impl Triangle {
             frequency,
             ramp: Default::default(),
             sample_rate: sample_rate as f64,
+            position: 0,
         }
     }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+
+    /// The next sample to be processed.
+    pub fn sample(&self) -> u64 {
+        self.position
+    }
 }
 
 impl Node for Triangle {
@@ -39,6 +53,27 @@ impl Node for Triangle {
         //   \/
         let sample = (((ramp - 0.25).abs() - 0.5).abs() - 0.25) * 4.0;
         outputs.push(sample.into());
+        self.position += 1;
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.position =
+            (self.position as f64 * context.sample_rate as f64 / self.sample_rate) as u64;
+        self.sample_rate = context.sample_rate as f64;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.position = 0;
+        self.ramp = 0.0;
+        Ok(())
+    }
+
+    /// Seeking assumes the frequency has stayed at `frequency`.
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.position = sample;
+        self.ramp = (self.frequency * sample as f64 / self.sample_rate).rem_euclid(1.0);
         Ok(())
     }
 }
@@ -0,0 +1,279 @@
+//! Polyphase up- and downsampling by an integer factor, with anti-aliasing
+//! low pass filters, for running nonlinear processing at a higher rate.
+
+use crate::{sample::Sample, Context, Node, Result};
+use std::{
+    collections::VecDeque,
+    f64::consts::PI,
+    sync::{Arc, Mutex},
+};
+
+/// Filter taps per phase of the polyphase filters.
+const TAPS_PER_PHASE: usize = 16;
+
+/// The filter cutoff, as a fraction of the original Nyquist frequency.
+const CUTOFF: f64 = 0.9;
+
+/// A Blackman-windowed sinc low pass filter for the given factor, normalized
+/// to unity gain.
+fn anti_aliasing(factor: usize) -> Arc<[f64]> {
+    if factor <= 1 {
+        return [1.0].into();
+    }
+    let length = factor * TAPS_PER_PHASE;
+    let center = (length - 1) as f64 / 2.0;
+    let cutoff = CUTOFF / (2.0 * factor as f64);
+    let mut taps: Vec<f64> = (0..length)
+        .map(|i| {
+            let x = i as f64 - center;
+            let sinc = if x == 0.0 {
+                2.0 * cutoff
+            } else {
+                (2.0 * PI * cutoff * x).sin() / (PI * x)
+            };
+            let phase = 2.0 * PI * i as f64 / (length - 1) as f64;
+            let window = 0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos();
+            sinc * window
+        })
+        .collect();
+    let sum: f64 = taps.iter().sum();
+    for tap in &mut taps {
+        *tap /= sum;
+    }
+    taps.into()
+}
+
+/// Add `input * gain` into `output`, growing it as needed.
+fn accumulate(output: &mut Sample, input: &Sample, gain: f64) {
+    if output.len() < input.len() {
+        output.channels.resize(input.len(), 0.0);
+    }
+    for (output, input) in output.iter_mut().zip(input) {
+        *output += input * gain;
+    }
+}
+
+/// Raises the sample rate by an integer factor.
+#[derive(Debug)]
+pub struct Upsampler {
+    factor: usize,
+    taps: Arc<[f64]>,
+
+    /// Recent input samples, most recent first.
+    history: VecDeque<Sample>,
+}
+
+impl Upsampler {
+    pub fn new(factor: usize) -> Self {
+        let factor = factor.max(1);
+        let taps = anti_aliasing(factor);
+        let length = taps.len() / factor;
+        Self {
+            factor,
+            taps,
+            history: std::iter::repeat_with(Sample::default)
+                .take(length)
+                .collect(),
+        }
+    }
+
+    pub fn factor(&self) -> usize {
+        self.factor
+    }
+
+    /// Push `factor` output samples for one input sample.
+    pub fn process(&mut self, input: &Sample, outputs: &mut Vec<Sample>) {
+        if self.factor == 1 {
+            outputs.push(input.clone());
+            return;
+        }
+        self.history.pop_back();
+        self.history.push_front(input.clone());
+        for phase in 0..self.factor {
+            let mut output = Sample::zeroed(input.len());
+            for (sample, &tap) in self
+                .history
+                .iter()
+                .zip(self.taps.iter().skip(phase).step_by(self.factor))
+            {
+                accumulate(&mut output, sample, tap * self.factor as f64);
+            }
+            outputs.push(output);
+        }
+    }
+
+    /// Forget all previous input.
+    pub fn clear(&mut self) {
+        self.history
+            .iter_mut()
+            .for_each(|sample| sample.channels.clear());
+    }
+}
+
+/// Lowers the sample rate by an integer factor.
+#[derive(Debug)]
+pub struct Downsampler {
+    factor: usize,
+    taps: Arc<[f64]>,
+
+    /// Recent input samples, most recent first.
+    history: VecDeque<Sample>,
+}
+
+impl Downsampler {
+    pub fn new(factor: usize) -> Self {
+        let factor = factor.max(1);
+        let taps = anti_aliasing(factor);
+        Self {
+            factor,
+            history: std::iter::repeat_with(Sample::default)
+                .take(taps.len())
+                .collect(),
+            taps,
+        }
+    }
+
+    pub fn factor(&self) -> usize {
+        self.factor
+    }
+
+    /// Produce one output sample from `factor` input samples.
+    pub fn process(&mut self, inputs: &[Sample]) -> Sample {
+        for input in inputs {
+            self.history.pop_back();
+            self.history.push_front(input.clone());
+        }
+        let mut output = Sample::default();
+        for (sample, &tap) in self.history.iter().zip(self.taps.iter()) {
+            accumulate(&mut output, sample, tap);
+        }
+        output
+    }
+
+    /// Forget all previous input.
+    pub fn clear(&mut self) {
+        self.history
+            .iter_mut()
+            .for_each(|sample| sample.channels.clear());
+    }
+}
+
+/// Runs a node at an integer multiple of the outer sample rate.
+///
+/// Each input stream is upsampled, the node is processed `factor` times per
+/// outer sample, and each of its output streams is filtered and downsampled
+/// again.  The node, often a [Graph](crate::nodes::Graph), must be
+/// constructed with the oversampled rate, or prepared through this node.
+#[derive(Debug)]
+pub struct Oversample {
+    node: Arc<Mutex<dyn Node>>,
+    factor: usize,
+    upsamplers: Vec<Upsampler>,
+    downsamplers: Vec<Downsampler>,
+
+    /// Upsampled input, one buffer per input stream.
+    upsampled: Vec<Vec<Sample>>,
+
+    /// Inner output, one buffer per output stream.
+    processed: Vec<Vec<Sample>>,
+    inputs: Vec<Sample>,
+    outputs: Vec<Sample>,
+}
+
+impl Oversample {
+    pub fn new(factor: usize, node: Arc<Mutex<dyn Node>>) -> Self {
+        Self {
+            node,
+            factor: factor.max(1),
+            upsamplers: Vec::new(),
+            downsamplers: Vec::new(),
+            upsampled: Vec::new(),
+            processed: Vec::new(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    pub fn factor(&self) -> usize {
+        self.factor
+    }
+
+    pub fn node(&self) -> Arc<Mutex<dyn Node>> {
+        self.node.clone()
+    }
+}
+
+impl Node for Oversample {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let factor = self.factor;
+        if self.upsamplers.len() < inputs.len() {
+            self.upsamplers
+                .resize_with(inputs.len(), || Upsampler::new(factor));
+            self.upsampled.resize_with(inputs.len(), Vec::new);
+        }
+        for ((input, upsampler), upsampled) in inputs
+            .iter()
+            .zip(&mut self.upsamplers)
+            .zip(&mut self.upsampled)
+        {
+            upsampled.clear();
+            upsampler.process(input, upsampled);
+        }
+        self.processed.iter_mut().for_each(Vec::clear);
+        let mut node = self.node.lock().expect("poisoned");
+        for index in 0..factor {
+            self.inputs.clear();
+            self.inputs.extend(
+                self.upsampled[..inputs.len()]
+                    .iter()
+                    .map(|upsampled| upsampled[index].clone()),
+            );
+            self.outputs.clear();
+            node.process(&self.inputs, &mut self.outputs)?;
+            if self.processed.len() < self.outputs.len() {
+                self.processed.resize_with(self.outputs.len(), Vec::new);
+            }
+            for (processed, output) in self.processed.iter_mut().zip(self.outputs.drain(..)) {
+                processed.push(output);
+            }
+        }
+        if self.downsamplers.len() < self.processed.len() {
+            self.downsamplers
+                .resize_with(self.processed.len(), || Downsampler::new(factor));
+        }
+        for (processed, downsampler) in self.processed.iter().zip(&mut self.downsamplers) {
+            if !processed.is_empty() {
+                outputs.push(downsampler.process(processed));
+            }
+        }
+        Ok(())
+    }
+
+    /// The node is prepared at the oversampled rate.
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.node.lock().expect("poisoned").prepare(&Context {
+            sample_rate: context.sample_rate * self.factor as u32,
+            max_block_size: context.max_block_size * self.factor,
+            ..*context
+        })
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.upsamplers.clear();
+        self.downsamplers.clear();
+        self.node.lock().expect("poisoned").reset()
+    }
+
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.upsamplers.clear();
+        self.downsamplers.clear();
+        self.node
+            .lock()
+            .expect("poisoned")
+            .seek(sample * self.factor as u64)
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{sample::Sample, Context, Node, Result};
+use std::{
+    collections::VecDeque,
+    f64::consts::PI,
+    sync::{Arc, Mutex},
+};
+
+/// Zero crossings of the sinc kernel on each side of its center.
+const ZERO_CROSSINGS: usize = 16;
+
+/// Kernel table entries per zero crossing.
+const RESOLUTION: usize = 512;
+
+/// The cutoff as a fraction of the lower Nyquist frequency, leaving room for
+/// the transition band.
+const CUTOFF: f64 = 0.95;
+
+/// A Blackman-windowed sinc from the center out to the last zero crossing.
+fn kernel_table() -> Arc<[f64]> {
+    (0..=ZERO_CROSSINGS * RESOLUTION + 1)
+        .map(|index| {
+            let x = index as f64 / RESOLUTION as f64;
+            if x >= ZERO_CROSSINGS as f64 {
+                return 0.0;
+            }
+            let sinc = if index == 0 {
+                1.0
+            } else {
+                (PI * x).sin() / (PI * x)
+            };
+            let phase = PI * x / ZERO_CROSSINGS as f64;
+            let window = 0.42 + 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos();
+            sinc * window
+        })
+        .collect()
+}
+
+/// Runs a node at a different sample rate, converting its output streams
+/// with band-limited windowed-sinc interpolation.
+///
+/// The node is processed as often as its own rate requires, running slightly
+/// ahead of the output to see the future samples the interpolation needs.
+/// The resampler's input streams are passed to it unchanged each time it is
+/// processed.
+#[derive(Debug)]
+pub struct Resampler {
+    node: Arc<Mutex<dyn Node>>,
+    node_sample_rate: u32,
+
+    /// Node samples per output sample.
+    step: f64,
+
+    /// The kernel scale, below 1 when the node runs faster than the output
+    /// and the kernel must also filter out its upper frequencies.
+    scale: f64,
+
+    /// Node samples used on each side of the interpolated position.
+    half_width: usize,
+
+    table: Arc<[f64]>,
+
+    /// The position of the next output sample in node samples.
+    position: f64,
+
+    /// The index of the first sample in the history.
+    start: i64,
+
+    /// The index after the last sample the node produced.
+    end: i64,
+
+    /// Recent node output, one queue per output stream.
+    history: Vec<VecDeque<Sample>>,
+    buffer: Vec<Sample>,
+}
+
+impl Resampler {
+    /// Resample the output of a node running at `node_sample_rate` to
+    /// `sample_rate`.
+    pub fn new(sample_rate: u32, node_sample_rate: u32, node: Arc<Mutex<dyn Node>>) -> Self {
+        let step = node_sample_rate as f64 / sample_rate as f64;
+        let scale = CUTOFF * step.recip().min(1.0);
+        Self {
+            node,
+            node_sample_rate,
+            step,
+            scale,
+            half_width: (ZERO_CROSSINGS as f64 / scale).ceil() as usize,
+            table: kernel_table(),
+            position: 0.0,
+            start: 0,
+            end: 0,
+            history: Vec::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn node(&self) -> Arc<Mutex<dyn Node>> {
+        self.node.clone()
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        (self.node_sample_rate as f64 / self.step).round() as u32
+    }
+
+    pub fn node_sample_rate(&self) -> u32 {
+        self.node_sample_rate
+    }
+
+    /// The kernel value at a distance in node samples.
+    fn kernel(&self, distance: f64) -> f64 {
+        let index = distance.abs() * self.scale * RESOLUTION as f64;
+        let whole = index as usize;
+        if whole >= ZERO_CROSSINGS * RESOLUTION {
+            return 0.0;
+        }
+        let fraction = index - whole as f64;
+        let value = self.table[whole] + (self.table[whole + 1] - self.table[whole]) * fraction;
+        value * self.scale
+    }
+}
+
+impl Node for Resampler {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let center = self.position.floor() as i64;
+        let first = center - self.half_width as i64 + 1;
+        let last = center + self.half_width as i64;
+        // Run the node until it has produced the last needed sample.
+        let mut node = self.node.lock().expect("poisoned");
+        for _ in self.end..=last {
+            self.buffer.clear();
+            node.process(inputs, &mut self.buffer)?;
+            if self.history.len() < self.buffer.len() {
+                let len = (self.end - self.start) as usize;
+                self.history.resize_with(self.buffer.len(), || {
+                    std::iter::repeat_with(Sample::default).take(len).collect()
+                });
+            }
+            let mut buffer = self.buffer.drain(..);
+            for history in &mut self.history {
+                history.push_back(buffer.next().unwrap_or_default());
+            }
+            self.end += 1;
+        }
+        drop(node);
+
+        // Forget samples that are no longer needed.
+        while self.start < first.min(self.end) {
+            for history in &mut self.history {
+                history.pop_front();
+            }
+            self.start += 1;
+        }
+
+        for history in &self.history {
+            let mut output = Sample::default();
+            for (offset, sample) in history.iter().enumerate() {
+                let gain = self.kernel(self.position - (self.start + offset as i64) as f64);
+                if output.len() < sample.len() {
+                    output.channels.resize(sample.len(), 0.0);
+                }
+                for (output, input) in output.iter_mut().zip(sample) {
+                    *output += input * gain;
+                }
+            }
+            outputs.push(output);
+        }
+        self.position += self.step;
+        Ok(())
+    }
+
+    /// The node is prepared at its own sample rate, which does not change.
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.step = self.node_sample_rate as f64 / context.sample_rate as f64;
+        self.scale = CUTOFF * self.step.recip().min(1.0);
+        self.half_width = (ZERO_CROSSINGS as f64 / self.scale).ceil() as usize;
+        self.node.lock().expect("poisoned").prepare(&Context {
+            sample_rate: self.node_sample_rate,
+            max_block_size: (context.max_block_size as f64 * self.step).ceil() as usize,
+            ..*context
+        })
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.position = 0.0;
+        self.start = 0;
+        self.end = 0;
+        self.history.clear();
+        self.node.lock().expect("poisoned").reset()
+    }
+
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.position = sample as f64 * self.step;
+        // Start the node at the first sample the interpolation will need.
+        let first = (self.position.floor() as i64 - self.half_width as i64 + 1).max(0);
+        self.start = first;
+        self.end = first;
+        self.history.clear();
+        self.node.lock().expect("poisoned").seek(first as u64)
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{
+    nodes::delay_line::{DelayLine, Interpolation},
+    sample::Sample,
+    time::Duration,
+    Context, Node, Result,
+};
+
+/// Comb filter lengths, in samples at 44100 Hz.
+const COMB_TUNINGS: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+
+/// All-pass filter lengths, in samples at 44100 Hz.
+const ALLPASS_TUNINGS: [usize; 4] = [556, 441, 341, 225];
+
+/// Extra length of the right channel filters, in samples at 44100 Hz.
+const STEREO_SPREAD: usize = 23;
+
+const FIXED_GAIN: f64 = 0.015;
+const SCALE_DAMPING: f64 = 0.4;
+const SCALE_ROOM: f64 = 0.28;
+const OFFSET_ROOM: f64 = 0.7;
+const ALLPASS_FEEDBACK: f64 = 0.5;
+
+/// The longest allowed pre-delay.
+const MAX_PRE_DELAY: f64 = 1.0;
+
+#[derive(Debug)]
+struct Comb {
+    buffer: Box<[f64]>,
+    index: usize,
+    filter_store: f64,
+}
+
+impl Comb {
+    fn new(length: usize) -> Self {
+        Self {
+            buffer: vec![0.0; length.max(1)].into(),
+            index: 0,
+            filter_store: 0.0,
+        }
+    }
+
+    fn process(&mut self, input: f64, feedback: f64, damping: f64) -> f64 {
+        let output = self.buffer[self.index];
+        self.filter_store = output * (1.0 - damping) + self.filter_store * damping;
+        self.buffer[self.index] = input + self.filter_store * feedback;
+        self.index = (self.index + 1) % self.buffer.len();
+        output
+    }
+}
+
+#[derive(Debug)]
+struct AllPass {
+    buffer: Box<[f64]>,
+    index: usize,
+}
+
+impl AllPass {
+    fn new(length: usize) -> Self {
+        Self {
+            buffer: vec![0.0; length.max(1)].into(),
+            index: 0,
+        }
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        let buffered = self.buffer[self.index];
+        self.buffer[self.index] = input + buffered * ALLPASS_FEEDBACK;
+        self.index = (self.index + 1) % self.buffer.len();
+        buffered - input
+    }
+}
+
+/// One side of the stereo reverb tank.
+#[derive(Debug)]
+struct Tank {
+    combs: Vec<Comb>,
+    allpasses: Vec<AllPass>,
+}
+
+impl Tank {
+    fn new(scale: f64, spread: usize) -> Self {
+        let length = |tuning: usize| ((tuning + spread) as f64 * scale).round() as usize;
+        Self {
+            combs: COMB_TUNINGS
+                .into_iter()
+                .map(|tuning| Comb::new(length(tuning)))
+                .collect(),
+            allpasses: ALLPASS_TUNINGS
+                .into_iter()
+                .map(|tuning| AllPass::new(length(tuning)))
+                .collect(),
+        }
+    }
+
+    fn process(&mut self, input: f64, feedback: f64, damping: f64) -> f64 {
+        let combed: f64 = self
+            .combs
+            .iter_mut()
+            .map(|comb| comb.process(input, feedback, damping))
+            .sum();
+        self.allpasses
+            .iter_mut()
+            .fold(combed, |value, allpass| allpass.process(value))
+    }
+}
+
+/// A Schroeder-Moorer style algorithmic reverb, after Freeverb.
+///
+/// Takes a single input stream.  All of its channels are summed into the
+/// reverb, and its output is always two channels.  A mono input is sent
+/// dry to both output channels.
+#[derive(Debug)]
+pub struct Reverb {
+    /// The size of the room, from 0 to 1.
+    pub room_size: f64,
+
+    /// How quickly high frequencies die away, from 0 to 1.
+    pub damping: f64,
+
+    /// Time before the reverb starts, up to one second.
+    pub pre_delay: Duration,
+
+    /// The stereo width of the reverb, from 0 (mono) to 1.
+    pub width: f64,
+
+    /// The level of the reverberated signal.
+    pub wet: f64,
+
+    /// The level of the original signal.
+    pub dry: f64,
+
+    sample_rate: f64,
+    pre_delay_line: DelayLine,
+    left: Tank,
+    right: Tank,
+}
+
+impl Reverb {
+    pub fn new(sample_rate: u32) -> Self {
+        let scale = sample_rate as f64 / 44100.0;
+        Self {
+            room_size: 0.5,
+            damping: 0.5,
+            pre_delay: Duration::ZERO,
+            width: 1.0,
+            wet: 1.0 / 3.0,
+            dry: 1.0,
+            sample_rate: sample_rate as f64,
+            pre_delay_line: DelayLine::new((MAX_PRE_DELAY * sample_rate as f64).ceil() as usize),
+            left: Tank::new(scale, 0),
+            right: Tank::new(scale, STEREO_SPREAD),
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate as u32
+    }
+}
+
+impl Reverb {
+    /// Recreate the delay lines at the given sample rate, keeping the
+    /// settings.
+    fn rebuild(&mut self, sample_rate: u32) {
+        *self = Self {
+            room_size: self.room_size,
+            damping: self.damping,
+            pre_delay: self.pre_delay,
+            width: self.width,
+            wet: self.wet,
+            dry: self.dry,
+            ..Self::new(sample_rate)
+        };
+    }
+}
+
+impl Node for Reverb {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let Some(input) = inputs.first() else {
+            return Ok(());
+        };
+        let (dry_left, dry_right) = match input.len() {
+            0 => (0.0, 0.0),
+            1 => (input[0], input[0]),
+            _ => (input[0], input[1]),
+        };
+
+        let mono = input.iter().sum::<f64>() * FIXED_GAIN;
+        let pre_delay = self.pre_delay.seconds() * self.sample_rate;
+        let mono = if pre_delay >= 1.0 {
+            let delayed = self.pre_delay_line.read(pre_delay, Interpolation::Linear);
+            self.pre_delay_line.push(mono);
+            delayed
+        } else {
+            mono
+        };
+
+        let feedback = self.room_size.clamp(0.0, 1.0) * SCALE_ROOM + OFFSET_ROOM;
+        let damping = self.damping.clamp(0.0, 1.0) * SCALE_DAMPING;
+        let left = self.left.process(mono, feedback, damping);
+        let right = self.right.process(mono, feedback, damping);
+
+        let width = self.width.clamp(0.0, 1.0);
+        let wet1 = self.wet * (width / 2.0 + 0.5);
+        let wet2 = self.wet * ((1.0 - width) / 2.0);
+        outputs.push(Sample::from([
+            dry_left * self.dry + left * wet1 + right * wet2,
+            dry_right * self.dry + right * wet1 + left * wet2,
+        ]));
+        Ok(())
+    }
+
+    /// Changing the sample rate clears the reverb tail.
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        if context.sample_rate as f64 != self.sample_rate {
+            self.rebuild(context.sample_rate);
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.rebuild(self.sample_rate as u32);
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    output::{Backend as _, Format, WavFile},
+    sample::Sample,
+    Context, Node, Result,
+};
+use std::path::PathBuf;
+
+/// Where a tap records to.
+#[derive(Debug)]
+enum Destination {
+    Memory,
+
+    /// A WAV file that is created once the tap is prepared, so that it
+    /// takes the sample rate of the context.
+    Pending {
+        path: PathBuf,
+        channels: u16,
+    },
+
+    File(WavFile),
+}
+
+/// Passes its input through unchanged, while recording it into memory or to
+/// a WAV file whenever it is armed.
+///
+/// The recording is the sum of all input streams, one sample per frame.
+/// Recording into memory grows a buffer, so it allocates unless it was
+/// created with enough capacity.  Once the tap is finished, it records
+/// nothing more.
+#[derive(Debug)]
+pub struct Tap {
+    /// Whether incoming audio is being recorded.
+    pub armed: bool,
+
+    finished: bool,
+    captured: Vec<Sample>,
+    destination: Destination,
+}
+
+impl Default for Tap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tap {
+    /// Record into memory.  The tap starts armed.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Record into memory, with room for `frames` frames before allocating.
+    pub fn with_capacity(frames: usize) -> Self {
+        Self {
+            armed: true,
+            finished: false,
+            captured: Vec::with_capacity(frames),
+            destination: Destination::Memory,
+        }
+    }
+
+    /// Record into memory, following on from audio that was already
+    /// captured.  The tap starts armed.
+    pub fn resume(captured: Vec<Sample>) -> Self {
+        Self {
+            armed: true,
+            finished: false,
+            captured,
+            destination: Destination::Memory,
+        }
+    }
+
+    /// Stream the recording to a 32-bit float WAV file, which is complete
+    /// once the tap is finished or dropped.  The file is created when the
+    /// tap is first prepared, at the context's sample rate, and the tap has
+    /// to be prepared before it records.  The tap starts armed.
+    pub fn to_wav(path: impl Into<PathBuf>, channels: u16) -> Self {
+        Self {
+            armed: true,
+            finished: false,
+            captured: Vec::new(),
+            destination: Destination::Pending {
+                path: path.into(),
+                channels,
+            },
+        }
+    }
+
+    /// Arm the tap, which fails once it is finished.
+    pub fn arm(&mut self) -> Result<()> {
+        if self.finished {
+            return Err("can not arm a finished tap".into());
+        }
+        self.armed = true;
+        Ok(())
+    }
+
+    pub fn disarm(&mut self) {
+        self.armed = false;
+    }
+
+    /// Whether the tap records to a WAV file rather than into memory.
+    pub fn is_to_wav(&self) -> bool {
+        !matches!(self.destination, Destination::Memory)
+    }
+
+    /// Whether the tap has been finished, after which it records nothing.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// The audio recorded into memory so far.  This is always empty when
+    /// recording to a file.
+    pub fn captured(&self) -> &[Sample] {
+        &self.captured
+    }
+
+    /// Take the audio recorded into memory, leaving the tap empty.
+    pub fn take(&mut self) -> Vec<Sample> {
+        std::mem::take(&mut self.captured)
+    }
+
+    /// Disarm the tap and complete its WAV file, if it has one.  Nothing
+    /// more can be recorded afterward, and the tap can not be armed again.
+    pub fn finish(&mut self) -> Result<()> {
+        self.armed = false;
+        self.finished = true;
+        if let Destination::File(file) = &mut self.destination {
+            file.finish()?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Tap {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+impl Node for Tap {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        outputs.extend_from_slice(inputs);
+        if self.armed && !self.finished {
+            let frame: Sample = inputs.iter().sum();
+            match &mut self.destination {
+                Destination::Memory => self.captured.push(frame),
+                Destination::Pending { .. } => {
+                    return Err("a tap must be prepared before it records to a file".into())
+                }
+                Destination::File(file) => file.write(std::slice::from_ref(&frame))?,
+            }
+        }
+        Ok(())
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        match &self.destination {
+            Destination::Pending { path, channels } if !self.finished => {
+                let format = Format {
+                    sample_rate: context.sample_rate,
+                    channels: *channels,
+                };
+                self.destination = Destination::File(WavFile::create(path, format)?);
+            }
+            Destination::File(file) if file.format().sample_rate != context.sample_rate => {
+                return Err(format!(
+                    "can not change the sample rate of a tap's WAV file from {} to {}",
+                    file.format().sample_rate,
+                    context.sample_rate
+                )
+                .into());
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_only_while_armed() {
+        let mut tap = Tap::new();
+        let mut outputs = Vec::new();
+        for value in [1.0, 2.0, 3.0] {
+            if value == 2.0 {
+                tap.disarm();
+            } else {
+                tap.arm().unwrap();
+            }
+            tap.process(&[Sample::from(value)], &mut outputs).unwrap();
+        }
+        let passed: Vec<f64> = outputs.iter().map(|sample| sample[0]).collect();
+        let captured: Vec<f64> = tap.captured().iter().map(|sample| sample[0]).collect();
+        assert_eq!(passed, [1.0, 2.0, 3.0]);
+        assert_eq!(captured, [1.0, 3.0]);
+    }
+
+    #[test]
+    fn records_nothing_once_finished() {
+        let mut tap = Tap::new();
+        let mut outputs = Vec::new();
+        tap.process(&[Sample::from(1.0)], &mut outputs).unwrap();
+        tap.finish().unwrap();
+        assert!(tap.arm().is_err());
+        tap.armed = true;
+        tap.process(&[Sample::from(2.0)], &mut outputs).unwrap();
+        assert_eq!(tap.captured().len(), 1);
+        assert_eq!(outputs.len(), 2);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    nodes::oversampling::{Downsampler, Upsampler},
+    sample::Sample,
+    Node, Result,
+};
+use std::{fmt, sync::Arc};
+
+/// A transfer function mapping input values to output values.
+#[derive(Clone)]
+pub enum Curve {
+    /// Smooth saturation.
+    Tanh,
+
+    /// Clamps to the range -1 to 1.
+    HardClip,
+
+    /// Reflects anything beyond -1 or 1 back into range.
+    Foldback,
+
+    /// A polynomial with the given coefficients, starting with the constant
+    /// term.
+    Polynomial(Vec<f64>),
+
+    /// A sum of Chebyshev polynomials of the first kind, with the given
+    /// amplitudes starting at the first order.  A full scale sine wave input
+    /// produces exactly these harmonic amplitudes.  Input is clamped to the
+    /// range -1 to 1.
+    Chebyshev(Vec<f64>),
+
+    /// A user-supplied transfer function.
+    Function(Arc<dyn Fn(f64) -> Result<f64> + Send + Sync>),
+}
+
+impl fmt::Debug for Curve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tanh => write!(f, "Tanh"),
+            Self::HardClip => write!(f, "HardClip"),
+            Self::Foldback => write!(f, "Foldback"),
+            Self::Polynomial(coefficients) => {
+                f.debug_tuple("Polynomial").field(coefficients).finish()
+            }
+            Self::Chebyshev(amplitudes) => f.debug_tuple("Chebyshev").field(amplitudes).finish(),
+            Self::Function(_) => write!(f, "Function(..)"),
+        }
+    }
+}
+
+impl Curve {
+    pub fn apply(&self, input: f64) -> Result<f64> {
+        Ok(match self {
+            Self::Tanh => input.tanh(),
+            Self::HardClip => input.clamp(-1.0, 1.0),
+            Self::Foldback => 1.0 - ((input + 1.0).rem_euclid(4.0) - 2.0).abs(),
+            Self::Polynomial(coefficients) => coefficients
+                .iter()
+                .rev()
+                .fold(0.0, |output, coefficient| output * input + coefficient),
+            Self::Chebyshev(amplitudes) => {
+                let input = input.clamp(-1.0, 1.0);
+                let mut previous = 1.0;
+                let mut current = input;
+                let mut output = 0.0;
+                for amplitude in amplitudes {
+                    output += amplitude * current;
+                    (previous, current) = (current, 2.0 * input * current - previous);
+                }
+                output
+            }
+            Self::Function(function) => function(input)?,
+        })
+    }
+}
+
+/// Distorts each input stream through a transfer function, optionally
+/// oversampled to reduce aliasing.
+#[derive(Debug)]
+pub struct Waveshaper {
+    pub curve: Curve,
+
+    /// Gain applied before the curve.
+    pub drive: f64,
+
+    oversampling: usize,
+    resamplers: Vec<(Upsampler, Downsampler)>,
+    buffer: Vec<Sample>,
+}
+
+impl Waveshaper {
+    /// Create a waveshaper running the curve at `oversampling` times the
+    /// sample rate.  An oversampling of 1 runs it directly.
+    pub fn new(curve: Curve, oversampling: usize) -> Self {
+        let oversampling = oversampling.max(1);
+        Self {
+            curve,
+            drive: 1.0,
+            oversampling,
+            resamplers: Vec::new(),
+            buffer: Vec::with_capacity(oversampling),
+        }
+    }
+
+    pub fn oversampling(&self) -> usize {
+        self.oversampling
+    }
+}
+
+impl Node for Waveshaper {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        let oversampling = self.oversampling;
+        if self.resamplers.len() < inputs.len() {
+            self.resamplers.resize_with(inputs.len(), || {
+                (Upsampler::new(oversampling), Downsampler::new(oversampling))
+            });
+        }
+        for (input, (upsampler, downsampler)) in inputs.iter().zip(&mut self.resamplers) {
+            self.buffer.clear();
+            upsampler.process(input, &mut self.buffer);
+            for sample in &mut self.buffer {
+                for channel in sample.iter_mut() {
+                    *channel = self.curve.apply(*channel * self.drive)?;
+                }
+            }
+            outputs.push(downsampler.process(&self.buffer));
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.resamplers.clear();
+        Ok(())
+    }
+}
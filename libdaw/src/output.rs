@@ -0,0 +1,291 @@
+//! Playing nodes through an output backend, such as the system's speakers,
+//! a file, or an in-memory fake device for testing.
+
+pub mod memory;
+pub mod null;
+pub mod pipe;
+#[cfg(feature = "rodio")]
+pub mod rodio;
+pub mod tee;
+pub mod wav_file;
+
+pub use memory::Memory;
+pub use null::Null;
+pub use pipe::Pipe;
+#[cfg(feature = "rodio")]
+pub use rodio::Rodio;
+pub use tee::Tee;
+pub use wav_file::WavFile;
+
+use crate::{input, nodes::Graph, sample::Sample, Context, Node, Result};
+use std::sync::{Arc, Mutex};
+
+/// The number of frames rendered before each write to the backend.
+pub const BLOCK_SIZE: usize = 512;
+
+/// The audio format a backend plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Format {
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// Somewhere to send rendered audio.
+pub trait Backend {
+    /// The format the backend plays at.
+    fn format(&self) -> Format;
+
+    /// Play a block of frames, one sample per frame.  Samples with fewer
+    /// channels than the format are padded with silence, and extra channels
+    /// are dropped.  Real-time backends block while the device catches up.
+    fn write(&mut self, frames: &[Sample]) -> Result<()>;
+
+    /// Flush anything buffered, and wait for all written audio to be
+    /// played.
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<B: Backend + ?Sized> Backend for &mut B {
+    fn format(&self) -> Format {
+        (**self).format()
+    }
+
+    fn write(&mut self, frames: &[Sample]) -> Result<()> {
+        (**self).write(frames)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        (**self).finish()
+    }
+}
+
+/// Play a node through a backend, one block at a time, after preparing it
+/// for the backend's format.
+///
+/// All output streams of the node are summed.  Plays `length` frames, or
+/// forever if it is None.  `check` is called before each block, and any
+/// error it returns stops playback.  This does not finish the backend, so
+/// the caller may decide how to wait for it.
+pub fn play(
+    node: &mut dyn Node,
+    backend: &mut dyn Backend,
+    length: Option<u64>,
+    check: impl FnMut() -> Result<()>,
+) -> Result<()> {
+    run(node, None, backend, length, check)
+}
+
+/// Play a node as [`play`] does, while feeding it audio captured from an
+/// input backend as its only input stream.  This runs in step with the
+/// input, so effects can be played live on a real instrument; once the
+/// input ends, the node is fed silence.  The input and output must have the
+/// same sample rate.
+pub fn play_through(
+    node: &mut dyn Node,
+    input: &mut dyn input::Backend,
+    backend: &mut dyn Backend,
+    length: Option<u64>,
+    check: impl FnMut() -> Result<()>,
+) -> Result<()> {
+    let (input_format, format) = (input.format(), backend.format());
+    if input_format.sample_rate != format.sample_rate {
+        return Err(format!(
+            "input sample rate {} does not match output sample rate {}",
+            input_format.sample_rate, format.sample_rate
+        )
+        .into());
+    }
+    run(node, Some(input), backend, length, check)
+}
+
+fn run(
+    node: &mut dyn Node,
+    mut input: Option<&mut dyn input::Backend>,
+    backend: &mut dyn Backend,
+    length: Option<u64>,
+    mut check: impl FnMut() -> Result<()>,
+) -> Result<()> {
+    let format = backend.format();
+    node.prepare(&Context {
+        sample_rate: format.sample_rate,
+        max_block_size: BLOCK_SIZE,
+        channels: format.channels.into(),
+    })?;
+    let silence = Sample::zeroed(
+        input
+            .as_ref()
+            .map_or(0, |input| input.format().channels.into()),
+    );
+    let mut remaining = length.unwrap_or(u64::MAX);
+    let mut outputs = Vec::new();
+    let mut captured = Vec::with_capacity(BLOCK_SIZE);
+    let mut block = Vec::with_capacity(BLOCK_SIZE);
+    while remaining > 0 {
+        check()?;
+        let frames = remaining.min(BLOCK_SIZE as u64) as usize;
+        captured.clear();
+        if let Some(input) = &mut input {
+            input.read(&mut captured, frames)?;
+            captured.resize(frames, silence.clone());
+        }
+        block.clear();
+        for index in 0..frames {
+            outputs.clear();
+            let inputs = captured.get(index..=index).unwrap_or_default();
+            node.process(inputs, &mut outputs)?;
+            block.push(outputs.iter().sum());
+        }
+        backend.write(&block)?;
+        remaining -= frames as u64;
+    }
+    Ok(())
+}
+
+/// A node within a graph, and where to write its output.
+pub type Stem<'a> = (Arc<Mutex<dyn Node>>, &'a mut dyn Backend);
+
+/// Render `length` frames of a graph in a single pass, writing the graph's
+/// output to `master` and the output of each stem node to its own backend,
+/// so that every stem is sample-aligned with the master.
+///
+/// Each stem is the sum of the output streams of a node within the graph,
+/// as it was processed for the master, so the node has to lead to the
+/// graph's output.  The graph is prepared for the master's format, and all
+/// backends are finished.  `check` is called before each block, as in
+/// [`play`].
+pub fn render_stems(
+    graph: &mut Graph,
+    length: u64,
+    master: &mut dyn Backend,
+    stems: &mut [Stem<'_>],
+    mut check: impl FnMut() -> Result<()>,
+) -> Result<()> {
+    let format = master.format();
+    for (node, backend) in stems.iter() {
+        if graph.output_of(node).is_none() {
+            return Err("stem node is not in the graph".into());
+        }
+        if backend.format().sample_rate != format.sample_rate {
+            return Err("stem sample rate does not match the master".into());
+        }
+    }
+    graph.prepare(&Context {
+        sample_rate: format.sample_rate,
+        max_block_size: BLOCK_SIZE,
+        channels: format.channels.into(),
+    })?;
+    let mut remaining = length;
+    let mut outputs = Vec::new();
+    let mut block = Vec::with_capacity(BLOCK_SIZE);
+    let mut stem_blocks = vec![Vec::with_capacity(BLOCK_SIZE); stems.len()];
+    while remaining > 0 {
+        check()?;
+        let frames = remaining.min(BLOCK_SIZE as u64) as usize;
+        block.clear();
+        stem_blocks.iter_mut().for_each(Vec::clear);
+        for _ in 0..frames {
+            outputs.clear();
+            graph.process(&[], &mut outputs)?;
+            block.push(outputs.iter().sum());
+            for ((node, _), stem_block) in stems.iter().zip(&mut stem_blocks) {
+                let output = graph.output_of(node).expect("checked above");
+                stem_block.push(output.iter().sum());
+            }
+        }
+        master.write(&block)?;
+        for ((_, backend), stem_block) in stems.iter_mut().zip(&stem_blocks) {
+            backend.write(stem_block)?;
+        }
+        remaining -= frames as u64;
+    }
+    master.finish()?;
+    for (_, backend) in stems.iter_mut() {
+        backend.finish()?;
+    }
+    Ok(())
+}
+
+/// Iterate the values of each frame, fit to the channel count.
+fn interleave(frames: &[Sample], channels: u16) -> impl Iterator<Item = f64> + '_ {
+    frames.iter().flat_map(move |frame| {
+        (0..channels as usize).map(move |channel| frame.get(channel).copied().unwrap_or(0.0))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::{ConstantValue, Gain};
+
+    #[test]
+    fn plays_the_requested_length() {
+        let format = Format {
+            sample_rate: 48000,
+            channels: 2,
+        };
+        let mut backend = Memory::new(format);
+        let mut node = ConstantValue::new(0.5);
+        let mut checks = 0;
+        play(&mut node, &mut backend, Some(1000), || {
+            checks += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(checks, 2);
+        assert_eq!(backend.frames.len(), 1000);
+        assert_eq!(
+            backend.interleaved().take(4).collect::<Vec<_>>(),
+            [0.5, 0.0, 0.5, 0.0]
+        );
+    }
+
+    #[test]
+    fn plays_through_the_input() {
+        let format = Format {
+            sample_rate: 48000,
+            channels: 1,
+        };
+        let frames = (1..=600).map(|index| Sample::from(index as f64)).collect();
+        let mut input = input::Memory::new(format, frames);
+        let mut backend = Memory::new(format);
+        let mut graph = Graph::default();
+        let gain: Arc<Mutex<dyn Node>> = Arc::new(Mutex::new(Gain::new(2.0)));
+        graph.input(gain.clone(), None).unwrap();
+        graph.output(gain, None).unwrap();
+        play_through(&mut graph, &mut input, &mut backend, Some(1000), || Ok(())).unwrap();
+        let played: Vec<f64> = backend.interleaved().collect();
+        assert_eq!(played.len(), 1000);
+        assert_eq!(played[..3], [2.0, 4.0, 6.0]);
+        assert_eq!(played[599], 1200.0);
+        assert_eq!(played[600], 0.0);
+    }
+
+    #[test]
+    fn renders_aligned_stems() {
+        let format = Format {
+            sample_rate: 48000,
+            channels: 1,
+        };
+        let mut graph = Graph::default();
+        let low: Arc<Mutex<dyn Node>> = Arc::new(Mutex::new(ConstantValue::new(0.25)));
+        let high: Arc<Mutex<dyn Node>> = Arc::new(Mutex::new(ConstantValue::new(0.5)));
+        graph.output(low.clone(), None).unwrap();
+        graph.output(high.clone(), None).unwrap();
+        let mut master = Memory::new(format);
+        let (mut low_stem, mut high_stem) = (Memory::new(format), Memory::new(format));
+        render_stems(
+            &mut graph,
+            600,
+            &mut master,
+            &mut [(low, &mut low_stem), (high, &mut high_stem)],
+            || Ok(()),
+        )
+        .unwrap();
+        for (backend, value) in [(master, 0.75), (low_stem, 0.25), (high_stem, 0.5)] {
+            assert_eq!(backend.frames.len(), 600);
+            assert!(backend.interleaved().all(|played| played == value));
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use super::{interleave, Backend, Format};
+use crate::{sample::Sample, Result};
+
+/// A fake device that keeps everything written to it, for deterministic
+/// tests without audio hardware.
+#[derive(Debug, Clone)]
+pub struct Memory {
+    format: Format,
+
+    /// Every frame written, one sample per frame, as it was written.
+    pub frames: Vec<Sample>,
+}
+
+impl Memory {
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            frames: Vec::new(),
+        }
+    }
+
+    /// The written audio as the device would have played it, interleaved and
+    /// fit to the channel count.
+    pub fn interleaved(&self) -> impl Iterator<Item = f64> + '_ {
+        interleave(&self.frames, self.format.channels)
+    }
+}
+
+impl Backend for Memory {
+    fn format(&self) -> Format {
+        self.format
+    }
+
+    fn write(&mut self, frames: &[Sample]) -> Result<()> {
+        self.frames.extend_from_slice(frames);
+        Ok(())
+    }
+}
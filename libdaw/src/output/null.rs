@@ -0,0 +1,24 @@
+use super::{Backend, Format};
+use crate::{sample::Sample, Result};
+
+/// A backend that discards all audio, as fast as it is written.
+#[derive(Debug, Clone, Copy)]
+pub struct Null {
+    format: Format,
+}
+
+impl Null {
+    pub fn new(format: Format) -> Self {
+        Self { format }
+    }
+}
+
+impl Backend for Null {
+    fn format(&self) -> Format {
+        self.format
+    }
+
+    fn write(&mut self, _: &[Sample]) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use super::{interleave, Backend, Format};
+use crate::{sample::Sample, Result};
+use std::io::Write;
+
+/// Writes raw interleaved 32-bit little-endian float audio to a writer, such
+/// as a pipe into another program.
+#[derive(Debug)]
+pub struct Pipe<W> {
+    format: Format,
+    writer: W,
+    buffer: Vec<u8>,
+}
+
+impl<W> Pipe<W>
+where
+    W: Write,
+{
+    pub fn new(format: Format, writer: W) -> Self {
+        Self {
+            format,
+            writer,
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> Backend for Pipe<W>
+where
+    W: Write,
+{
+    fn format(&self) -> Format {
+        self.format
+    }
+
+    fn write(&mut self, frames: &[Sample]) -> Result<()> {
+        self.buffer.clear();
+        for value in interleave(frames, self.format.channels) {
+            self.buffer.extend_from_slice(&(value as f32).to_le_bytes());
+        }
+        self.writer.write_all(&self.buffer)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
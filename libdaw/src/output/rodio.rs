@@ -0,0 +1,119 @@
+use super::{interleave, Backend, Format};
+use crate::{sample::Sample, Result};
+use ::rodio::{OutputStream, OutputStreamHandle, Sink};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+
+/// Blocks of audio buffered between the renderer and the device.
+const BUFFERED_BLOCKS: usize = 16;
+
+/// Rodio audio source, playing blocks as they arrive.
+struct Source {
+    format: Format,
+    receiver: Receiver<Vec<f32>>,
+    block: std::vec::IntoIter<f32>,
+}
+
+impl ::rodio::Source for Source {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.format.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.format.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<std::time::Duration> {
+        None
+    }
+}
+
+impl Iterator for Source {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.block.next() {
+                return Some(value);
+            }
+            // The sender being dropped ends the source.
+            self.block = self.receiver.recv().ok()?.into_iter();
+        }
+    }
+}
+
+/// Plays through the system's default output device, using rodio.
+pub struct Rodio {
+    format: Format,
+    sender: Option<SyncSender<Vec<f32>>>,
+    sink: Sink,
+    _handle: OutputStreamHandle,
+    _stream: OutputStream,
+}
+
+impl std::fmt::Debug for Rodio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rodio")
+            .field("format", &self.format)
+            .field("closed", &self.sender.is_none())
+            .finish()
+    }
+}
+
+impl Rodio {
+    /// Open the default output device.
+    pub fn open(format: Format) -> Result<Self> {
+        let (stream, handle) = OutputStream::try_default()?;
+        let sink = Sink::try_new(&handle)?;
+        let (sender, receiver) = sync_channel(BUFFERED_BLOCKS);
+        sink.append(Source {
+            format,
+            receiver,
+            block: Vec::new().into_iter(),
+        });
+        Ok(Self {
+            format,
+            sender: Some(sender),
+            sink,
+            _handle: handle,
+            _stream: stream,
+        })
+    }
+
+    /// Stop accepting audio, letting what has been written play out.
+    pub fn close(&mut self) {
+        self.sender = None;
+    }
+
+    /// Whether everything written has been played, after closing.
+    pub fn done(&self) -> bool {
+        self.sink.empty()
+    }
+}
+
+impl Backend for Rodio {
+    fn format(&self) -> Format {
+        self.format
+    }
+
+    fn write(&mut self, frames: &[Sample]) -> Result<()> {
+        let Some(sender) = &self.sender else {
+            return Err("can not write to a closed output".into());
+        };
+        sender.send(
+            interleave(frames, self.format.channels)
+                .map(|value| value as f32)
+                .collect(),
+        )?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.close();
+        self.sink.sleep_until_end();
+        Ok(())
+    }
+}
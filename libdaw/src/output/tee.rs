@@ -0,0 +1,23 @@
+use super::{Backend, Format};
+use crate::{sample::Sample, Result};
+
+/// Writes the same audio to two backends, such as the speakers and a file.
+/// The format is that of the first backend.
+#[derive(Debug, Clone)]
+pub struct Tee<A, B>(pub A, pub B);
+
+impl<A: Backend, B: Backend> Backend for Tee<A, B> {
+    fn format(&self) -> Format {
+        self.0.format()
+    }
+
+    fn write(&mut self, frames: &[Sample]) -> Result<()> {
+        self.0.write(frames)?;
+        self.1.write(frames)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.0.finish()?;
+        self.1.finish()
+    }
+}
@@ -0,0 +1,60 @@
+use super::{interleave, Backend, Format};
+use crate::{sample::Sample, Result};
+use std::{fs::File, io::BufWriter, path::Path};
+
+/// Writes audio to a 32-bit float WAV file.  The file is complete once the
+/// backend is finished.
+pub struct WavFile {
+    format: Format,
+    writer: Option<hound::WavWriter<BufWriter<File>>>,
+}
+
+impl std::fmt::Debug for WavFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WavFile")
+            .field("format", &self.format)
+            .field("finished", &self.writer.is_none())
+            .finish()
+    }
+}
+
+impl WavFile {
+    pub fn create(path: impl AsRef<Path>, format: Format) -> Result<Self> {
+        let writer = hound::WavWriter::create(
+            path,
+            hound::WavSpec {
+                channels: format.channels,
+                sample_rate: format.sample_rate,
+                bits_per_sample: 32,
+                sample_format: hound::SampleFormat::Float,
+            },
+        )?;
+        Ok(Self {
+            format,
+            writer: Some(writer),
+        })
+    }
+}
+
+impl Backend for WavFile {
+    fn format(&self) -> Format {
+        self.format
+    }
+
+    fn write(&mut self, frames: &[Sample]) -> Result<()> {
+        let Some(writer) = &mut self.writer else {
+            return Err("can not write to a finished WAV file".into());
+        };
+        for value in interleave(frames, self.format.channels) {
+            writer.write_sample(value as f32)?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if let Some(writer) = self.writer.take() {
+            writer.finalize()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+//! Lock-free ways to change nodes while they are processed on a real-time
+//! audio thread.
+//!
+//! Once their buffers have filled, the built-in nodes don't allocate in
+//! [`Node::process`].  [`Instrument`] builds and frees its voices on a
+//! thread of its own, shortly before and after they play.  Samples keep up to
+//! [`INLINE_CHANNELS`](crate::sample::INLINE_CHANNELS) channels without
+//! allocating.  What remains is getting changes from a control thread to the
+//! audio thread without locking a node the audio thread is processing, which
+//! is what this module is for: [`Parameter`] for single values, which is
+//! also a node to feed them into other nodes' inputs, [`controlled`] for
+//! arbitrary changes to one node, and [`graph`] for a graph that owns its
+//! nodes and has them inserted, removed, and connected between samples.
+//! Unlike [`nodes::Graph`](crate::nodes::Graph), which locks each node as it
+//! processes it, the realtime [`Graph`] never locks.
+//!
+//! [`Instrument`]: crate::nodes::Instrument
+
+mod graph;
+
+pub use graph::{graph, Connection, Graph, GraphController, NodeId};
+
+use crate::{sample::Sample, Context, Node, Result};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+        Arc,
+    },
+};
+
+/// A number shared between threads, which can be read and set at any time
+/// without locking.
+#[derive(Debug, Clone, Default)]
+pub struct Parameter(Arc<AtomicU64>);
+
+impl Parameter {
+    pub fn new(value: f64) -> Self {
+        Self(Arc::new(AtomicU64::new(value.to_bits())))
+    }
+
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    pub fn set(&self, value: f64) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// A parameter is also a node with its value as its only output stream, so
+/// it can drive any node input, like an oscillator's frequency or one side
+/// of a [`Multiply`](crate::nodes::Multiply).
+impl Node for Parameter {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        _: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        outputs.push(self.get().into());
+        Ok(())
+    }
+}
+
+/// A change waiting to be made to a node.  It is only ever called once, but
+/// is `FnMut` so that the box can be sent back to be freed after running.
+type Command<N> = Box<dyn FnMut(&mut N) + Send>;
+
+/// Wrap a node so that changes to it can be queued from another thread.
+///
+/// Up to `capacity` changes may be waiting at once.  The [`Controlled`] node
+/// goes to the audio thread, and the [`Controller`] stays with the control
+/// thread.
+pub fn controlled<N: Node>(node: N, capacity: usize) -> (Controller<N>, Controlled<N>) {
+    let (commands, receiver) = sync_channel(capacity);
+    let (spent_sender, spent) = sync_channel(capacity);
+    (
+        Controller { commands, spent },
+        Controlled {
+            node,
+            commands: receiver,
+            spent: spent_sender,
+        },
+    )
+}
+
+/// The control side of a [`controlled`] node.
+pub struct Controller<N> {
+    commands: SyncSender<Command<N>>,
+
+    /// Commands that have been run, to be freed on this side.
+    spent: Receiver<Command<N>>,
+}
+
+impl<N> Controller<N> {
+    /// Queue a change to the node, to be made before it processes its next
+    /// sample.  The change is boxed here and sent back here to be freed once
+    /// it has run, so the audio thread never allocates for it.  Anything the
+    /// change drops, like a value it replaces, is still dropped on the audio
+    /// thread.
+    ///
+    /// Fails without blocking if the queue is full or the node is gone.
+    pub fn send(&mut self, change: impl FnOnce(&mut N) + Send + 'static) -> Result<()> {
+        while self.spent.try_recv().is_ok() {}
+        let mut change = Some(change);
+        let command: Command<N> = Box::new(move |node| {
+            if let Some(change) = change.take() {
+                change(node);
+            }
+        });
+        self.commands
+            .try_send(command)
+            .map_err(|error| match error {
+                TrySendError::Full(_) => "the command queue is full".into(),
+                TrySendError::Disconnected(_) => "the controlled node was dropped".into(),
+            })
+    }
+}
+
+impl<N> fmt::Debug for Controller<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Controller").finish_non_exhaustive()
+    }
+}
+
+/// The audio side of a [`controlled`] node, which processes as the node
+/// does after making any queued changes.
+pub struct Controlled<N> {
+    node: N,
+    commands: Receiver<Command<N>>,
+    spent: SyncSender<Command<N>>,
+}
+
+impl<N> Controlled<N> {
+    fn apply(&mut self) {
+        while let Ok(mut command) = self.commands.try_recv() {
+            command(&mut self.node);
+            // If the controller is gone, the command is freed here instead.
+            let _ = self.spent.try_send(command);
+        }
+    }
+
+    pub fn node(&self) -> &N {
+        &self.node
+    }
+}
+
+impl<N: fmt::Debug> fmt::Debug for Controlled<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Controlled")
+            .field("node", &self.node)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<N: Node> Node for Controlled<N> {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        self.apply();
+        self.node.process(inputs, outputs)
+    }
+
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        self.apply();
+        self.node.prepare(context)
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.apply();
+        self.node.reset()
+    }
+
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.apply();
+        self.node.seek(sample)
+    }
+}
@@ -0,0 +1,523 @@
+use crate::{sample::Sample, Context, Node, Result};
+use std::{
+    fmt,
+    sync::{
+        mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+        Arc, Mutex,
+    },
+};
+
+/// A node in a realtime [`Graph`], as returned by
+/// [`GraphController::insert`].  Ids are not reused, so one left over from
+/// a removed node never refers to a later node in the same slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    slot: usize,
+    generation: u64,
+}
+
+/// A connection within a realtime graph.  A source of `None` is the graph's
+/// input, and a destination of `None` is its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Connection {
+    pub source: Option<NodeId>,
+    pub destination: Option<NodeId>,
+    pub stream: Option<usize>,
+}
+
+/// Where a node's input streams come from.  A source of `None` is the
+/// graph's input.
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    source: Option<usize>,
+    stream: Option<usize>,
+}
+
+#[derive(Debug)]
+struct Step {
+    node: usize,
+    inputs: Vec<Edge>,
+}
+
+/// The order to process nodes in, and where each gets its inputs, built on
+/// the control side whenever the connections change.
+#[derive(Debug, Default)]
+struct Plan {
+    steps: Vec<Step>,
+    outputs: Vec<Edge>,
+}
+
+/// A change to the graph.  The audio side swaps in what it carries and
+/// sends it back holding what was replaced, so that is freed on the control
+/// side.
+struct Command {
+    node: Option<(usize, Option<Box<dyn Node>>)>,
+    plan: Option<Plan>,
+}
+
+/// Make a graph whose nodes and connections can be changed from another
+/// thread.
+///
+/// The graph holds up to `slots` nodes, and up to `capacity` changes may be
+/// waiting at once.  The [`Graph`] goes to the audio thread, and the
+/// [`GraphController`] stays with the control thread.
+pub fn graph(slots: usize, capacity: usize) -> (GraphController, Graph) {
+    let (commands, receiver) = sync_channel(capacity);
+    let (spent_sender, spent) = sync_channel(capacity);
+    let context = Arc::new(Mutex::new(None));
+    (
+        GraphController {
+            commands,
+            spent,
+            context: context.clone(),
+            slots: vec![Slot::default(); slots],
+            connections: Vec::new(),
+        },
+        Graph {
+            nodes: (0..slots).map(|_| None).collect(),
+            outputs: vec![Vec::new(); slots],
+            plan: Plan::default(),
+            input_buffer: Vec::new(),
+            commands: receiver,
+            spent: spent_sender,
+            context,
+        },
+    )
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Slot {
+    generation: u64,
+    occupied: bool,
+}
+
+/// The control side of a realtime [`graph`], which keeps its own copy of
+/// the connections to plan the audio side's processing order.
+pub struct GraphController {
+    commands: SyncSender<Command>,
+
+    /// Commands that have been applied, to be freed on this side.
+    spent: Receiver<Command>,
+
+    /// The context the graph was last prepared with, which inserted nodes
+    /// are prepared with.
+    context: Arc<Mutex<Option<Context>>>,
+
+    slots: Vec<Slot>,
+    connections: Vec<Connection>,
+}
+
+impl GraphController {
+    fn contains(&self, node: NodeId) -> bool {
+        self.slots
+            .get(node.slot)
+            .is_some_and(|slot| slot.occupied && slot.generation == node.generation)
+    }
+
+    fn check(&self, node: Option<NodeId>) -> Result<()> {
+        match node {
+            Some(node) if !self.contains(node) => Err("the node is not in the graph".into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Send a change, only keeping the new connections if it was sent.
+    fn send(
+        &mut self,
+        node: Option<(usize, Option<Box<dyn Node>>)>,
+        connections: Option<Vec<Connection>>,
+    ) -> Result<()> {
+        while self.spent.try_recv().is_ok() {}
+        let plan = connections
+            .as_ref()
+            .map(|connections| self.plan(connections));
+        self.commands
+            .try_send(Command { node, plan })
+            .map_err(|error| match error {
+                TrySendError::Full(_) => "the command queue is full",
+                TrySendError::Disconnected(_) => "the graph was dropped",
+            })?;
+        if let Some(connections) = connections {
+            self.connections = connections;
+        }
+        Ok(())
+    }
+
+    /// Put a node in a free slot, preparing it with the graph's context if
+    /// the graph has been prepared.  It is processed once it is connected
+    /// to the output.
+    pub fn insert(&mut self, node: impl Node + 'static) -> Result<NodeId> {
+        let mut node: Box<dyn Node> = Box::new(node);
+        let Some(index) = self.slots.iter().position(|slot| !slot.occupied) else {
+            return Err("the graph has no free slots".into());
+        };
+        // Held until the node is sent, so the graph can't be prepared with
+        // a different context in between.
+        let context = self.context.clone();
+        let context = context.lock().expect("poisoned");
+        if let Some(context) = &*context {
+            node.prepare(context)?;
+        }
+        self.send(Some((index, Some(node))), None)?;
+        let slot = &mut self.slots[index];
+        slot.occupied = true;
+        Ok(NodeId {
+            slot: index,
+            generation: slot.generation,
+        })
+    }
+
+    /// Remove a node and all of its connections, returning whether it was
+    /// in the graph.  The node is freed on this side.
+    pub fn remove(&mut self, node: NodeId) -> Result<bool> {
+        if !self.contains(node) {
+            return Ok(false);
+        }
+        let connections = self
+            .connections
+            .iter()
+            .filter(|connection| {
+                connection.source != Some(node) && connection.destination != Some(node)
+            })
+            .copied()
+            .collect();
+        self.send(Some((node.slot, None)), Some(connections))?;
+        let slot = &mut self.slots[node.slot];
+        slot.occupied = false;
+        slot.generation += 1;
+        Ok(true)
+    }
+
+    fn link(&mut self, connection: Connection) -> Result<()> {
+        self.check(connection.source)?;
+        self.check(connection.destination)?;
+        let mut connections = self.connections.clone();
+        connections.push(connection);
+        self.send(None, Some(connections))
+    }
+
+    fn unlink(&mut self, connection: Connection) -> Result<bool> {
+        let Some(index) = self.connections.iter().rposition(|&c| c == connection) else {
+            return Ok(false);
+        };
+        let mut connections = self.connections.clone();
+        connections.remove(index);
+        self.send(None, Some(connections))?;
+        Ok(true)
+    }
+
+    /// Connect the given output of the source to the destination.  The same
+    /// output may be attached multiple times. `None` will attach all outputs.
+    pub fn connect(
+        &mut self,
+        source: NodeId,
+        destination: NodeId,
+        stream: Option<usize>,
+    ) -> Result<()> {
+        self.link(Connection {
+            source: Some(source),
+            destination: Some(destination),
+            stream,
+        })
+    }
+
+    /// Disconnect the last-added matching connection, returning whether
+    /// anything was disconnected.
+    pub fn disconnect(
+        &mut self,
+        source: NodeId,
+        destination: NodeId,
+        stream: Option<usize>,
+    ) -> Result<bool> {
+        self.unlink(Connection {
+            source: Some(source),
+            destination: Some(destination),
+            stream,
+        })
+    }
+
+    /// Connect the given stream of the graph's input to the destination.
+    pub fn input(&mut self, destination: NodeId, stream: Option<usize>) -> Result<()> {
+        self.link(Connection {
+            source: None,
+            destination: Some(destination),
+            stream,
+        })
+    }
+
+    pub fn remove_input(&mut self, destination: NodeId, stream: Option<usize>) -> Result<bool> {
+        self.unlink(Connection {
+            source: None,
+            destination: Some(destination),
+            stream,
+        })
+    }
+
+    /// Connect the given output of the source to the graph's output.
+    pub fn output(&mut self, source: NodeId, stream: Option<usize>) -> Result<()> {
+        self.link(Connection {
+            source: Some(source),
+            destination: None,
+            stream,
+        })
+    }
+
+    pub fn remove_output(&mut self, source: NodeId, stream: Option<usize>) -> Result<bool> {
+        self.unlink(Connection {
+            source: Some(source),
+            destination: None,
+            stream,
+        })
+    }
+
+    /// Every connection in the graph, in the order they were made.
+    pub fn connections(&self) -> &[Connection] {
+        &self.connections
+    }
+
+    /// Plan to process each node after all of its sources, walking back
+    /// from the output as [`crate::nodes::Graph`] does, so a node in a
+    /// cycle gets the previous sample's output of the node that closes it.
+    fn plan(&self, connections: &[Connection]) -> Plan {
+        fn edges(connections: &[Connection], destination: Option<usize>) -> Vec<Edge> {
+            connections
+                .iter()
+                .filter(|connection| connection.destination.map(|node| node.slot) == destination)
+                .map(|connection| Edge {
+                    source: connection.source.map(|node| node.slot),
+                    stream: connection.stream,
+                })
+                .collect()
+        }
+
+        fn walk(connections: &[Connection], visited: &mut [bool], plan: &mut Plan, node: usize) {
+            if std::mem::replace(&mut visited[node], true) {
+                return;
+            }
+            let inputs = edges(connections, Some(node));
+            for source in inputs.iter().filter_map(|edge| edge.source) {
+                walk(connections, visited, plan, source);
+            }
+            plan.steps.push(Step { node, inputs });
+        }
+
+        let mut plan = Plan {
+            steps: Vec::new(),
+            outputs: edges(connections, None),
+        };
+        let mut visited = vec![false; self.slots.len()];
+        for source in plan.outputs.clone().iter().filter_map(|edge| edge.source) {
+            walk(connections, &mut visited, &mut plan, source);
+        }
+        plan
+    }
+}
+
+impl fmt::Debug for GraphController {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphController")
+            .field("connections", &self.connections)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The audio side of a realtime [`graph`], which owns its nodes, so
+/// processing it never locks.
+///
+/// Changes from the [`GraphController`] are applied before the next sample.
+/// Nodes that need changes of their own can be wrapped with
+/// [`controlled`](super::controlled), or take a [`Parameter`](super::Parameter)
+/// node as an input.
+pub struct Graph {
+    nodes: Vec<Option<Box<dyn Node>>>,
+    outputs: Vec<Vec<Sample>>,
+    plan: Plan,
+
+    /// Reused between nodes and samples, so processing doesn't allocate.
+    input_buffer: Vec<Sample>,
+
+    commands: Receiver<Command>,
+    spent: SyncSender<Command>,
+    context: Arc<Mutex<Option<Context>>>,
+}
+
+impl Graph {
+    fn apply(&mut self) {
+        while let Ok(mut command) = self.commands.try_recv() {
+            if let Some((slot, node)) = &mut command.node {
+                std::mem::swap(&mut self.nodes[*slot], node);
+                self.outputs[*slot].clear();
+            }
+            if let Some(plan) = &mut command.plan {
+                std::mem::swap(&mut self.plan, plan);
+            }
+            // If the controller is gone, the command is freed here instead.
+            let _ = self.spent.try_send(command);
+        }
+    }
+
+    fn nodes(&mut self) -> impl Iterator<Item = &mut Box<dyn Node>> {
+        self.nodes.iter_mut().flatten()
+    }
+}
+
+/// Add the streams the edges select to the buffer.
+fn gather(edges: &[Edge], inputs: &[Sample], outputs: &[Vec<Sample>], buffer: &mut Vec<Sample>) {
+    for edge in edges {
+        let source = match edge.source {
+            Some(node) => &outputs[node],
+            None => inputs,
+        };
+        match edge.stream {
+            Some(stream) => buffer.extend(source.get(stream).cloned()),
+            None => buffer.extend_from_slice(source),
+        }
+    }
+}
+
+impl fmt::Debug for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Graph")
+            .field("nodes", &self.nodes)
+            .field("plan", &self.plan)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Node for Graph {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [Sample],
+        outputs: &'c mut Vec<Sample>,
+    ) -> Result<()> {
+        self.apply();
+        let buffer = &mut self.input_buffer;
+        for step in &self.plan.steps {
+            buffer.clear();
+            gather(&step.inputs, inputs, &self.outputs, buffer);
+            let output = &mut self.outputs[step.node];
+            output.clear();
+            if let Some(node) = &mut self.nodes[step.node] {
+                node.process(buffer, output)?;
+            }
+        }
+        buffer.clear();
+        gather(&self.plan.outputs, inputs, &self.outputs, outputs);
+        Ok(())
+    }
+
+    /// Prepare every node.  The context is kept, and nodes inserted
+    /// afterward are prepared with it before they are sent.
+    fn prepare(&mut self, context: &Context) -> Result<()> {
+        let shared = self.context.clone();
+        let mut shared = shared.lock().expect("poisoned");
+        *shared = Some(*context);
+        self.apply();
+        for node in self.nodes() {
+            node.prepare(context)?;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.apply();
+        self.outputs.iter_mut().for_each(Vec::clear);
+        for node in self.nodes() {
+            node.reset()?;
+        }
+        Ok(())
+    }
+
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.apply();
+        self.outputs.iter_mut().for_each(Vec::clear);
+        for node in self.nodes() {
+            node.seek(sample)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nodes::Gain, realtime::Parameter};
+
+    fn process(graph: &mut Graph, input: f64) -> Vec<f64> {
+        let mut outputs = Vec::new();
+        graph.process(&[Sample::from(input)], &mut outputs).unwrap();
+        outputs.iter().map(|sample| sample[0]).collect()
+    }
+
+    #[test]
+    fn shared_source_is_processed_first() {
+        let (mut controller, mut graph) = super::graph(4, 8);
+        let source = controller.insert(Gain::new(1.0)).unwrap();
+        let branch = controller.insert(Gain::new(2.0)).unwrap();
+        controller.output(source, None).unwrap();
+        controller.output(branch, None).unwrap();
+        controller.input(source, None).unwrap();
+        controller.connect(source, branch, None).unwrap();
+        assert_eq!(process(&mut graph, 1.0), [1.0, 2.0]);
+    }
+
+    #[test]
+    fn changes_apply_before_the_next_sample() {
+        let (mut controller, mut graph) = super::graph(2, 8);
+        let gain = controller.insert(Gain::new(2.0)).unwrap();
+        controller.input(gain, None).unwrap();
+        controller.output(gain, None).unwrap();
+        assert_eq!(process(&mut graph, 1.0), [2.0]);
+        controller.input(gain, None).unwrap();
+        assert_eq!(process(&mut graph, 1.0), [2.0, 2.0]);
+        assert!(controller.remove_output(gain, None).unwrap());
+        assert_eq!(process(&mut graph, 1.0), []);
+        controller.output(gain, Some(1)).unwrap();
+        assert!(controller.remove(gain).unwrap());
+        assert!(controller.connections().is_empty());
+        assert_eq!(process(&mut graph, 1.0), []);
+        assert!(!controller.remove(gain).unwrap());
+    }
+
+    #[test]
+    fn old_ids_are_not_reused() {
+        let (mut controller, _graph) = super::graph(1, 8);
+        let first = controller.insert(Gain::new(1.0)).unwrap();
+        assert!(controller.insert(Gain::new(1.0)).is_err());
+        controller.remove(first).unwrap();
+        let second = controller.insert(Gain::new(1.0)).unwrap();
+        assert!(controller.output(first, None).is_err());
+        controller.output(second, None).unwrap();
+    }
+
+    /// Keeps the sample rate it was prepared with.
+    #[derive(Debug)]
+    struct Rate(Parameter);
+
+    impl Node for Rate {
+        fn process<'a, 'b, 'c>(
+            &'a mut self,
+            _: &'b [Sample],
+            _: &'c mut Vec<Sample>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        fn prepare(&mut self, context: &Context) -> Result<()> {
+            self.0.set(context.sample_rate.into());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn prepares_nodes_inserted_later() {
+        let (mut controller, mut graph) = super::graph(2, 8);
+        let before = Parameter::default();
+        controller.insert(Rate(before.clone())).unwrap();
+        graph.prepare(&Context::new(96000)).unwrap();
+        assert_eq!(before.get(), 96000.0);
+        let after = Parameter::default();
+        controller.insert(Rate(after.clone())).unwrap();
+        assert_eq!(after.get(), 96000.0);
+    }
+}
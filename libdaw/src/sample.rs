@@ -6,17 +6,26 @@ use std::{
     iter::{Product, Sum},
     ops::{Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
 };
+use tinyvec::TinyVec;
+
+/// The number of channels a sample can hold without allocating.
+pub const INLINE_CHANNELS: usize = 8;
+
+/// The storage for the channels of a sample, which only goes to the heap
+/// past [`INLINE_CHANNELS`] channels, so that creating and cloning samples
+/// in the audio path doesn't allocate.
+pub type Channels = TinyVec<[f64; INLINE_CHANNELS]>;
 
 #[derive(Debug, Clone, Default)]
 pub struct Sample {
-    pub channels: Vec<f64>,
+    pub channels: Channels,
 }
 
 impl Sample {
     pub fn zeroed(len: usize) -> Self {
-        Self {
-            channels: vec![0.0; len],
-        }
+        let mut channels = Channels::default();
+        channels.resize(len, 0.0);
+        Self { channels }
     }
     pub fn iter(&self) -> Iter<'_> {
         Iter(self.channels.iter())
@@ -32,7 +41,7 @@ impl IntoIterator for Sample {
     type IntoIter = IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        IntoIter(self.channels.into_iter())
+        IntoIter::new(self.channels)
     }
 }
 impl<'a> IntoIterator for &'a Sample {
@@ -68,20 +77,29 @@ impl FromIterator<f64> for Sample {
 /// Expected to always be a single-channel sample.
 impl From<f64> for Sample {
     fn from(sample: f64) -> Self {
-        let mut channels = Vec::new();
-        channels.reserve_exact(1);
+        let mut channels = Channels::default();
         channels.push(sample);
         Self { channels }
     }
 }
+impl<const N: usize> From<[f64; N]> for Sample {
+    fn from(sample: [f64; N]) -> Self {
+        sample.into_iter().collect()
+    }
+}
 impl From<Vec<f64>> for Sample {
     fn from(sample: Vec<f64>) -> Self {
-        Self { channels: sample }
+        let channels = if sample.len() > INLINE_CHANNELS {
+            TinyVec::Heap(sample)
+        } else {
+            sample.into_iter().collect()
+        };
+        Self { channels }
     }
 }
 impl From<Sample> for Vec<f64> {
     fn from(value: Sample) -> Self {
-        value.channels
+        value.channels.into_vec()
     }
 }
 
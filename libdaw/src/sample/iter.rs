@@ -1,11 +1,26 @@
+use super::Channels;
 use std::iter::FusedIterator;
 
 #[derive(Debug, Clone, Default)]
-pub struct IntoIter(pub(super) std::vec::IntoIter<f64>);
+pub struct IntoIter {
+    channels: Channels,
+    front: usize,
+    back: usize,
+}
+
+impl IntoIter {
+    pub(super) fn new(channels: Channels) -> Self {
+        Self {
+            back: channels.len(),
+            channels,
+            front: 0,
+        }
+    }
+}
 
 impl AsRef<[f64]> for IntoIter {
     fn as_ref(&self) -> &[f64] {
-        self.0.as_ref()
+        &self.channels[self.front..self.back]
     }
 }
 
@@ -13,28 +28,41 @@ impl Iterator for IntoIter {
     type Item = f64;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+        self.nth(0)
     }
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.0.size_hint()
+        let len = self.back - self.front;
+        (len, Some(len))
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        self.0.nth(n)
+        self.front = self.front.saturating_add(n).min(self.back);
+        if self.front < self.back {
+            self.front += 1;
+            Some(self.channels[self.front - 1])
+        } else {
+            None
+        }
     }
 }
 
 impl DoubleEndedIterator for IntoIter {
     fn next_back(&mut self) -> Option<Self::Item> {
-        self.0.next_back()
+        self.nth_back(0)
     }
     fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
-        self.0.nth_back(n)
+        self.back = self.back.saturating_sub(n).max(self.front);
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.channels[self.back])
+        } else {
+            None
+        }
     }
 }
 
 impl ExactSizeIterator for IntoIter {
     fn len(&self) -> usize {
-        self.0.len()
+        self.back - self.front
     }
 }
 
@@ -0,0 +1,354 @@
+//! Non-blocking playback with transport controls, rendering a node to an
+//! output backend on a background thread while it is started, paused,
+//! sought, and looped from another.
+
+use crate::{
+    output::{Backend, Format, BLOCK_SIZE},
+    sample::Sample,
+    Context, Error, Node, Result,
+};
+use std::sync::{
+    mpsc::{sync_channel, SyncSender},
+    Arc, Mutex,
+};
+
+/// The playback state shared with the output thread.
+#[derive(Debug)]
+struct State {
+    node: Arc<Mutex<dyn Node>>,
+    playing: bool,
+    sample: u64,
+
+    /// The start and end samples of the loop region.
+    looping: Option<(u64, u64)>,
+
+    /// The last processing error, which pauses playback.
+    error: Option<Error>,
+
+    outputs: Vec<Sample>,
+    block: Vec<Sample>,
+}
+
+impl State {
+    fn seek(&mut self, sample: u64) -> Result<()> {
+        self.node.lock().expect("poisoned").seek(sample)?;
+        self.sample = sample;
+        Ok(())
+    }
+
+    /// Render the next block, which is silent while paused.
+    fn render(&mut self, frames: usize) {
+        self.block.clear();
+        if self.playing {
+            if let Err(error) = self.process(frames) {
+                self.error = Some(error);
+                self.playing = false;
+            }
+        }
+        self.block.resize(frames, Sample::default());
+    }
+
+    fn process(&mut self, frames: usize) -> Result<()> {
+        let node = self.node.clone();
+        let mut node = node.lock().expect("poisoned");
+        for _ in 0..frames {
+            if let Some((start, end)) = self.looping {
+                if self.sample >= end {
+                    node.seek(start)?;
+                    self.sample = start;
+                }
+            }
+            self.outputs.clear();
+            node.process(&[], &mut self.outputs)?;
+            self.block.push(self.outputs.iter().sum());
+            self.sample += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Plays a node from a movable position, like the transport of a tape
+/// machine.
+///
+/// The node is prepared for the format when it is given to the transport.
+/// Audio is rendered a block at a time to a backend, either directly with
+/// [`Transport::render`] or continuously on a background thread with
+/// [`Transport::open`].  While paused, silence is rendered, and an error
+/// from the node pauses playback until it is taken with
+/// [`Transport::take_error`].  Clones share the same playback state.
+#[derive(Debug, Clone)]
+pub struct Transport {
+    state: Arc<Mutex<State>>,
+
+    /// The block being written, swapped out of the state so that the state
+    /// isn't locked while the backend blocks.
+    block: Arc<Mutex<Vec<Sample>>>,
+
+    format: Format,
+}
+
+impl Transport {
+    pub fn new(node: Arc<Mutex<dyn Node>>, format: Format) -> Result<Self> {
+        let transport = Self {
+            state: Arc::new(Mutex::new(State {
+                node: node.clone(),
+                playing: false,
+                sample: 0,
+                looping: None,
+                error: None,
+                outputs: Vec::new(),
+                block: Vec::with_capacity(BLOCK_SIZE),
+            })),
+            block: Arc::new(Mutex::new(Vec::with_capacity(BLOCK_SIZE))),
+            format,
+        };
+        node.lock()
+            .expect("poisoned")
+            .prepare(&transport.context())?;
+        Ok(transport)
+    }
+
+    fn context(&self) -> Context {
+        Context {
+            sample_rate: self.format.sample_rate,
+            max_block_size: BLOCK_SIZE,
+            channels: self.format.channels.into(),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("poisoned")
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn node(&self) -> Arc<Mutex<dyn Node>> {
+        self.state().node.clone()
+    }
+
+    /// Swap the node, which is prepared and picks up from the current
+    /// position.
+    pub fn set_node(&self, node: Arc<Mutex<dyn Node>>) -> Result<()> {
+        let context = self.context();
+        let mut state = self.state();
+        {
+            let mut lock = node.lock().expect("poisoned");
+            lock.prepare(&context)?;
+            lock.seek(state.sample)?;
+        }
+        state.node = node;
+        Ok(())
+    }
+
+    pub fn play(&self) {
+        self.state().playing = true;
+    }
+
+    pub fn pause(&self) {
+        self.state().playing = false;
+    }
+
+    pub fn playing(&self) -> bool {
+        self.state().playing
+    }
+
+    /// The position of the next sample to be rendered.
+    pub fn sample(&self) -> u64 {
+        self.state().sample
+    }
+
+    /// Move the position, seeking the node to match.
+    pub fn seek(&self, sample: u64) -> Result<()> {
+        self.state().seek(sample)
+    }
+
+    /// Loop playback between a start and end sample, or stop looping with
+    /// None.
+    pub fn set_loop(&self, region: Option<(u64, u64)>) -> Result<()> {
+        if let Some((start, end)) = region {
+            if end <= start {
+                return Err("loop end must be after its start".into());
+            }
+        }
+        self.state().looping = region;
+        Ok(())
+    }
+
+    pub fn loop_region(&self) -> Option<(u64, u64)> {
+        self.state().looping
+    }
+
+    /// Take the error that last paused playback, if there was one.
+    pub fn take_error(&self) -> Option<Error> {
+        self.state().error.take()
+    }
+
+    /// Render a block of up to [`BLOCK_SIZE`] frames to a backend.  Errors
+    /// from the node pause playback rather than being returned; only errors
+    /// from the backend are.  The playback state is only locked while the
+    /// block is rendered, not while the backend writes it.
+    pub fn render(&self, backend: &mut dyn Backend, frames: usize) -> Result<()> {
+        let mut block = self.block.lock().expect("poisoned");
+        {
+            let mut state = self.state();
+            state.render(frames.min(BLOCK_SIZE));
+            std::mem::swap(&mut state.block, &mut block);
+        }
+        backend.write(&block)
+    }
+
+    /// Render continuously to a backend on a background thread, until the
+    /// returned [`Output`] is dropped.  The backend is opened on that thread
+    /// by `open`, so it need not be [`Send`], and it should block on writes
+    /// to keep pace with its device.  Opening errors are returned here;
+    /// a later write error pauses playback as a node error does.
+    pub fn open<B, F>(&self, open: F) -> Result<Output>
+    where
+        B: Backend,
+        F: FnOnce(Format) -> Result<B> + Send + 'static,
+    {
+        let transport = self.clone();
+        let (ready_sender, ready) = sync_channel(1);
+        let (shutdown, shutdown_receiver) = sync_channel::<()>(1);
+        std::thread::spawn(move || {
+            let mut backend = match open(transport.format) {
+                Ok(backend) => {
+                    let _ = ready_sender.send(Ok(()));
+                    backend
+                }
+                Err(error) => {
+                    let _ = ready_sender.send(Err(error));
+                    return;
+                }
+            };
+            // Either a message or a disconnect means shutdown.
+            while shutdown_receiver.try_recv() == Err(std::sync::mpsc::TryRecvError::Empty) {
+                if let Err(error) = transport.render(&mut backend, BLOCK_SIZE) {
+                    let mut state = transport.state();
+                    state.error = Some(error);
+                    state.playing = false;
+                    return;
+                }
+            }
+        });
+        ready.recv()??;
+        Ok(Output { shutdown })
+    }
+}
+
+/// A background thread rendering a transport to a backend.  Dropping this
+/// stops the thread and closes the backend, without waiting for it.
+#[derive(Debug)]
+pub struct Output {
+    shutdown: SyncSender<()>,
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        let _ = self.shutdown.try_send(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Memory;
+
+    /// Plays the number of each sample, to show where it has been sought.
+    #[derive(Debug, Default)]
+    struct Counter(u64);
+
+    impl Node for Counter {
+        fn process<'a, 'b, 'c>(
+            &'a mut self,
+            _: &'b [Sample],
+            outputs: &'c mut Vec<Sample>,
+        ) -> Result<()> {
+            outputs.push(Sample::from(self.0 as f64));
+            self.0 += 1;
+            Ok(())
+        }
+
+        fn seek(&mut self, sample: u64) -> Result<()> {
+            self.0 = sample;
+            Ok(())
+        }
+    }
+
+    fn setup() -> (Transport, Memory) {
+        let format = Format {
+            sample_rate: 48000,
+            channels: 1,
+        };
+        let transport = Transport::new(Arc::new(Mutex::new(Counter::default())), format).unwrap();
+        (transport, Memory::new(format))
+    }
+
+    fn played(backend: &Memory) -> Vec<f64> {
+        backend.interleaved().collect()
+    }
+
+    #[test]
+    fn wraps_at_the_loop_end() {
+        let (transport, mut backend) = setup();
+        transport.set_loop(Some((2, 5))).unwrap();
+        transport.play();
+        transport.render(&mut backend, 9).unwrap();
+        assert_eq!(
+            played(&backend),
+            [0.0, 1.0, 2.0, 3.0, 4.0, 2.0, 3.0, 4.0, 2.0]
+        );
+        assert_eq!(transport.sample(), 3);
+    }
+
+    #[test]
+    fn seeks_the_node() {
+        let (transport, mut backend) = setup();
+        transport.play();
+        transport.render(&mut backend, 2).unwrap();
+        transport.seek(100).unwrap();
+        assert_eq!(transport.sample(), 100);
+        transport.render(&mut backend, 2).unwrap();
+        assert_eq!(played(&backend), [0.0, 1.0, 100.0, 101.0]);
+    }
+
+    #[test]
+    fn pauses_to_silence() {
+        let (transport, mut backend) = setup();
+        transport.play();
+        transport.render(&mut backend, 2).unwrap();
+        transport.pause();
+        transport.render(&mut backend, 2).unwrap();
+        transport.play();
+        transport.render(&mut backend, 2).unwrap();
+        assert_eq!(played(&backend), [0.0, 1.0, 0.0, 0.0, 2.0, 3.0]);
+        assert_eq!(transport.sample(), 4);
+    }
+
+    /// Pauses the transport from inside its own write.
+    struct Pausing(Transport);
+
+    impl Backend for Pausing {
+        fn format(&self) -> Format {
+            self.0.format()
+        }
+
+        fn write(&mut self, _: &[Sample]) -> Result<()> {
+            self.0.pause();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writes_without_locking_the_state() {
+        let (transport, _) = setup();
+        transport.play();
+        transport
+            .render(&mut Pausing(transport.clone()), 2)
+            .unwrap();
+        assert!(!transport.playing());
+        assert_eq!(transport.sample(), 2);
+    }
+}
@@ -0,0 +1,97 @@
+//! Reading and writing WAV files as sequences of samples, one per frame.
+
+use crate::{sample::Sample, Result};
+use std::path::Path;
+
+/// Audio loaded from a WAV file.
+#[derive(Debug, Clone, Default)]
+pub struct Wav {
+    pub sample_rate: u32,
+    pub channels: u16,
+
+    /// One sample per frame, each having a value per channel.
+    pub samples: Vec<Sample>,
+}
+
+/// Read a whole WAV file into memory.  Integer formats are normalized to the
+/// range of -1 to 1.
+pub fn read(path: impl AsRef<Path>) -> Result<Wav> {
+    let mut reader = hound::WavReader::open(path)?;
+    let spec = reader.spec();
+    let channels = spec.channels as usize;
+    let values: Vec<f64> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .map(|value| value.map(f64::from))
+            .collect::<std::result::Result<_, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = (1u64 << (spec.bits_per_sample - 1)) as f64;
+            reader
+                .samples::<i32>()
+                .map(|value| value.map(|value| value as f64 / scale))
+                .collect::<std::result::Result<_, _>>()?
+        }
+    };
+    Ok(Wav {
+        sample_rate: spec.sample_rate,
+        channels: spec.channels,
+        samples: values
+            .chunks_exact(channels)
+            .map(|frame| frame.to_vec().into())
+            .collect(),
+    })
+}
+
+/// Write samples to a 32-bit float WAV file.  Samples with fewer channels
+/// than `channels` are padded with silence, and extra channels are dropped.
+pub fn write<'a>(
+    path: impl AsRef<Path>,
+    sample_rate: u32,
+    channels: u16,
+    samples: impl IntoIterator<Item = &'a Sample>,
+) -> Result<()> {
+    let mut writer = hound::WavWriter::create(
+        path,
+        hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        },
+    )?;
+    for sample in samples {
+        for channel in 0..channels as usize {
+            writer.write_sample(sample.get(channel).copied().unwrap_or(0.0) as f32)?;
+        }
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+/// Encode samples as a 16-bit PCM WAV file in memory, the format players
+/// most widely support.  Samples are fit to `channels` as [`write`] does,
+/// and values are clipped to the range of -1 to 1.
+pub fn encode<'a>(
+    sample_rate: u32,
+    channels: u16,
+    samples: impl IntoIterator<Item = &'a Sample>,
+) -> Result<Vec<u8>> {
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(
+        &mut bytes,
+        hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        },
+    )?;
+    for sample in samples {
+        for channel in 0..channels as usize {
+            let value = sample.get(channel).copied().unwrap_or(0.0).clamp(-1.0, 1.0);
+            writer.write_sample((value * i16::MAX as f64).round() as i16)?;
+        }
+    }
+    writer.finalize()?;
+    Ok(bytes.into_inner())
+}
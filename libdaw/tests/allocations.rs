@@ -0,0 +1,350 @@
+//! Checks that the built-in nodes never allocate in `process` once they have
+//! warmed up, which is what makes them safe to run on a real-time thread.
+//!
+//! This is its own test binary because it replaces the global allocator.
+
+use libdaw::{
+    nodes::{
+        channels::{Matrix, Mono, Pan, PanLaw, StereoWidth, Upmix},
+        dynamics::{Compressor, Gate, Limiter},
+        envelope::{Offset, Point},
+        filters::{
+            butterworth::{self, BandPass},
+            chebyshev, MovingAverage,
+        },
+        instrument::Tone,
+        lfo::{Rate, Shape},
+        modulation::{Chorus, Flanger, Phaser},
+        oscillators::{Additive, Partial, Sawtooth, Sine, Square, Triangle},
+        waveshaper::Curve,
+        Add, Bitcrusher, ConstantValue, Convolution, Delay, Detune, Envelope, Explode,
+        FeedbackDelay, Gain, Granular, Graph, Implode, Instrument, KarplusStrong, Lfo, Mixer,
+        Multiply, Oversample, Passthrough, Resampler, Reverb, Tap, Waveshaper,
+    },
+    realtime::{self, controlled, Parameter},
+    time::{Duration, Timestamp},
+    Node, Sample,
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::{Arc, Mutex},
+};
+
+const SAMPLE_RATE: u32 = 48000;
+
+/// Counts the allocations made on threads that are currently counting.
+struct CountingAllocator;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count() {
+    let _ = COUNTING.try_with(|counting| {
+        if counting.get() {
+            ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        }
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        count();
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Two stereo streams: a tone, and a slow square wave for triggers and
+/// sidechains.
+fn inputs(sample: usize) -> [Sample; 2] {
+    let time = sample as f64 / SAMPLE_RATE as f64;
+    let tone = (time * 220.0 * std::f64::consts::TAU).sin() * 0.9;
+    let trigger = if (time * 4.0).fract() < 0.5 {
+        1.0
+    } else {
+        -1.0
+    };
+    [[tone, -tone].into(), [trigger, trigger * 0.5].into()]
+}
+
+/// Run the node for a second to fill its buffers, then count the
+/// allocations over the following second.
+fn allocations(node: &mut dyn Node) -> usize {
+    let mut outputs = Vec::new();
+    let mut sample = 0;
+    let mut run = |node: &mut dyn Node, counting: bool| {
+        for _ in 0..SAMPLE_RATE {
+            let inputs = inputs(sample);
+            sample += 1;
+            outputs.clear();
+            COUNTING.with(|cell| cell.set(counting));
+            node.process(&inputs, &mut outputs).unwrap();
+            COUNTING.with(|cell| cell.set(false));
+        }
+    };
+    run(node, false);
+    ALLOCATIONS.with(|cell| cell.set(0));
+    run(node, true);
+    ALLOCATIONS.with(|cell| cell.get())
+}
+
+fn shared(node: impl Node + 'static) -> Arc<Mutex<dyn Node>> {
+    Arc::new(Mutex::new(node))
+}
+
+fn seconds(seconds: f64) -> Duration {
+    Duration::from_seconds(seconds).unwrap()
+}
+
+fn nodes() -> Vec<(&'static str, Box<dyn Node>)> {
+    let envelope = || {
+        Envelope::new(
+            SAMPLE_RATE,
+            seconds(0.5),
+            [
+                Point {
+                    offset: Offset::Ratio(0.0),
+                    whence: 0.0,
+                    volume: 0.0,
+                },
+                Point {
+                    offset: Offset::Ratio(0.1),
+                    whence: 0.0,
+                    volume: 1.0,
+                },
+                Point {
+                    offset: Offset::Ratio(0.0),
+                    whence: 1.0,
+                    volume: 0.0,
+                },
+            ],
+        )
+    };
+    let impulse_response: Vec<Sample> = (0..2048)
+        .map(|index| {
+            let decay = (-(index as f64) / 300.0).exp();
+            [decay, -decay].into()
+        })
+        .collect();
+    let mut graph = Graph::default();
+    let gain = shared(Gain::new(0.5));
+    let filter = shared(butterworth::LowPass::new(SAMPLE_RATE, 4, 1000.0).unwrap());
+    graph.input(gain.clone(), Some(0)).unwrap();
+    graph.connect(gain.clone(), filter.clone(), None).unwrap();
+    graph.output(gain, None).unwrap();
+    graph.output(filter, None).unwrap();
+    let mut mixer = Mixer::default();
+    mixer.add_channel("lead").unwrap();
+    mixer.add_channel("bass").unwrap();
+    mixer.add_bus("reverb").unwrap();
+    mixer.send("lead", "reverb", 0.5, false).unwrap();
+    mixer
+        .bus("reverb")
+        .unwrap()
+        .lock()
+        .unwrap()
+        .inserts
+        .push(shared(Reverb::new(SAMPLE_RATE)));
+    // A new tone every quarter second, each lasting half a second, for
+    // longer than the node is run.
+    let mut instrument = Instrument::new(SAMPLE_RATE, |_| Ok(shared(Sine::new(SAMPLE_RATE, 0.0))));
+    for index in 0..12 {
+        instrument.add_tone(Tone {
+            start: Timestamp::from_seconds(index as f64 * 0.25).unwrap(),
+            length: seconds(0.5),
+            frequency: 220.0 + index as f64 * 20.0,
+        });
+    }
+    let mut filtered_delay = FeedbackDelay::new(SAMPLE_RATE, seconds(1.0), seconds(0.25));
+    filtered_delay.feedback = 0.5;
+    filtered_delay.filter = Some(shared(
+        butterworth::LowPass::new(SAMPLE_RATE, 2, 2000.0).unwrap(),
+    ));
+    vec![
+        ("Add", Box::new(Add::default())),
+        ("Bitcrusher", Box::new(Bitcrusher::new(SAMPLE_RATE))),
+        ("ConstantValue", Box::new(ConstantValue::new(0.5))),
+        (
+            "Convolution",
+            Box::new(Convolution::new(&impulse_response, 256).unwrap()),
+        ),
+        ("Delay", Box::new(Delay::new(SAMPLE_RATE, seconds(0.1)))),
+        ("Detune", Box::new(Detune::new())),
+        ("Envelope", Box::new(envelope())),
+        ("Explode", Box::new(Explode::default())),
+        (
+            "FeedbackDelay",
+            Box::new(FeedbackDelay::new(SAMPLE_RATE, seconds(1.0), seconds(0.25))),
+        ),
+        ("FeedbackDelay with a filter", Box::new(filtered_delay)),
+        ("Gain", Box::new(Gain::new(0.5))),
+        (
+            "Granular",
+            Box::new(Granular::capture(SAMPLE_RATE, seconds(0.5), 2)),
+        ),
+        ("Graph", Box::new(graph)),
+        ("Implode", Box::new(Implode::default())),
+        ("Instrument", Box::new(instrument)),
+        (
+            "KarplusStrong",
+            Box::new(KarplusStrong::new(SAMPLE_RATE, 110.0)),
+        ),
+        (
+            "Lfo",
+            Box::new(Lfo::new(SAMPLE_RATE, Shape::Triangle, Rate::Hertz(3.0))),
+        ),
+        ("Mixer", Box::new(mixer)),
+        ("Multiply", Box::new(Multiply::default())),
+        (
+            "Oversample",
+            Box::new(Oversample::new(4, shared(Waveshaper::new(Curve::Tanh, 1)))),
+        ),
+        ("Passthrough", Box::new(Passthrough::default())),
+        (
+            "Resampler",
+            Box::new(Resampler::new(
+                SAMPLE_RATE,
+                44100,
+                shared(Sine::new(44100, 440.0)),
+            )),
+        ),
+        ("Reverb", Box::new(Reverb::new(SAMPLE_RATE))),
+        // Recording into memory, with room for both seconds it is run.
+        (
+            "Tap",
+            Box::new(Tap::with_capacity(SAMPLE_RATE as usize * 2)),
+        ),
+        ("Waveshaper", Box::new(Waveshaper::new(Curve::Tanh, 4))),
+        ("Matrix", Box::new(Matrix::identity(2))),
+        ("Mono", Box::new(Mono::default())),
+        ("Pan", Box::new(Pan::new(0.25, PanLaw::ConstantPower))),
+        ("StereoWidth", Box::new(StereoWidth::new(1.5, 0.0))),
+        ("Upmix", Box::new(Upmix::new(2))),
+        ("Compressor", Box::new(Compressor::new(SAMPLE_RATE))),
+        ("Gate", Box::new(Gate::new(SAMPLE_RATE))),
+        (
+            "Limiter",
+            Box::new(Limiter::new(SAMPLE_RATE, seconds(0.005))),
+        ),
+        (
+            "butterworth::BandPass",
+            Box::new(BandPass::new(SAMPLE_RATE, 4, 500.0, 2000.0).unwrap()),
+        ),
+        (
+            "chebyshev::HighPass",
+            Box::new(chebyshev::HighPass::new(SAMPLE_RATE, 4, 0.5, 500.0).unwrap()),
+        ),
+        (
+            "MovingAverage",
+            Box::new(MovingAverage::new(SAMPLE_RATE, seconds(0.01))),
+        ),
+        ("Chorus", Box::new(Chorus::new(SAMPLE_RATE))),
+        ("Flanger", Box::new(Flanger::new(SAMPLE_RATE))),
+        ("Phaser", Box::new(Phaser::new(SAMPLE_RATE, 4))),
+        (
+            "Additive",
+            Box::new(Additive::new(
+                SAMPLE_RATE,
+                110.0,
+                [
+                    Partial::new(1.0, 1.0),
+                    Partial {
+                        envelope: Some(envelope()),
+                        ..Partial::new(2.0, 0.5)
+                    },
+                ],
+            )),
+        ),
+        ("Sawtooth", Box::new(Sawtooth::new(SAMPLE_RATE, 110.0))),
+        ("Sine", Box::new(Sine::new(SAMPLE_RATE, 110.0))),
+        ("Square", Box::new(Square::new(SAMPLE_RATE, 110.0))),
+        ("Triangle", Box::new(Triangle::new(SAMPLE_RATE, 110.0))),
+    ]
+}
+
+#[test]
+fn built_in_nodes_do_not_allocate() {
+    let allocating: Vec<_> = nodes()
+        .into_iter()
+        .map(|(name, mut node)| (name, allocations(&mut *node)))
+        .filter(|&(_, allocations)| allocations > 0)
+        .collect();
+    assert_eq!(allocating, []);
+}
+
+#[test]
+fn controlled_changes_do_not_allocate() {
+    let (mut controller, mut node) = controlled(Gain::new(1.0), 4);
+    let parameter = Parameter::new(1.0);
+    let mut outputs = Vec::with_capacity(1);
+    for step in 0..1000 {
+        let gain = step as f64;
+        controller.send(move |node| node.gain = gain).unwrap();
+        parameter.set(gain);
+        outputs.clear();
+        ALLOCATIONS.with(|cell| cell.set(0));
+        COUNTING.with(|cell| cell.set(true));
+        node.process(&[Sample::from(parameter.get())], &mut outputs)
+            .unwrap();
+        COUNTING.with(|cell| cell.set(false));
+        assert_eq!(ALLOCATIONS.with(|cell| cell.get()), 0);
+        assert_eq!(outputs[0][0], gain * gain);
+    }
+}
+
+#[test]
+fn realtime_graph_changes_do_not_allocate() {
+    let (mut controller, mut graph) = realtime::graph(4, 8);
+    let level = Parameter::new(0.0);
+    let parameter = controller.insert(level.clone()).unwrap();
+    let multiply = controller.insert(Multiply::default()).unwrap();
+    controller.input(multiply, Some(0)).unwrap();
+    controller.connect(parameter, multiply, None).unwrap();
+    let mut gain = controller.insert(Gain::new(2.0)).unwrap();
+    let mut outputs = Vec::with_capacity(1);
+    // Run through every change once to fill the buffers, then again
+    // counting.
+    for counting in [false, true] {
+        for step in 0..1000 {
+            match step % 4 {
+                0 => controller.connect(multiply, gain, None).unwrap(),
+                1 => controller.output(gain, None).unwrap(),
+                2 => assert!(controller.remove_output(gain, None).unwrap()),
+                _ => {
+                    assert!(controller.remove(gain).unwrap());
+                    gain = controller.insert(Gain::new(2.0)).unwrap();
+                }
+            }
+            level.set(step as f64);
+            outputs.clear();
+            ALLOCATIONS.with(|cell| cell.set(0));
+            COUNTING.with(|cell| cell.set(counting));
+            graph.process(&[Sample::from(0.5)], &mut outputs).unwrap();
+            COUNTING.with(|cell| cell.set(false));
+            assert_eq!(ALLOCATIONS.with(|cell| cell.get()), 0);
+            let expected: &[f64] = if step % 4 == 1 { &[step as f64] } else { &[] };
+            let outputs: Vec<f64> = outputs.iter().map(|sample| sample[0]).collect();
+            assert_eq!(outputs, expected);
+        }
+    }
+}
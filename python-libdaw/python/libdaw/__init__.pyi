@@ -1,5 +1,10 @@
-from collections.abc import Sequence
-from libdaw.time import Duration
+from collections.abc import Buffer, Mapping, Sequence
+from typing import Any
+import numpy
+from os import PathLike
+from libdaw.metronome import Beat, Metronome
+from libdaw.nodes import Graph
+from libdaw.time import Duration, Timestamp
 
 class Sample:
     def __new__(cls: type, channels: Sequence[float]):
@@ -13,8 +18,159 @@ class Sample:
     def __iadd__(self, other: Sample) -> Sample: ...
     def __mul__(self, other: Sample) -> Sample: ...
     def __imul__(self, other: Sample) -> Sample: ...
+    def __array__(self, dtype: Any = None, copy: bool | None = None) -> numpy.ndarray:
+        '''Convert to a one-dimensional NumPy array of the channels.
+        '''
+
+class Audio:
+    '''Rendered audio, which displays in a Jupyter notebook as an audio
+    player with an optional waveform and spectrogram.
+    '''
+    def __new__(cls: type, node: Node, duration: Duration, sample_rate: int = 48000, channels: int = 2, waveform: bool = True, spectrogram: bool = False):
+        '''Render a node offline for a duration, preparing it for the given
+        format as `play` does.
+        '''
+    @property
+    def sample_rate(self) -> int: ...
+    @property
+    def channels(self) -> int: ...
+    @property
+    def duration(self) -> Duration: ...
+    @property
+    def wav(self) -> bytes:
+        '''The audio as a 16-bit WAV file.
+        '''
+    def __len__(self) -> int: ...
+    def _repr_html_(self) -> str:
+        '''An HTML5 audio player with the audio embedded, for IPython's rich
+        display.
+        '''
+
+class Context:
+    '''The processing context a node tree is run under.
+    '''
+    def __new__(cls: type, sample_rate: int = 48000, max_block_size: int = 1024, channels: int = 2): ...
+    @property
+    def sample_rate(self) -> int: ...
+    @sample_rate.setter
+    def sample_rate(self, value: int) -> None: ...
+    @property
+    def max_block_size(self) -> int: ...
+    @max_block_size.setter
+    def max_block_size(self, value: int) -> None: ...
+    @property
+    def channels(self) -> int: ...
+    @channels.setter
+    def channels(self, value: int) -> None: ...
 
 class Node:
+    '''The base class of all nodes.
+
+    Built-in nodes can be pickled and copied with `copy.deepcopy`.  This
+    keeps only their configuration, not their processing state: phases,
+    filter memory, delay buffers, envelope positions, and the like are all
+    lost, so a restored node is as if it had just been reset.  The one
+    exception is an Instrument, which seeks back to the sample it had
+    reached.  Any Python callables a node holds must be picklable
+    themselves.
+    '''
     def process(self, inputs: Sequence[Sample]) -> Sequence[Sample]: ...
+    def render(self, frames: int | None = None, inputs: Sequence[Buffer] = (), stream: int | None = None) -> numpy.ndarray:
+        '''Process many frames at once, returning a NumPy array of frames by
+        channels.
+
+        Each input is an input stream given as a float64 array, either
+        one-dimensional for a single channel or frames by channels, read
+        through the buffer protocol.  `frames` defaults to the length of the
+        shortest input.  The output is the sum of all output streams, or
+        only the given stream, padded to the widest frame.  Other Python
+        threads keep running during the render, and Ctrl-C interrupts it.
+        '''
+    def prepare(self, context: Context) -> None:
+        '''Prepare the node, and any nodes it contains, to be processed under
+        the given context.
+        '''
+    def reset(self) -> None:
+        '''Return the node, and any nodes it contains, to its initial state.
+        '''
+    def seek(self, sample: int) -> None:
+        '''Move the node, and any nodes it contains, to the given sample, so
+        the next processed sample is that one.
+        '''
 
-def play(node: Node, sample_rate: int = 48000, channels: int = 1, duration: Duration | None = None, grace_sleep: bool = True) -> None: ...
+def play(node: Node, sample_rate: int = 48000, channels: int = 1, duration: Duration | None = None, grace_sleep: bool = True, input_channels: int | None = None, record: str | PathLike | None = None) -> None:
+    '''Play a node to the default speakers of the system.
+
+    The node is prepared for the given sample rate and channel count before
+    playing.  With `input_channels`, the default input device is captured
+    and fed to the node as its input stream, so effects can be played live.
+    With `record`, what is played is also written to a WAV file.
+    '''
+
+def render_stems(graph: Graph, directory: str | PathLike, stems: Mapping[str, Node], duration: Duration, sample_rate: int = 48000, channels: int = 2, master: str | None = 'master') -> None:
+    '''Render a graph in a single pass, writing its output and the output of
+    each named node within it to its own WAV file in `directory`.
+
+    Every file is named after its stem with `.wav` appended, and the graph's
+    own output is written as `master`, unless `master` is None.  A ValueError
+    is raised before any file is created if two would share a path, as a
+    stem named like `master` would.  Each stem is the node's
+    output as it was processed within the graph, so it has to lead to the
+    graph's output.  The files are sample-aligned.  Other Python threads
+    keep running during the render, and Ctrl-C interrupts it.
+    '''
+
+class Transport:
+    '''A non-blocking transport for real-time playback.
+
+    Audio is rendered from the node to the system's speakers on a background
+    thread that does not hold the GIL, while playback is controlled from
+    Python.  The node is prepared for the transport's sample rate and channel
+    count.  Rendering runs a few blocks ahead of the speakers, so controls
+    take effect after a short delay.
+    '''
+    def __new__(cls: type, node: Node, sample_rate: int = 48000, channels: int = 2, metronome: Metronome = Metronome()): ...
+    def start(self) -> None:
+        '''Start or resume playback, opening the audio output if needed.
+        '''
+    def pause(self) -> None:
+        '''Pause playback, keeping the position.
+        '''
+    def stop(self) -> None:
+        '''Stop playback, rewind to the start, and close the audio output.
+        '''
+    def seek(self, position: Timestamp | Beat) -> None:
+        '''Move playback to a timestamp or beat.
+        '''
+    def loop(self, start: Timestamp | Beat, end: Timestamp | Beat) -> None:
+        '''Loop playback between two positions.
+        '''
+    def clear_loop(self) -> None:
+        '''Stop looping, letting playback continue past the loop end.
+        '''
+    @property
+    def loop_region(self) -> tuple[Timestamp, Timestamp] | None: ...
+    @property
+    def playing(self) -> bool: ...
+    @property
+    def sample(self) -> int:
+        '''The current position, in samples.
+        '''
+    @property
+    def position(self) -> Timestamp: ...
+    @property
+    def beat(self) -> Beat: ...
+    @property
+    def sample_rate(self) -> int: ...
+    @property
+    def channels(self) -> int: ...
+    @property
+    def metronome(self) -> Metronome: ...
+    @metronome.setter
+    def metronome(self, value: Metronome) -> None: ...
+    @property
+    def node(self) -> Node: ...
+    @node.setter
+    def node(self, value: Node) -> None:
+        '''Swap the root node, which picks up from the current position.
+        '''
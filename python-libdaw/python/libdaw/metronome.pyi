@@ -1,5 +1,5 @@
 from typing import Any
-from python.libdaw.time import Timestamp
+from python.libdaw.time import Duration, Timestamp
 
 class Beat:
     def __new__(cls: type, value: float): ...
@@ -42,3 +42,5 @@ class Metronome:
     def __new__(cls: type): ...
     def add_tempo_instruction(self, instruction: TempoInstruction) -> None: ...
     def beat_to_time(self, beat: Beat) -> Timestamp: ...
+    def time_to_beat(self, time: Timestamp) -> Beat: ...
+    def beats_to_duration(self, beat: Beat, length: Beat) -> Duration: ...
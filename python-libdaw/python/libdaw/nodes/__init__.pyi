@@ -1,12 +1,41 @@
-from collections.abc import Callable, Sequence
+from collections.abc import Buffer, Callable, Sequence
+import numpy
+from os import PathLike
 from libdaw import Node, Sample
+from libdaw.metronome import Beat, Metronome
 from python.libdaw.time import Duration, Timestamp
+from .delay_line import Interpolation
 from .envelope import Point
+from .granular import Window
 from .instrument import Tone
+from .lfo import Shape
+from .waveshaper import Curve
 
 class Add(Node):
     def __new__(cls: type): ...
 
+class Bitcrusher(Node):
+    '''Reduces the bit depth and sample rate of each input stream.
+
+    The rate defaults to a quarter of the sample rate.
+    '''
+    def __new__(
+        cls: type,
+        bits: float = 8.0,
+        rate: float | None = None,
+        sample_rate: int = 48000,
+    ): ...
+
+    @property
+    def bits(self) -> float: ...
+    @bits.setter
+    def bits(self, value: float) -> None: ...
+
+    @property
+    def rate(self) -> float: ...
+    @rate.setter
+    def rate(self, value: float) -> None: ...
+
 class Callback(Node):
     def __new__(cls: type, node: Node, sample_rate: int = 48000): ...
     def add(
@@ -20,6 +49,20 @@ class Callback(Node):
 class ConstantValue(Node):
     def __new__(cls: type, value: float): ...
 
+class Convolution(Node):
+    '''Convolves its input with an impulse response, one Sample per frame.
+
+    A 1-channel impulse response is applied to every input channel, a
+    2-channel one is applied per side, and a 4-channel one is true stereo,
+    ordered as left to left, left to right, right to left, right to right.
+    Long impulse responses are delayed by one block.
+    '''
+    def __new__(cls: type, impulse_response: Sequence[Sample], block_size: int = 256): ...
+    @staticmethod
+    def from_wav(path: str | PathLike, block_size: int = 256, sample_rate: int = 48000) -> Convolution: ...
+    @property
+    def latency(self) -> int: ...
+
 class Custom(Node):
     '''A custom Node.
 
@@ -42,6 +85,37 @@ class Custom(Node):
     @callable.setter
     def callable(self, value: Callable[[Sequence[Sample]], Sequence[Sample]]): ...
 
+class CustomBlock(Node):
+    '''A custom Node that processes blocks of frames at once.
+
+    Input frames are gathered into blocks of `block_size` frames, and the
+    callable is called once per block with a list of NumPy arrays, one for
+    each input stream, of frames by channels.  It returns an array, or a
+    sequence of them, one for each output stream, each with `block_size`
+    frames.  The output is played back over the next block, so it lags the
+    input by `block_size` frames, and there are no output streams during the
+    first block.
+
+    As with Custom, you can pass the callable into this, assign it to its
+    `callable` property, or subclass this with a callable.  If you subclass
+    this, you **must** call super().__init__()
+    '''
+    def __new__(cls: type, callable: Callable[[list[numpy.ndarray]], Buffer | Sequence[Buffer]] | None = None, block_size: int = 256): ...
+    def __init__(self, callable: Callable[[list[numpy.ndarray]], Buffer | Sequence[Buffer]] | None = None, block_size: int = 256): ...
+
+    @property
+    def callable(self) -> Callable[[list[numpy.ndarray]], Buffer | Sequence[Buffer]]:
+        '''Returns the callable.
+
+        Will return self if the callable is self, such as for a subclass.
+        '''
+
+    @callable.setter
+    def callable(self, value: Callable[[list[numpy.ndarray]], Buffer | Sequence[Buffer]]): ...
+
+    @property
+    def block_size(self) -> int: ...
+
 class Delay(Node):
     def __new__(cls: type, delay: Duration, sample_rate: int = 48000): ...
 
@@ -60,6 +134,51 @@ class Envelope(Node):
 class Explode(Node):
     pass
 
+class FeedbackDelay(Node):
+    '''A delay line with feedback and a dry/wet mix.
+
+    The first input stream is the audio to be delayed.  The second input
+    stream, if present, overrides the delay time in seconds, and may be
+    changed smoothly for modulation.  The filter node, if given, is run on
+    the feedback path.
+    '''
+    def __new__(
+        cls: type,
+        max_delay: Duration,
+        delay: Duration,
+        feedback: float = 0.0,
+        mix: float = 0.5,
+        interpolation: Interpolation = Interpolation.Linear,
+        filter: Node | None = None,
+        sample_rate: int = 48000,
+    ): ...
+    def sync(self, length: Beat, beat: Beat = Beat(0), metronome: Metronome = Metronome()) -> None: ...
+
+    @property
+    def delay(self) -> Duration: ...
+    @delay.setter
+    def delay(self, value: Duration) -> None: ...
+
+    @property
+    def feedback(self) -> float: ...
+    @feedback.setter
+    def feedback(self, value: float) -> None: ...
+
+    @property
+    def mix(self) -> float: ...
+    @mix.setter
+    def mix(self, value: float) -> None: ...
+
+    @property
+    def interpolation(self) -> Interpolation: ...
+    @interpolation.setter
+    def interpolation(self, value: Interpolation) -> None: ...
+
+    @property
+    def filter(self) -> Node | None: ...
+    @filter.setter
+    def filter(self, value: Node | None) -> None: ...
+
 class Gain(Node):
     def __new__(cls: type, gain: float): ...
 
@@ -69,7 +188,68 @@ class Gain(Node):
     @gain.setter
     def gain(self, value: float) -> None: ...
 
+class Granular(Node):
+    '''A granular engine, spawning grains from an in-memory buffer.
+
+    Input streams 0 through 4 control the density, grain size (in seconds),
+    position, jitter, and pitch respectively, using the first channel of
+    each.  Stream 5 is captured into the buffer while recording.  Missing
+    streams fall back to the properties.
+    '''
+    def __new__(cls: type, buffer: Sequence[Sample], buffer_sample_rate: int | None = None, sample_rate: int = 48000): ...
+    @staticmethod
+    def from_wav(path: str | PathLike, sample_rate: int = 48000) -> Granular: ...
+    @staticmethod
+    def capture(length: Duration, channels: int = 1, sample_rate: int = 48000) -> Granular: ...
+    def seed(self, seed: int) -> None: ...
+
+    @property
+    def buffer(self) -> list[Sample]: ...
+
+    @property
+    def density(self) -> float: ...
+    @density.setter
+    def density(self, value: float) -> None: ...
+
+    @property
+    def grain_size(self) -> Duration: ...
+    @grain_size.setter
+    def grain_size(self, value: Duration) -> None: ...
+
+    @property
+    def position(self) -> float: ...
+    @position.setter
+    def position(self, value: float) -> None: ...
+
+    @property
+    def jitter(self) -> float: ...
+    @jitter.setter
+    def jitter(self, value: float) -> None: ...
+
+    @property
+    def pitch(self) -> float: ...
+    @pitch.setter
+    def pitch(self, value: float) -> None: ...
+
+    @property
+    def window(self) -> Window: ...
+    @window.setter
+    def window(self, value: Window) -> None: ...
+
+    @property
+    def recording(self) -> bool: ...
+    @recording.setter
+    def recording(self, value: bool) -> None: ...
+
 class Graph(Node):
+    '''A graph of nodes.
+
+    Once the graph has been prepared, nodes connected to it are prepared with
+    the same context as they are added, so patching while playing is safe.
+
+    Pickling keeps the topology, and pickles every node in the graph along
+    with it.
+    '''
     def remove(self, node: Node) -> bool: ...
     def connect(self, source: Node, destination: Node, stream: int | None = None) -> None: ...
     def disconnect(self, source: Node, destination: Node, stream: int | None = None) -> bool: ...
@@ -82,11 +262,233 @@ class Implode(Node):
     pass
 
 class Instrument(Node):
+    '''Plays tones, each on a voice built by the factory.
+
+    The factory is called on a thread of its own shortly before each tone
+    starts, and the voice is freed after the tone ends.  Each voice is given
+    its tone's frequency as its first input stream.
+    '''
     def __new__(cls: type, factory: Callable[[Tone], Node], sample_rate: int = 48000): ...
     def add_tone(self, tone: Tone) -> None: ...
 
+class KarplusStrong(Node):
+    '''An extended Karplus-Strong plucked string.
+
+    The first input stream is the frequency, and the second is an excitation
+    trigger, which plucks the string whenever it rises above zero.  Without a
+    trigger stream, the string is plucked on the first sample, so this can be
+    returned directly from an Instrument factory.
+    '''
+    def __new__(
+        cls: type,
+        frequency: float = 0.0,
+        stretch: float = 0.5,
+        loss: float = 0.996,
+        pick_position: float = 0.0,
+        seed: int | None = None,
+        sample_rate: int = 48000,
+    ): ...
+    def pluck(self) -> None: ...
+
+    @property
+    def frequency(self) -> float: ...
+    @frequency.setter
+    def frequency(self, value: float) -> None: ...
+
+    @property
+    def stretch(self) -> float: ...
+    @stretch.setter
+    def stretch(self, value: float) -> None: ...
+
+    @property
+    def loss(self) -> float: ...
+    @loss.setter
+    def loss(self, value: float) -> None: ...
+
+    @property
+    def pick_position(self) -> float: ...
+    @pick_position.setter
+    def pick_position(self, value: float) -> None: ...
+
+class Lfo(Node):
+    '''A low frequency oscillator for control signals.
+
+    The first input stream, if present, overrides the rate in Hz.  The second
+    input stream, if present, is a gate which restarts the cycle each time it
+    rises above zero.
+    '''
+    def __new__(
+        cls: type,
+        shape: Shape = Shape.Sine,
+        frequency: float = 1.0,
+        phase: float = 0.0,
+        unipolar: bool = False,
+        sample_rate: int = 48000,
+    ): ...
+    def sync(self, length: Beat, metronome: Metronome = Metronome()) -> None: ...
+    def retrigger(self) -> None: ...
+    def seed(self, seed: int) -> None: ...
+
+    @property
+    def frequency(self) -> float | None: ...
+    @frequency.setter
+    def frequency(self, value: float) -> None: ...
+
+    @property
+    def beats(self) -> Beat | None: ...
+
+    @property
+    def shape(self) -> Shape: ...
+    @shape.setter
+    def shape(self, value: Shape) -> None: ...
+
+    @property
+    def phase(self) -> float: ...
+    @phase.setter
+    def phase(self, value: float) -> None: ...
+
+    @property
+    def unipolar(self) -> bool: ...
+    @unipolar.setter
+    def unipolar(self, value: bool) -> None: ...
+
 class Multiply(Node):
     def __new__(cls: type): ...
 
+class Oversample(Node):
+    '''Runs a node, often a Graph, at an integer multiple of the outer
+    sample rate, with anti-aliasing filters on every input and output stream.
+
+    The node must be constructed with the oversampled rate.
+    '''
+    def __new__(cls: type, node: Node, factor: int = 2): ...
+    @property
+    def factor(self) -> int: ...
+    @property
+    def node(self) -> Node: ...
+
 class Passthrough(Node):
     pass
+
+class Resampler(Node):
+    '''Runs a node at a different sample rate, converting its output streams
+    with band-limited windowed-sinc interpolation.
+
+    The resampler's input streams are passed to the node unchanged each time
+    it is processed.
+    '''
+    def __new__(cls: type, node: Node, node_sample_rate: int, sample_rate: int = 48000): ...
+    @property
+    def node(self) -> Node: ...
+
+class Reverb(Node):
+    '''A Schroeder-Moorer style algorithmic reverb, after Freeverb.
+
+    All channels of the input stream are summed into the reverb, and the
+    output is always two channels.  A mono input is sent dry to both output
+    channels.  The pre-delay may be up to one second.
+    '''
+    def __new__(
+        cls: type,
+        room_size: float = 0.5,
+        damping: float = 0.5,
+        pre_delay: Duration = Duration(0),
+        width: float = 1.0,
+        wet: float = 1.0 / 3.0,
+        dry: float = 1.0,
+        sample_rate: int = 48000,
+    ): ...
+
+    @property
+    def room_size(self) -> float: ...
+    @room_size.setter
+    def room_size(self, value: float) -> None: ...
+
+    @property
+    def damping(self) -> float: ...
+    @damping.setter
+    def damping(self, value: float) -> None: ...
+
+    @property
+    def pre_delay(self) -> Duration: ...
+    @pre_delay.setter
+    def pre_delay(self, value: Duration) -> None: ...
+
+    @property
+    def width(self) -> float: ...
+    @width.setter
+    def width(self, value: float) -> None: ...
+
+    @property
+    def wet(self) -> float: ...
+    @wet.setter
+    def wet(self, value: float) -> None: ...
+
+    @property
+    def dry(self) -> float: ...
+    @dry.setter
+    def dry(self, value: float) -> None: ...
+
+class Tap(Node):
+    '''Passes its input through unchanged, while recording it into memory or
+    to a WAV file whenever it is armed.
+
+    The recording is the sum of all input streams.  Without a path, it is
+    kept in memory.  With one, it is streamed to a 32-bit float WAV file,
+    which is created when the tap is prepared, at the context's sample rate,
+    and complete once the tap is finished.  The tap starts armed.  Once
+    finished, it records nothing more, and arming it raises an error.
+
+    Only a tap recording into memory can be pickled, which keeps the audio
+    captured so far.
+    '''
+    def __new__(cls: type, path: str | PathLike | None = None, channels: int = 2): ...
+    def arm(self) -> None:
+        '''Arm the tap, which fails once it is finished.
+        '''
+    def disarm(self) -> None: ...
+    def take(self) -> list[Sample]:
+        '''Take the audio recorded into memory, leaving the tap empty.
+        '''
+    def finish(self) -> None:
+        '''Disarm the tap for good and complete its WAV file, if it has one.
+        '''
+
+    @property
+    def captured(self) -> list[Sample]:
+        '''The audio recorded into memory so far.
+        '''
+
+    @property
+    def armed(self) -> bool: ...
+    @armed.setter
+    def armed(self, value: bool) -> None: ...
+    @property
+    def finished(self) -> bool: ...
+
+class Waveshaper(Node):
+    '''Distorts each input stream through a transfer function, optionally
+    oversampled to reduce aliasing.
+
+    The curve may be a built-in Curve or any callable taking and returning a
+    float.
+    '''
+    def __new__(
+        cls: type,
+        curve: Curve | Callable[[float], float] = Curve.tanh(),
+        drive: float = 1.0,
+        oversampling: int = 1,
+    ): ...
+
+    @property
+    def curve(self) -> Curve | Callable[[float], float]: ...
+    @curve.setter
+    def curve(self, value: Curve | Callable[[float], float]) -> None: ...
+
+    @property
+    def drive(self) -> float: ...
+    @drive.setter
+    def drive(self, value: float) -> None: ...
+
+    @property
+    def oversampling(self) -> int: ...
@@ -0,0 +1,83 @@
+from enum import Enum, auto
+from libdaw import Node
+
+class PanLaw(Enum):
+    Linear = auto()
+    ConstantPower = auto()
+    Compromise = auto()
+
+    def gains(self, position: float) -> tuple[float, float]: ...
+
+class Matrix(Node):
+    '''Routes the concatenated channels of all input streams to output
+    channels, with one row of gains per output channel.
+    '''
+    def __new__(cls: type, gains: list[list[float]]): ...
+    @staticmethod
+    def identity(channels: int) -> Matrix: ...
+
+    @property
+    def gains(self) -> list[list[float]]: ...
+    @gains.setter
+    def gains(self, value: list[list[float]]) -> None: ...
+
+class Mono(Node):
+    '''Sums all channels of each input stream into a single channel,
+    averaging them unless told otherwise.
+    '''
+    def __new__(cls: type, average: bool = True): ...
+
+    @property
+    def average(self) -> bool: ...
+    @average.setter
+    def average(self, value: bool) -> None: ...
+
+class Pan(Node):
+    '''Pans the first input stream.
+
+    A mono input is spread into two channels.  A stereo input is balanced
+    instead, kept at unity at the center with the opposite side fading out
+    along the law.  The second input stream, if present, overrides the
+    position.
+    '''
+    def __new__(cls: type, position: float = 0.0, law: PanLaw = PanLaw.ConstantPower): ...
+
+    @property
+    def position(self) -> float: ...
+    @position.setter
+    def position(self, value: float) -> None: ...
+
+    @property
+    def law(self) -> PanLaw: ...
+    @law.setter
+    def law(self, value: PanLaw) -> None: ...
+
+class StereoWidth(Node):
+    '''Adjusts the width and balance of a stereo signal through mid-side
+    processing.
+
+    The second and third input streams, if present, override the balance and
+    width.
+    '''
+    def __new__(cls: type, width: float = 1.0, balance: float = 0.0): ...
+
+    @property
+    def width(self) -> float: ...
+    @width.setter
+    def width(self, value: float) -> None: ...
+
+    @property
+    def balance(self) -> float: ...
+    @balance.setter
+    def balance(self, value: float) -> None: ...
+
+class Upmix(Node):
+    '''Expands each input stream to a fixed number of channels, repeating the
+    input channels in order.
+    '''
+    def __new__(cls: type, channels: int = 2): ...
+
+    @property
+    def channels(self) -> int: ...
+    @channels.setter
+    def channels(self, value: int) -> None: ...
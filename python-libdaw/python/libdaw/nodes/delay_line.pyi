@@ -0,0 +1,6 @@
+from enum import Enum, auto
+
+class Interpolation(Enum):
+    Linear = auto()
+    Cubic = auto()
+    AllPass = auto()
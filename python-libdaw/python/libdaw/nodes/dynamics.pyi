@@ -0,0 +1,143 @@
+from enum import Enum, auto
+from libdaw import Node
+from libdaw.time import Duration
+
+class Detection(Enum):
+    Peak = auto()
+    Rms = auto()
+
+class Compressor(Node):
+    '''A feed-forward compressor, turning down the signal as it rises above
+    the threshold.
+
+    The first input stream is the audio.  A second input stream, if present,
+    is the sidechain, whose level is detected instead of the audio's.
+    '''
+    def __new__(
+        cls: type,
+        threshold: float = -20.0,
+        ratio: float = 4.0,
+        knee: float = 6.0,
+        attack: Duration = Duration(0.01),
+        release: Duration = Duration(0.1),
+        makeup: float = 0.0,
+        detection: Detection = Detection.Rms,
+        sample_rate: int = 48000,
+    ): ...
+    def gain(self, level: float) -> float: ...
+
+    @property
+    def threshold(self) -> float: ...
+    @threshold.setter
+    def threshold(self, value: float) -> None: ...
+
+    @property
+    def ratio(self) -> float: ...
+    @ratio.setter
+    def ratio(self, value: float) -> None: ...
+
+    @property
+    def knee(self) -> float: ...
+    @knee.setter
+    def knee(self, value: float) -> None: ...
+
+    @property
+    def attack(self) -> Duration: ...
+    @attack.setter
+    def attack(self, value: Duration) -> None: ...
+
+    @property
+    def release(self) -> Duration: ...
+    @release.setter
+    def release(self, value: Duration) -> None: ...
+
+    @property
+    def makeup(self) -> float: ...
+    @makeup.setter
+    def makeup(self, value: float) -> None: ...
+
+    @property
+    def detection(self) -> Detection: ...
+    @detection.setter
+    def detection(self, value: Detection) -> None: ...
+
+class Gate(Node):
+    '''A noise gate, or a downward expander when given a finite ratio.
+
+    The first input stream is the audio.  A second input stream, if present,
+    is the sidechain, whose level is detected instead of the audio's.
+    '''
+    def __new__(
+        cls: type,
+        threshold: float = -40.0,
+        ratio: float = float('inf'),
+        range: float = -80.0,
+        attack: Duration = Duration(0.001),
+        hold: Duration = Duration(0.01),
+        release: Duration = Duration(0.1),
+        detection: Detection = Detection.Peak,
+        sample_rate: int = 48000,
+    ): ...
+    def gain(self, level: float) -> float: ...
+
+    @property
+    def threshold(self) -> float: ...
+    @threshold.setter
+    def threshold(self, value: float) -> None: ...
+
+    @property
+    def ratio(self) -> float: ...
+    @ratio.setter
+    def ratio(self, value: float) -> None: ...
+
+    @property
+    def range(self) -> float: ...
+    @range.setter
+    def range(self, value: float) -> None: ...
+
+    @property
+    def attack(self) -> Duration: ...
+    @attack.setter
+    def attack(self, value: Duration) -> None: ...
+
+    @property
+    def hold(self) -> Duration: ...
+    @hold.setter
+    def hold(self, value: Duration) -> None: ...
+
+    @property
+    def release(self) -> Duration: ...
+    @release.setter
+    def release(self, value: Duration) -> None: ...
+
+    @property
+    def detection(self) -> Detection: ...
+    @detection.setter
+    def detection(self, value: Detection) -> None: ...
+
+class Limiter(Node):
+    '''A brickwall look-ahead limiter, delaying the audio by the look-ahead
+    so the signal never exceeds the ceiling.
+
+    The first input stream is the audio.  A second input stream, if present,
+    is the sidechain, whose level is detected instead of the audio's.
+    '''
+    def __new__(
+        cls: type,
+        lookahead: Duration = Duration(0.005),
+        ceiling: float = -1.0,
+        release: Duration = Duration(0.05),
+        sample_rate: int = 48000,
+    ): ...
+    @property
+    def latency(self) -> int: ...
+
+    @property
+    def ceiling(self) -> float: ...
+    @ceiling.setter
+    def ceiling(self, value: float) -> None: ...
+
+    @property
+    def release(self) -> Duration: ...
+    @release.setter
+    def release(self, value: Duration) -> None: ...
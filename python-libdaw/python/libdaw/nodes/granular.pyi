@@ -0,0 +1,7 @@
+from enum import Enum, auto
+
+class Window(Enum):
+    Rectangle = auto()
+    Triangle = auto()
+    Hann = auto()
+    Tukey = auto()
@@ -0,0 +1,9 @@
+from enum import Enum, auto
+
+class Shape(Enum):
+    Sine = auto()
+    Triangle = auto()
+    Saw = auto()
+    Square = auto()
+    SampleAndHold = auto()
+    SmoothRandom = auto()
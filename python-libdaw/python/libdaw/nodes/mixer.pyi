@@ -0,0 +1,78 @@
+from libdaw import Node
+from .channels import PanLaw
+
+class AuxSend:
+    '''A send from a channel strip to an auxiliary bus.'''
+    @property
+    def bus(self) -> str: ...
+    @property
+    def level(self) -> float: ...
+    @property
+    def pre_fader(self) -> bool: ...
+
+class Mixer(Node):
+    '''A mixing console with channel strips, auxiliary buses, and a master
+    bus.
+
+    Each input stream feeds the channel strip at the same index, in the order
+    the channels were added.  Every channel and bus feeds the master bus,
+    whose output is the mixer's only output stream.  When any channel is
+    soloed, all channels that are not soloed are silenced.
+
+    Pickling keeps every strip's settings, inserts, and sends.
+    '''
+    def __new__(cls: type): ...
+    def add_channel(self, name: str) -> Strip: ...
+    def remove_channel(self, name: str) -> bool: ...
+    def add_bus(self, name: str) -> Strip: ...
+    def remove_bus(self, name: str) -> bool: ...
+    def channel(self, name: str) -> Strip | None: ...
+    def bus(self, name: str) -> Strip | None: ...
+    def send(self, channel: str, bus: str, level: float = 1.0, pre_fader: bool = False) -> None: ...
+    def remove_send(self, channel: str, bus: str) -> bool: ...
+    @property
+    def master(self) -> Strip: ...
+    @property
+    def channels(self) -> list[str]: ...
+    @property
+    def buses(self) -> list[str]: ...
+
+class Strip(Node):
+    '''A mixer channel strip, used for channels, buses, and the master bus.
+
+    All input streams are summed and run through the inserts in order.  The
+    first output stream is the signal after gain and pan, followed by one
+    stream per send.
+
+    Strips belong to their mixer, so they can not be pickled or deep-copied
+    on their own, which raises a TypeError; pickle the mixer instead.
+    '''
+    def add_insert(self, node: Node) -> None: ...
+    def clear_inserts(self) -> None: ...
+    @property
+    def sends(self) -> list[AuxSend]: ...
+
+    @property
+    def gain(self) -> float: ...
+    @gain.setter
+    def gain(self, value: float) -> None: ...
+
+    @property
+    def pan(self) -> float: ...
+    @pan.setter
+    def pan(self, value: float) -> None: ...
+
+    @property
+    def law(self) -> PanLaw: ...
+    @law.setter
+    def law(self, value: PanLaw) -> None: ...
+
+    @property
+    def mute(self) -> bool: ...
+    @mute.setter
+    def mute(self, value: bool) -> None: ...
+
+    @property
+    def solo(self) -> bool: ...
+    @solo.setter
+    def solo(self, value: bool) -> None: ...
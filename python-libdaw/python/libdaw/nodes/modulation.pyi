@@ -0,0 +1,146 @@
+from libdaw import Node
+from libdaw.time import Duration
+
+class Chorus(Node):
+    '''A chorus, mixing in a copy of the signal with a slowly wavering delay.
+
+    The first input stream is the audio.  A second input stream, if present,
+    replaces the internal oscillator, and should range from -1 to 1.
+    '''
+    def __new__(
+        cls: type,
+        rate: float = 0.8,
+        delay: Duration = Duration(0.02),
+        depth: Duration = Duration(0.005),
+        feedback: float = 0.0,
+        mix: float = 0.5,
+        stereo_phase: float = 0.25,
+        sample_rate: int = 48000,
+    ): ...
+
+    @property
+    def rate(self) -> float: ...
+    @rate.setter
+    def rate(self, value: float) -> None: ...
+
+    @property
+    def delay(self) -> Duration: ...
+    @delay.setter
+    def delay(self, value: Duration) -> None: ...
+
+    @property
+    def depth(self) -> Duration: ...
+    @depth.setter
+    def depth(self, value: Duration) -> None: ...
+
+    @property
+    def feedback(self) -> float: ...
+    @feedback.setter
+    def feedback(self, value: float) -> None: ...
+
+    @property
+    def mix(self) -> float: ...
+    @mix.setter
+    def mix(self, value: float) -> None: ...
+
+    @property
+    def stereo_phase(self) -> float: ...
+    @stereo_phase.setter
+    def stereo_phase(self, value: float) -> None: ...
+
+class Flanger(Node):
+    '''A flanger, mixing in a copy of the signal with a very short sweeping
+    delay.
+
+    The first input stream is the audio.  A second input stream, if present,
+    replaces the internal oscillator, and should range from -1 to 1.
+    '''
+    def __new__(
+        cls: type,
+        rate: float = 0.25,
+        delay: Duration = Duration(0.0025),
+        depth: Duration = Duration(0.002),
+        feedback: float = 0.5,
+        mix: float = 0.5,
+        stereo_phase: float = 0.25,
+        sample_rate: int = 48000,
+    ): ...
+
+    @property
+    def rate(self) -> float: ...
+    @rate.setter
+    def rate(self, value: float) -> None: ...
+
+    @property
+    def delay(self) -> Duration: ...
+    @delay.setter
+    def delay(self, value: Duration) -> None: ...
+
+    @property
+    def depth(self) -> Duration: ...
+    @depth.setter
+    def depth(self, value: Duration) -> None: ...
+
+    @property
+    def feedback(self) -> float: ...
+    @feedback.setter
+    def feedback(self, value: float) -> None: ...
+
+    @property
+    def mix(self) -> float: ...
+    @mix.setter
+    def mix(self, value: float) -> None: ...
+
+    @property
+    def stereo_phase(self) -> float: ...
+    @stereo_phase.setter
+    def stereo_phase(self, value: float) -> None: ...
+
+class Phaser(Node):
+    '''A phaser, mixing in the signal passed through a chain of sweeping
+    all-pass filters.
+
+    The first input stream is the audio.  A second input stream, if present,
+    replaces the internal oscillator, and should range from -1 to 1.
+    '''
+    def __new__(
+        cls: type,
+        stages: int = 4,
+        rate: float = 0.5,
+        min_frequency: float = 200.0,
+        max_frequency: float = 2000.0,
+        feedback: float = 0.5,
+        mix: float = 0.5,
+        stereo_phase: float = 0.25,
+        sample_rate: int = 48000,
+    ): ...
+
+    @property
+    def rate(self) -> float: ...
+    @rate.setter
+    def rate(self, value: float) -> None: ...
+
+    @property
+    def min_frequency(self) -> float: ...
+    @min_frequency.setter
+    def min_frequency(self, value: float) -> None: ...
+
+    @property
+    def max_frequency(self) -> float: ...
+    @max_frequency.setter
+    def max_frequency(self, value: float) -> None: ...
+
+    @property
+    def feedback(self) -> float: ...
+    @feedback.setter
+    def feedback(self, value: float) -> None: ...
+
+    @property
+    def mix(self) -> float: ...
+    @mix.setter
+    def mix(self, value: float) -> None: ...
+
+    @property
+    def stereo_phase(self) -> float: ...
+    @stereo_phase.setter
+    def stereo_phase(self, value: float) -> None: ...
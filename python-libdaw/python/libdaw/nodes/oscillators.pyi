@@ -1,4 +1,31 @@
+from collections.abc import Sequence
 from libdaw import Node
+from libdaw.nodes import Envelope
+
+class Partial:
+    '''A single sine partial of an Additive oscillator.
+
+    The ratio is relative to the fundamental, and phase goes from 0 to 1 per
+    period.  The envelope is copied in, and only applies to this partial.
+    '''
+    def __new__(cls: type, ratio: float, amplitude: float = 1.0, phase: float = 0.0, envelope: Envelope | None = None): ...
+    @property
+    def ratio(self) -> float: ...
+    @property
+    def amplitude(self) -> float: ...
+    @property
+    def phase(self) -> float: ...
+
+class Additive(Node):
+    '''An additive oscillator bank, summing sine partials over a fundamental.
+
+    Partials at or above the Nyquist frequency are skipped.
+    '''
+    def __new__(cls: type, partials: Sequence[Partial], sample_rate: int = 48000, frequency: float = 0.0): ...
+    @property
+    def frequency(self) -> float: ...
+    @frequency.setter
+    def frequency(self, value: float): ...
 
 class Sawtooth(Node):
     def __new__(cls: type, sample_rate: int = 48000, frequency: float = 0.0): ...
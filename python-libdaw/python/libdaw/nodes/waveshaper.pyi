@@ -0,0 +1,13 @@
+class Curve:
+    '''A built-in waveshaper transfer function.'''
+    @staticmethod
+    def tanh() -> Curve: ...
+    @staticmethod
+    def hard_clip() -> Curve: ...
+    @staticmethod
+    def foldback() -> Curve: ...
+    @staticmethod
+    def polynomial(coefficients: list[float]) -> Curve: ...
+    @staticmethod
+    def chebyshev(amplitudes: list[float]) -> Curve: ...
+    def __call__(self, input: float) -> float: ...
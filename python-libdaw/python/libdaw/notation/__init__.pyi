@@ -1,6 +1,8 @@
 from collections import abc
 from typing import Self
 from libdaw.metronome import Beat, Metronome
+from libdaw import Audio
+from libdaw.nodes import Instrument
 from libdaw.nodes.instrument import Tone
 from libdaw.pitch import A440, PitchStandard, PitchClass
 from enum import Enum, auto, unique
@@ -38,6 +40,23 @@ class Element:
         metronome: Metronome = Metronome(),
         pitch_standard: PitchStandard = A440(),
     ) -> abc.Sequence[Tone]: ...
+    def audio(
+        self,
+        instrument: Instrument | None = None,
+        *,
+        metronome: Metronome = Metronome(),
+        pitch_standard: PitchStandard = A440(),
+        sample_rate: int = 48000,
+        channels: int = 2,
+        waveform: bool = True,
+        spectrogram: bool = False,
+    ) -> Audio:
+        '''Render the section offline on an instrument, or a plain sine voice
+        if there is none, until its last tone ends.  The tones are played on
+        a new instrument with the same factory, so the given instrument is
+        left untouched.
+        '''
+    def _repr_html_(self) -> str: ...
     def length_(self) -> Beat: ...
     def duration_(self) -> Beat: ...
 
@@ -97,6 +116,21 @@ class Item:
     @staticmethod
     def loads(source: str) -> Item: ...
 
+    def audio(
+        self,
+        instrument: Instrument | None = None,
+        *,
+        metronome: Metronome = Metronome(),
+        pitch_standard: PitchStandard = A440(),
+        sample_rate: int = 48000,
+        channels: int = 2,
+        waveform: bool = True,
+        spectrogram: bool = False,
+    ) -> Audio:
+        '''Render the item's element offline, as `Element.audio` does.
+        '''
+    def _repr_html_(self) -> str: ...
+
 class Chord(Element):
     def __new__(
         cls: type,
@@ -0,0 +1,411 @@
+use crate::{time::Duration, Node};
+use libdaw::{
+    nodes::{
+        envelope::{Offset, Point},
+        instrument::{Instrument, Tone},
+        oscillators::Sine,
+        Envelope, Graph,
+    },
+    output::{self, Format, Memory},
+    time::Time,
+    wav, Node as DawNode, Sample as DawSample,
+};
+use pyo3::{pyclass, pymethods, types::PyBytes, Bound, PyResult, Python};
+use rustfft::{num_complex::Complex, FftPlanner};
+use std::{
+    fmt::Write as _,
+    sync::{Arc, Mutex},
+};
+
+/// The width of the waveform and spectrogram images, in pixels.
+const WIDTH: usize = 800;
+
+const WAVEFORM_HEIGHT: usize = 120;
+
+/// The FFT size of the spectrogram, which gives it half as many rows.
+const WINDOW: usize = 1024;
+
+/// The quietest level shown on the spectrogram, in decibels.
+const FLOOR: f64 = -100.0;
+
+/// Rendered audio, which displays in a Jupyter notebook as an audio player
+/// with an optional waveform and spectrogram.
+#[pyclass(module = "libdaw")]
+#[derive(Debug, Clone)]
+pub struct Audio {
+    pub format: Format,
+    pub frames: Vec<DawSample>,
+    pub waveform: bool,
+    pub spectrogram: bool,
+}
+
+impl Audio {
+    /// Play a node offline into memory for `length` frames, without holding
+    /// the GIL.
+    pub fn render(
+        py: Python<'_>,
+        node: &Mutex<dyn DawNode>,
+        length: u64,
+        format: Format,
+        waveform: bool,
+        spectrogram: bool,
+    ) -> PyResult<Self> {
+        let frames = crate::render_without_gil(py, |check| {
+            let mut backend = Memory::new(format);
+            let mut node = node.lock().expect("poisoned");
+            output::play(&mut *node, &mut backend, Some(length), check)?;
+            Ok(backend.frames)
+        })?;
+        Ok(Self {
+            format,
+            frames,
+            waveform,
+            spectrogram,
+        })
+    }
+
+    /// Play tones on an instrument from the start until the last tone ends.
+    /// Without an instrument, the tones are played on a plain sine voice.
+    /// The instrument should have no tones of its own, such as one from
+    /// `nodes::Instrument::empty`.
+    pub fn play_tones(
+        py: Python<'_>,
+        tones: Vec<Tone>,
+        instrument: Option<Instrument>,
+        format: Format,
+        waveform: bool,
+        spectrogram: bool,
+    ) -> PyResult<Self> {
+        let sample_rate = format.sample_rate;
+        let mut instrument = instrument
+            .unwrap_or_else(|| Instrument::new(sample_rate, move |tone| voice(sample_rate, tone)));
+        let mut end: f64 = 0.0;
+        for tone in tones {
+            end = end.max((tone.start + tone.length).seconds());
+            instrument.add_tone(tone);
+        }
+        let length = (end * sample_rate as f64).ceil() as u64;
+        Self::render(
+            py,
+            &Mutex::new(instrument),
+            length,
+            format,
+            waveform,
+            spectrogram,
+        )
+    }
+
+    fn wav(&self) -> crate::Result<Vec<u8>> {
+        Ok(wav::encode(
+            self.format.sample_rate,
+            self.format.channels,
+            &self.frames,
+        )?)
+    }
+
+    /// Each frame mixed down to a single channel.
+    fn mono(&self) -> Vec<f64> {
+        self.frames
+            .iter()
+            .map(|frame| frame.iter().sum::<f64>() / frame.len().max(1) as f64)
+            .collect()
+    }
+}
+
+#[pymethods]
+impl Audio {
+    /// Render a node offline for a duration, preparing it for the given
+    /// format as `play` does.
+    #[new]
+    #[pyo3(signature = (node, duration, sample_rate = 48000, channels = 2, waveform = true, spectrogram = false))]
+    pub fn new(
+        py: Python<'_>,
+        node: &Bound<'_, Node>,
+        duration: Duration,
+        sample_rate: u32,
+        channels: u16,
+        waveform: bool,
+        spectrogram: bool,
+    ) -> PyResult<Self> {
+        let length = (duration.0.seconds() * sample_rate as f64) as u64;
+        let node = node.borrow().0.clone();
+        Self::render(
+            py,
+            &*node,
+            length,
+            Format {
+                sample_rate,
+                channels,
+            },
+            waveform,
+            spectrogram,
+        )
+    }
+
+    #[getter]
+    pub fn get_sample_rate(&self) -> u32 {
+        self.format.sample_rate
+    }
+
+    #[getter]
+    pub fn get_channels(&self) -> u16 {
+        self.format.channels
+    }
+
+    #[getter]
+    pub fn get_duration(&self) -> crate::Result<Duration> {
+        Ok(Duration(libdaw::time::Duration::from_seconds(
+            self.frames.len() as f64 / self.format.sample_rate as f64,
+        )?))
+    }
+
+    /// The audio as a 16-bit WAV file.
+    #[getter]
+    pub fn get_wav<'py>(&self, py: Python<'py>) -> crate::Result<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &self.wav()?))
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Audio(frames={}, sample_rate={}, channels={})",
+            self.frames.len(),
+            self.format.sample_rate,
+            self.format.channels
+        )
+    }
+
+    /// An HTML5 audio player with the audio embedded, for IPython's rich
+    /// display.
+    pub fn _repr_html_(&self) -> crate::Result<String> {
+        let mut html = String::from("<div class=\"libdaw-audio\">");
+        write!(
+            html,
+            "<audio controls src=\"data:audio/wav;base64,{}\"></audio>",
+            base64(&self.wav()?)
+        )?;
+        let mono = self.mono();
+        if self.waveform {
+            write!(html, "<div>{}</div>", waveform(&mono))?;
+        }
+        if self.spectrogram {
+            write!(
+                html,
+                "<div><img alt=\"spectrogram\" width=\"{WIDTH}\" src=\"data:image/bmp;base64,{}\"></div>",
+                base64(&spectrogram(&mono))
+            )?;
+        }
+        html.push_str("</div>");
+        Ok(html)
+    }
+}
+
+/// The default voice for playing tones: a quiet sine wave with a short
+/// attack and release.
+fn voice(sample_rate: u32, tone: Tone) -> libdaw::Result<Arc<Mutex<dyn DawNode>>> {
+    let (attack, release) = (Time::from_seconds(0.01)?, Time::from_seconds(-0.01)?);
+    let sine = Arc::new(Mutex::new(Sine::new(sample_rate, tone.frequency)));
+    let envelope = Arc::new(Mutex::new(Envelope::new(
+        sample_rate,
+        tone.length,
+        [
+            Point {
+                offset: Offset::Time(Time::ZERO),
+                whence: 0.0,
+                volume: 0.0,
+            },
+            Point {
+                offset: Offset::Time(attack),
+                whence: 0.0,
+                volume: 0.25,
+            },
+            Point {
+                offset: Offset::Time(release),
+                whence: 1.0,
+                volume: 0.25,
+            },
+            Point {
+                offset: Offset::Time(Time::ZERO),
+                whence: 1.0,
+                volume: 0.0,
+            },
+        ],
+    )));
+    let mut graph = Graph::default();
+    graph.connect(sine, envelope.clone(), None)?;
+    graph.output(envelope, None)?;
+    Ok(Arc::new(Mutex::new(graph)))
+}
+
+/// Standard base64, as used in data URIs.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (index, &byte)| {
+            bits | (byte as u32) << (16 - index * 8)
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(ALPHABET[(bits >> (18 - index * 6)) as usize & 0x3f] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// The frames of the audio split into at most `WIDTH` columns.
+fn columns(values: &[f64]) -> std::slice::Chunks<'_, f64> {
+    values.chunks(values.len().div_ceil(WIDTH).max(1))
+}
+
+/// An SVG of the peaks of the audio, one vertical line per column.
+fn waveform(values: &[f64]) -> String {
+    let middle = WAVEFORM_HEIGHT as f64 / 2.0;
+    let mut path = String::new();
+    for (x, column) in columns(values).enumerate() {
+        let (low, high) = column.iter().fold((0.0f64, 0.0f64), |(low, high), &value| {
+            (low.min(value), high.max(value))
+        });
+        let top = middle - high.min(1.0) * middle;
+        let bottom = (middle - low.max(-1.0) * middle).max(top + 1.0);
+        let _ = write!(path, "M{x}.5 {top:.1}V{bottom:.1}");
+    }
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{WAVEFORM_HEIGHT}\" \
+         viewBox=\"0 0 {WIDTH} {WAVEFORM_HEIGHT}\">\
+         <rect width=\"100%\" height=\"100%\" fill=\"#fafafa\"/>\
+         <line x1=\"0\" y1=\"{middle}\" x2=\"{WIDTH}\" y2=\"{middle}\" stroke=\"#ccc\"/>\
+         <path d=\"{path}\" stroke=\"#1f77b4\" fill=\"none\"/></svg>"
+    )
+}
+
+/// A BMP image of the spectrum of the audio over time, computed with a
+/// Hann-windowed FFT at the start of each column.  Low frequencies are at
+/// the bottom, and brighter is louder.
+fn spectrogram(values: &[f64]) -> Vec<u8> {
+    let hop = values.len().div_ceil(WIDTH).max(1);
+    let width = values.len().div_ceil(hop).max(1);
+    let height = WINDOW / 2;
+    let fft = FftPlanner::new().plan_fft_forward(WINDOW);
+    let window: Vec<f64> = (0..WINDOW)
+        .map(|index| {
+            let phase = index as f64 / WINDOW as f64 * std::f64::consts::TAU;
+            0.5 - 0.5 * phase.cos()
+        })
+        .collect();
+    let mut buffer = vec![Complex::default(); WINDOW];
+    let mut levels = vec![0u8; width * height];
+    for x in 0..width {
+        let start = x * hop;
+        for (index, bin) in buffer.iter_mut().enumerate() {
+            let value = values.get(start + index).copied().unwrap_or(0.0);
+            *bin = Complex::new(value * window[index], 0.0);
+        }
+        fft.process(&mut buffer);
+        for (y, bin) in buffer[..height].iter().enumerate() {
+            // Normalized so a full-scale sine is at 0 decibels.
+            let amplitude = bin.norm() * 4.0 / WINDOW as f64;
+            let decibels = 20.0 * amplitude.max(f64::MIN_POSITIVE).log10();
+            let level = ((decibels - FLOOR) / -FLOOR).clamp(0.0, 1.0);
+            levels[y * width + x] = (level * 255.0) as u8;
+        }
+    }
+
+    // A 24-bit BMP stores its rows bottom first, each padded to 4 bytes.
+    let row = (width * 3).div_ceil(4) * 4;
+    let size = 54 + row * height;
+    let mut bmp = Vec::with_capacity(size);
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&(size as u32).to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&54u32.to_le_bytes());
+    bmp.extend_from_slice(&40u32.to_le_bytes());
+    bmp.extend_from_slice(&(width as i32).to_le_bytes());
+    bmp.extend_from_slice(&(height as i32).to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&24u16.to_le_bytes());
+    bmp.extend_from_slice(&[0; 24]);
+    for y in 0..height {
+        for &level in &levels[y * width..(y + 1) * width] {
+            let [red, green, blue] = heat(level);
+            bmp.extend_from_slice(&[blue, green, red]);
+        }
+        bmp.resize(bmp.len() + row - width * 3, 0);
+    }
+    bmp
+}
+
+/// A black to red to yellow to white color scale.
+fn heat(level: u8) -> [u8; 3] {
+    let level = level as u32 * 3;
+    let channel = |offset: u32| level.saturating_sub(offset).min(255) as u8;
+    [channel(0), channel(255), channel(510)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_base64() {
+        // The test vectors from RFC 4648.
+        for (bytes, encoded) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64(bytes.as_bytes()), encoded);
+        }
+        assert_eq!(base64(&[0xfb, 0xff, 0xfe]), "+//+");
+    }
+
+    /// The blue, green, and red bytes of a spectrogram pixel.
+    fn pixel(bmp: &[u8], x: usize, y: usize) -> &[u8] {
+        let width = i32::from_le_bytes(bmp[18..22].try_into().unwrap()) as usize;
+        let row = (width * 3).div_ceil(4) * 4;
+        let start = 54 + y * row + x * 3;
+        &bmp[start..start + 3]
+    }
+
+    #[test]
+    fn writes_a_padded_bmp() {
+        let bmp = spectrogram(&[0.0; 3]);
+        assert_eq!(&bmp[..2], b"BM");
+        assert_eq!(
+            u32::from_le_bytes(bmp[2..6].try_into().unwrap()) as usize,
+            bmp.len()
+        );
+        assert_eq!(u32::from_le_bytes(bmp[10..14].try_into().unwrap()), 54);
+        assert_eq!(i32::from_le_bytes(bmp[18..22].try_into().unwrap()), 3);
+        assert_eq!(
+            i32::from_le_bytes(bmp[22..26].try_into().unwrap()) as usize,
+            WINDOW / 2
+        );
+        assert_eq!(u16::from_le_bytes(bmp[28..30].try_into().unwrap()), 24);
+        // Three pixels of three bytes, padded to twelve bytes a row.
+        assert_eq!(bmp.len(), 54 + 12 * WINDOW / 2);
+        assert!(bmp[54..].iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn shows_a_sine_in_its_bin() {
+        let bin = 64;
+        let values: Vec<f64> = (0..WINDOW)
+            .map(|index| (index as f64 * bin as f64 / WINDOW as f64 * std::f64::consts::TAU).sin())
+            .collect();
+        let bmp = spectrogram(&values);
+        // A full-scale sine is at 0 decibels, which is white.
+        assert!(pixel(&bmp, 0, bin).iter().all(|&byte| byte >= 254));
+        assert_eq!(pixel(&bmp, 0, bin + 20), [0, 0, 0]);
+    }
+}
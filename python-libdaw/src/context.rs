@@ -0,0 +1,62 @@
+use libdaw::Context as DawContext;
+use pyo3::{pyclass, pymethods};
+
+/// The processing context a node tree is run under.
+#[pyclass(module = "libdaw")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Context(pub DawContext);
+
+#[pymethods]
+impl Context {
+    #[new]
+    #[pyo3(signature = (sample_rate = 48000, max_block_size = 1024, channels = 2))]
+    pub fn new(sample_rate: u32, max_block_size: usize, channels: usize) -> Self {
+        Self(DawContext {
+            sample_rate,
+            max_block_size,
+            channels,
+        })
+    }
+
+    #[getter]
+    pub fn get_sample_rate(&self) -> u32 {
+        self.0.sample_rate
+    }
+
+    #[setter]
+    pub fn set_sample_rate(&mut self, value: u32) {
+        self.0.sample_rate = value;
+    }
+
+    #[getter]
+    pub fn get_max_block_size(&self) -> usize {
+        self.0.max_block_size
+    }
+
+    #[setter]
+    pub fn set_max_block_size(&mut self, value: usize) {
+        self.0.max_block_size = value;
+    }
+
+    #[getter]
+    pub fn get_channels(&self) -> usize {
+        self.0.channels
+    }
+
+    #[setter]
+    pub fn set_channels(&mut self, value: usize) {
+        self.0.channels = value;
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+
+    pub fn __eq__(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    pub fn __getnewargs__(&self) -> (u32, usize, usize) {
+        (self.0.sample_rate, self.0.max_block_size, self.0.channels)
+    }
+}
@@ -0,0 +1,107 @@
+use libdaw::Sample as DawSample;
+use pyo3::{
+    buffer::PyBuffer,
+    exceptions::{PyBufferError, PyValueError},
+    ffi, pyclass, pymethods,
+    types::{PyAnyMethods as _, PyModule},
+    Bound, PyAny, PyResult, Python,
+};
+use std::{ffi::c_int, os::raw::c_void, ptr};
+
+/// Rendered audio, exposed through the buffer protocol as a C-contiguous
+/// array of frames by channels, so NumPy can wrap it without copying.
+#[pyclass(module = "libdaw")]
+#[derive(Debug)]
+pub struct Frames {
+    data: Box<[f64]>,
+    shape: [ffi::Py_ssize_t; 2],
+    strides: [ffi::Py_ssize_t; 2],
+}
+
+impl Frames {
+    /// Interleave the frames, padding each to the widest of them.
+    pub fn new(frames: &[DawSample]) -> Self {
+        let channels = frames.iter().map(|frame| frame.len()).max().unwrap_or(0);
+        let mut data = vec![0.0; frames.len() * channels];
+        for (row, frame) in data.chunks_exact_mut(channels.max(1)).zip(frames) {
+            row[..frame.len()].copy_from_slice(frame);
+        }
+        let item = std::mem::size_of::<f64>() as ffi::Py_ssize_t;
+        Self {
+            data: data.into(),
+            shape: [frames.len() as _, channels as _],
+            strides: [channels as ffi::Py_ssize_t * item, item],
+        }
+    }
+
+    /// Wrap the frames in a two-dimensional NumPy array.
+    pub fn into_array(self, py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        let frames = Bound::new(py, self)?;
+        PyModule::import_bound(py, "numpy")?.call_method1("asarray", (frames,))
+    }
+}
+
+#[pymethods]
+impl Frames {
+    unsafe fn __getbuffer__(
+        slf: Bound<'_, Self>,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> PyResult<()> {
+        if view.is_null() {
+            return Err(PyBufferError::new_err("View is null"));
+        }
+        let frames = slf.borrow();
+        let view = &mut *view;
+        view.buf = frames.data.as_ptr() as *mut c_void;
+        view.len = std::mem::size_of_val(&*frames.data) as ffi::Py_ssize_t;
+        view.readonly = 0;
+        view.itemsize = std::mem::size_of::<f64>() as ffi::Py_ssize_t;
+        // The format is static, so nothing needs to be freed on release.
+        view.format = if flags & ffi::PyBUF_FORMAT == ffi::PyBUF_FORMAT {
+            c"d".as_ptr() as *mut _
+        } else {
+            ptr::null_mut()
+        };
+        view.ndim = 2;
+        view.shape = if flags & ffi::PyBUF_ND == ffi::PyBUF_ND {
+            frames.shape.as_ptr() as *mut _
+        } else {
+            ptr::null_mut()
+        };
+        view.strides = if flags & ffi::PyBUF_STRIDES == ffi::PyBUF_STRIDES {
+            frames.strides.as_ptr() as *mut _
+        } else {
+            ptr::null_mut()
+        };
+        view.suboffsets = ptr::null_mut();
+        view.internal = ptr::null_mut();
+        drop(frames);
+        view.obj = slf.into_any().into_ptr();
+        Ok(())
+    }
+
+    unsafe fn __releasebuffer__(&self, _view: *mut ffi::Py_buffer) {}
+}
+
+/// Read an input stream from anything supporting the buffer protocol with
+/// float64 items, either one-dimensional for a single channel, or frames by
+/// channels.
+pub fn read(py: Python<'_>, object: &Bound<'_, PyAny>) -> PyResult<Vec<DawSample>> {
+    let buffer = PyBuffer::<f64>::get_bound(object)?;
+    let channels = match buffer.shape() {
+        [_] => 1,
+        [_, channels] => *channels,
+        shape => {
+            return Err(PyValueError::new_err(format!(
+                "input streams must have one or two dimensions, not {}",
+                shape.len()
+            )))
+        }
+    };
+    let values = buffer.to_vec(py)?;
+    Ok(values
+        .chunks_exact(channels.max(1))
+        .map(|frame| frame.iter().copied().collect())
+        .collect())
+}
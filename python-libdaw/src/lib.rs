@@ -1,3 +1,6 @@
+mod audio;
+mod context;
+mod frames;
 mod indexing;
 mod metronome;
 mod node;
@@ -5,11 +8,16 @@ mod nodes;
 mod notation;
 mod pitch;
 mod play;
+mod render;
 mod sample;
 mod time;
+mod transport;
 
+pub use audio::Audio;
+pub use context::Context;
 pub use node::Node;
 pub use sample::Sample;
+pub use transport::Transport;
 
 use pyo3::{
     create_exception,
@@ -18,29 +26,63 @@ use pyo3::{
     types::{PyModule, PyModuleMethods as _},
     wrap_pyfunction_bound, Bound, PyErr, PyResult, Python,
 };
+use std::any::Any;
 
 create_exception!(libdaw, Error, PyRuntimeError);
 
 /// An intermediate conversion type that allows converting all Errors to our error type.
-pub struct ErrorWrapper(String);
+/// Python exceptions, including those raised by callbacks and passed back
+/// out through libdaw, are kept as they are.
+pub struct ErrorWrapper(PyErr);
 
 impl<T> From<T> for ErrorWrapper
 where
-    T: ToString,
+    T: ToString + 'static,
 {
     fn from(value: T) -> Self {
-        ErrorWrapper(value.to_string())
+        let value: Box<dyn Any> = Box::new(value);
+        let value = match value.downcast::<PyErr>() {
+            Ok(error) => return ErrorWrapper(*error),
+            Err(value) => value,
+        };
+        let message = match value.downcast::<::libdaw::Error>() {
+            Ok(error) => match error.downcast::<PyErr>() {
+                Ok(error) => return ErrorWrapper(*error),
+                Err(error) => error.to_string(),
+            },
+            Err(value) => value.downcast::<T>().expect("the value is a T").to_string(),
+        };
+        ErrorWrapper(Error::new_err(message))
     }
 }
 
 impl From<ErrorWrapper> for PyErr {
     fn from(value: ErrorWrapper) -> Self {
-        Error::new_err(value.0)
+        value.0
     }
 }
 
 pub type Result<T> = std::result::Result<T, ErrorWrapper>;
 
+/// Run an offline render without holding the GIL, so other Python threads
+/// keep running.  The render is given a check to call once per block, which
+/// takes the GIL back just long enough to check for signals, so Ctrl-C stops
+/// it with a KeyboardInterrupt.
+pub fn render_without_gil<T, F>(py: Python<'_>, render: F) -> Result<T>
+where
+    T: Send,
+    F: FnOnce(&mut dyn FnMut() -> ::libdaw::Result<()>) -> ::libdaw::Result<T> + Send,
+{
+    py.allow_threads(|| {
+        let mut check = || -> ::libdaw::Result<()> {
+            Python::with_gil(|py| py.check_signals())?;
+            Ok(())
+        };
+        render(&mut check)
+    })
+    .map_err(ErrorWrapper::from)
+}
+
 /// Define a submodule, adding it to sys.modules.
 macro_rules! submodule {
     ($parent:expr, $parent_package:literal, $name:literal) => {{
@@ -65,8 +107,12 @@ use submodule;
 fn libdaw(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("Error", py.get_type_bound::<Error>())?;
     m.add_class::<Sample>()?;
+    m.add_class::<Audio>()?;
     m.add_class::<Node>()?;
+    m.add_class::<Context>()?;
+    m.add_class::<Transport>()?;
     m.add_function(wrap_pyfunction_bound!(play::play, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(render::render_stems, m)?)?;
 
     nodes::register(&submodule!(m, "libdaw", "nodes"))?;
     pitch::register(&submodule!(m, "libdaw", "pitch"))?;
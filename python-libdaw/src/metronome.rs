@@ -1,4 +1,7 @@
-use crate::{time::Timestamp, Result};
+use crate::{
+    time::{Duration, Timestamp},
+    Result,
+};
 use libdaw::metronome::Metronome as DawMetronome;
 use pyo3::{
     pyclass,
@@ -139,6 +142,16 @@ impl Metronome {
     pub fn new() -> Self {
         Self(libdaw::metronome::Metronome::default())
     }
+
+    pub fn __getstate__(&self) -> Vec<TempoInstruction> {
+        self.0.tempo_instructions().map(TempoInstruction).collect()
+    }
+    pub fn __setstate__(&mut self, instructions: Vec<TempoInstruction>) {
+        self.0 = libdaw::metronome::Metronome::default();
+        for instruction in instructions {
+            self.0.add_tempo_instruction(instruction.0);
+        }
+    }
     pub fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -148,6 +161,12 @@ impl Metronome {
     pub fn beat_to_time(&mut self, beat: Beat) -> Timestamp {
         Timestamp(self.0.beat_to_time(beat.0))
     }
+    pub fn time_to_beat(&mut self, time: Timestamp) -> Beat {
+        Beat(self.0.time_to_beat(time.0))
+    }
+    pub fn beats_to_duration(&mut self, beat: Beat, length: Beat) -> Duration {
+        Duration(self.0.beats_to_duration(beat.0, length.0))
+    }
 }
 
 /// Helper to allow taking an optional metronome with a default value
@@ -1,6 +1,6 @@
-use crate::{Result, Sample};
-use libdaw::Node as Inner;
-use pyo3::{pyclass, pymethods, Bound, PyResult};
+use crate::{frames, frames::Frames, Context, Result, Sample};
+use libdaw::{output::BLOCK_SIZE, Node as Inner};
+use pyo3::{exceptions::PyValueError, pyclass, pymethods, Bound, PyAny, PyResult, Python};
 use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Clone)]
@@ -9,17 +9,93 @@ pub struct Node(pub Arc<Mutex<dyn Inner>>);
 
 #[pymethods]
 impl Node {
-    pub fn process(&self, inputs: Vec<Bound<'_, Sample>>) -> Result<Vec<Sample>> {
+    pub fn process(&self, py: Python<'_>, inputs: Vec<Bound<'_, Sample>>) -> Result<Vec<Sample>> {
         let mut outputs = Vec::new();
         let inputs: Vec<_> = inputs.into_iter().map(|i| i.borrow().0.clone()).collect();
-        self.0
-            .lock()
-            .expect("poisoned")
-            .process(&inputs, &mut outputs)?;
+        self.with_inner(py, |node| node.process(&inputs, &mut outputs))?;
         let outputs: Vec<_> = outputs.into_iter().map(Sample).collect();
         Ok(outputs)
     }
 
+    /// Process many frames at once, returning a NumPy array of frames by
+    /// channels.
+    ///
+    /// Each input is an input stream given as a float64 array, either
+    /// one-dimensional for a single channel or frames by channels, read
+    /// through the buffer protocol.  `frames` defaults to the length of the
+    /// shortest input.  The output is the sum of all output streams, or only
+    /// the given stream, padded to the widest frame.  Other Python threads
+    /// keep running during the render, and Ctrl-C interrupts it.
+    #[pyo3(signature = (frames=None, inputs=Vec::new(), stream=None))]
+    pub fn render<'py>(
+        &self,
+        py: Python<'py>,
+        frames: Option<usize>,
+        inputs: Vec<Bound<'py, PyAny>>,
+        stream: Option<usize>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inputs = inputs
+            .iter()
+            .map(|input| frames::read(py, input))
+            .collect::<PyResult<Vec<_>>>()?;
+        let shortest = inputs.iter().map(Vec::len).min();
+        let frames = match (frames, shortest) {
+            (Some(frames), Some(shortest)) if frames > shortest => {
+                return Err(PyValueError::new_err(format!(
+                    "an input only has {shortest} frames, not {frames}"
+                )))
+            }
+            (Some(frames), _) => frames,
+            (None, Some(shortest)) => shortest,
+            (None, None) => {
+                return Err(PyValueError::new_err(
+                    "frames is needed when there are no inputs",
+                ))
+            }
+        };
+        let node = self.0.clone();
+        let rendered = crate::render_without_gil(py, move |check| {
+            let mut node = node.lock().expect("poisoned");
+            let mut frame_inputs = Vec::with_capacity(inputs.len());
+            let mut outputs = Vec::new();
+            let mut rendered = Vec::with_capacity(frames);
+            for index in 0..frames {
+                if index % BLOCK_SIZE == 0 {
+                    check()?;
+                }
+                frame_inputs.clear();
+                frame_inputs.extend(inputs.iter().map(|input| input[index].clone()));
+                outputs.clear();
+                node.process(&frame_inputs, &mut outputs)?;
+                rendered.push(match stream {
+                    Some(stream) => outputs.get(stream).cloned().unwrap_or_default(),
+                    None => outputs.iter().sum(),
+                });
+            }
+            Ok(rendered)
+        })?;
+        Frames::new(&rendered).into_array(py)
+    }
+
+    /// Prepare the node, and any nodes it contains, to be processed under
+    /// the given context.
+    pub fn prepare(&self, py: Python<'_>, context: Context) -> Result<()> {
+        self.with_inner(py, |node| node.prepare(&context.0))?;
+        Ok(())
+    }
+
+    /// Return the node, and any nodes it contains, to its initial state.
+    pub fn reset(&self, py: Python<'_>) -> Result<()> {
+        self.with_inner(py, |node| node.reset())?;
+        Ok(())
+    }
+
+    /// Move the node, and any nodes it contains, to the given sample.
+    pub fn seek(&self, py: Python<'_>, sample: u64) -> Result<()> {
+        self.with_inner(py, |node| node.seek(sample))?;
+        Ok(())
+    }
+
     pub fn __repr__(&self) -> String {
         format!("{:?}", (&*self.0))
     }
@@ -28,10 +104,9 @@ impl Node {
         self_
     }
 
-    pub fn __next__(&self) -> PyResult<Option<Vec<Sample>>> {
-        let mut lock = self.0.lock().expect("poisoned");
-        let mut dynref: &mut dyn libdaw::Node = &mut *lock;
-        match dynref.next() {
+    pub fn __next__(&self, py: Python<'_>) -> PyResult<Option<Vec<Sample>>> {
+        let next = self.with_inner(py, |mut node| node.next());
+        match next {
             Some(outputs) => Ok(Some(
                 outputs
                     .map_err(|e| crate::Error::new_err(e.to_string()))?
@@ -43,3 +118,15 @@ impl Node {
         }
     }
 }
+
+impl Node {
+    /// Lock the node without holding the GIL, since processing it may wait
+    /// on another thread that needs the GIL, like an instrument's factory.
+    fn with_inner<T: Send>(
+        &self,
+        py: Python<'_>,
+        function: impl FnOnce(&mut dyn Inner) -> T + Send,
+    ) -> T {
+        py.allow_threads(|| function(&mut *self.0.lock().expect("poisoned")))
+    }
+}
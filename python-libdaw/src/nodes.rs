@@ -1,34 +1,63 @@
 pub mod add;
+pub mod bitcrusher;
 pub mod callback;
+pub mod channels;
 pub mod constant_value;
+pub mod convolution;
 pub mod custom;
+pub mod custom_block;
 pub mod delay;
+pub mod delay_line;
 pub mod detune;
+pub mod dynamics;
 pub mod envelope;
 pub mod explode;
+pub mod feedback_delay;
 pub mod filters;
 pub mod gain;
+pub mod granular;
 pub mod graph;
 pub mod implode;
 pub mod instrument;
+pub mod karplus_strong;
+pub mod lfo;
+pub mod mixer;
+pub mod modulation;
 pub mod multiply;
 pub mod oscillators;
+pub mod oversample;
 pub mod passthrough;
+pub mod resampler;
+pub mod reverb;
+pub mod tap;
+pub mod waveshaper;
 
 pub use add::Add;
+pub use bitcrusher::Bitcrusher;
 pub use callback::Callback;
 pub use constant_value::ConstantValue;
+pub use convolution::Convolution;
 pub use custom::Custom;
+pub use custom_block::CustomBlock;
 pub use delay::Delay;
 pub use detune::Detune;
 pub use envelope::Envelope;
 pub use explode::Explode;
+pub use feedback_delay::FeedbackDelay;
 pub use gain::Gain;
+pub use granular::Granular;
 pub use graph::Graph;
 pub use implode::Implode;
 pub use instrument::Instrument;
+pub use karplus_strong::KarplusStrong;
+pub use lfo::Lfo;
 pub use multiply::Multiply;
+pub use oversample::Oversample;
 pub use passthrough::Passthrough;
+pub use resampler::Resampler;
+pub use reverb::Reverb;
+pub use tap::Tap;
+pub use waveshaper::Waveshaper;
 
 use crate::submodule;
 use pyo3::{
@@ -38,22 +67,42 @@ use pyo3::{
 
 pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_class::<Add>()?;
+    module.add_class::<Bitcrusher>()?;
     module.add_class::<Callback>()?;
     module.add_class::<ConstantValue>()?;
+    module.add_class::<Convolution>()?;
     module.add_class::<Custom>()?;
+    module.add_class::<CustomBlock>()?;
     module.add_class::<Delay>()?;
     module.add_class::<Detune>()?;
     module.add_class::<Envelope>()?;
     module.add_class::<Explode>()?;
+    module.add_class::<FeedbackDelay>()?;
     module.add_class::<Implode>()?;
     module.add_class::<Gain>()?;
+    module.add_class::<Granular>()?;
     module.add_class::<Graph>()?;
     module.add_class::<Instrument>()?;
+    module.add_class::<KarplusStrong>()?;
+    module.add_class::<Lfo>()?;
     module.add_class::<Multiply>()?;
+    module.add_class::<Oversample>()?;
     module.add_class::<Passthrough>()?;
+    module.add_class::<Resampler>()?;
+    module.add_class::<Reverb>()?;
+    module.add_class::<Tap>()?;
+    module.add_class::<Waveshaper>()?;
+    delay_line::register(&submodule!(module, "libdaw.nodes", "delay_line"))?;
     envelope::register(&submodule!(module, "libdaw.nodes", "envelope"))?;
+    channels::register(&submodule!(module, "libdaw.nodes", "channels"))?;
+    dynamics::register(&submodule!(module, "libdaw.nodes", "dynamics"))?;
     filters::register(&submodule!(module, "libdaw.nodes", "filters"))?;
+    granular::register(&submodule!(module, "libdaw.nodes", "granular"))?;
     instrument::register(&submodule!(module, "libdaw.nodes", "instrument"))?;
+    lfo::register(&submodule!(module, "libdaw.nodes", "lfo"))?;
+    mixer::register(&submodule!(module, "libdaw.nodes", "mixer"))?;
+    modulation::register(&submodule!(module, "libdaw.nodes", "modulation"))?;
     oscillators::register(&submodule!(module, "libdaw.nodes", "oscillators"))?;
+    waveshaper::register(&submodule!(module, "libdaw.nodes", "waveshaper"))?;
     Ok(())
 }
@@ -1,6 +1,6 @@
 use crate::Node;
 use libdaw::nodes::Add as Inner;
-use pyo3::{pyclass, pymethods, PyClassInitializer};
+use pyo3::{pyclass, pymethods, types::PyTuple, Bound, PyClassInitializer, Python};
 use std::sync::{Arc, Mutex};
 
 #[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
@@ -14,4 +14,8 @@ impl Add {
         let inner = Arc::new(Mutex::new(Inner::default()));
         PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
     }
+
+    pub fn __getnewargs__<'py>(&self, py: Python<'py>) -> Bound<'py, PyTuple> {
+        PyTuple::empty_bound(py)
+    }
 }
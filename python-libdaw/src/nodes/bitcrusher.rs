@@ -0,0 +1,46 @@
+use crate::Node;
+use libdaw::nodes::Bitcrusher as Inner;
+use pyo3::{pyclass, pymethods, PyClassInitializer};
+use std::sync::{Arc, Mutex};
+
+/// Reduces the bit depth and sample rate of each input stream.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
+#[derive(Debug, Clone)]
+pub struct Bitcrusher(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Bitcrusher {
+    #[new]
+    #[pyo3(signature = (bits = 8.0, rate = None, sample_rate = 48000))]
+    pub fn new(bits: f64, rate: Option<f64>, sample_rate: u32) -> PyClassInitializer<Self> {
+        let mut inner = Inner::new(sample_rate);
+        inner.bits = bits;
+        if let Some(rate) = rate {
+            inner.rate = rate;
+        }
+        let inner = Arc::new(Mutex::new(inner));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (f64, Option<f64>, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (inner.bits, Some(inner.rate), inner.sample_rate())
+    }
+
+    #[getter]
+    pub fn get_bits(&self) -> f64 {
+        self.0.lock().expect("poisoned").bits
+    }
+    #[setter]
+    pub fn set_bits(&self, bits: f64) {
+        self.0.lock().expect("poisoned").bits = bits;
+    }
+    #[getter]
+    pub fn get_rate(&self) -> f64 {
+        self.0.lock().expect("poisoned").rate
+    }
+    #[setter]
+    pub fn set_rate(&self, rate: f64) {
+        self.0.lock().expect("poisoned").rate = rate;
+    }
+}
@@ -151,6 +151,26 @@ impl DawNode for Inner {
         self.sample += 1;
         Ok(())
     }
+
+    fn prepare(&mut self, context: &libdaw::Context) -> libdaw::Result<()> {
+        if context.sample_rate != self.sample_rate {
+            self.sample =
+                (self.sample as f64 * context.sample_rate as f64 / self.sample_rate as f64) as u64;
+            self.sample_rate = context.sample_rate;
+        }
+        self.node.lock().expect("poisoned").prepare(context)
+    }
+
+    fn reset(&mut self) -> libdaw::Result<()> {
+        self.sample = 0;
+        self.node.lock().expect("poisoned").reset()
+    }
+
+    /// Callbacks that have already been removed are not restored.
+    fn seek(&mut self, sample: u64) -> libdaw::Result<()> {
+        self.sample = sample;
+        self.node.lock().expect("poisoned").seek(sample)
+    }
 }
 
 #[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
@@ -171,6 +191,39 @@ impl Callback {
             node: Some(node.unbind()),
         })
     }
+
+    pub fn __getnewargs__(&self) -> (Py<Node>, u32) {
+        (
+            self.get_node(),
+            self.inner.lock().expect("poisoned").sample_rate,
+        )
+    }
+
+    /// The callbacks that have not been removed yet, as `(callable, start,
+    /// end, post)` tuples.
+    #[allow(clippy::type_complexity)]
+    pub fn __getstate__(&self, py: Python<'_>) -> Vec<(Py<PyAny>, Timestamp, Timestamp, bool)> {
+        let inner = self.inner.lock().expect("poisoned");
+        let pre = inner.pre.slots.iter().map(|slot| (slot, false));
+        let post = inner.post.slots.iter().map(|slot| (slot, true));
+        pre.chain(post)
+            .map(|(slot, post)| {
+                (
+                    slot.callable.clone_ref(py),
+                    Timestamp(slot.start),
+                    Timestamp(slot.end),
+                    post,
+                )
+            })
+            .collect()
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn __setstate__(&self, state: Vec<(Py<PyAny>, Timestamp, Timestamp, bool)>) {
+        for (callable, start, end, post) in state {
+            self.add(callable, start, end, post);
+        }
+    }
     #[pyo3(signature = (
         callable,
         start = Timestamp::MIN,
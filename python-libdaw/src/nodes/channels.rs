@@ -0,0 +1,26 @@
+pub mod matrix;
+pub mod mono;
+pub mod pan;
+pub mod stereo_width;
+pub mod upmix;
+
+pub use matrix::Matrix;
+pub use mono::Mono;
+pub use pan::{Pan, PanLaw};
+pub use stereo_width::StereoWidth;
+pub use upmix::Upmix;
+
+use pyo3::{
+    types::{PyModule, PyModuleMethods as _},
+    Bound, PyResult,
+};
+
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PanLaw>()?;
+    module.add_class::<Matrix>()?;
+    module.add_class::<Mono>()?;
+    module.add_class::<Pan>()?;
+    module.add_class::<StereoWidth>()?;
+    module.add_class::<Upmix>()?;
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::Node;
+use libdaw::nodes::channels::Matrix as Inner;
+use pyo3::{pyclass, pymethods, Py, PyClassInitializer, PyResult, Python};
+use std::sync::{Arc, Mutex};
+
+/// Routes the concatenated channels of all input streams to output channels,
+/// with one row of gains per output channel.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.channels")]
+#[derive(Debug, Clone)]
+pub struct Matrix(pub Arc<Mutex<Inner>>);
+
+impl Matrix {
+    fn from_inner(inner: Inner) -> PyClassInitializer<Self> {
+        let inner = Arc::new(Mutex::new(inner));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+}
+
+#[pymethods]
+impl Matrix {
+    #[new]
+    pub fn new(gains: Vec<Vec<f64>>) -> PyClassInitializer<Self> {
+        Self::from_inner(Inner::new(gains))
+    }
+
+    pub fn __getnewargs__(&self) -> (Vec<Vec<f64>>,) {
+        (self.get_gains(),)
+    }
+
+    #[staticmethod]
+    pub fn identity(py: Python<'_>, channels: usize) -> PyResult<Py<Self>> {
+        Py::new(py, Self::from_inner(Inner::identity(channels)))
+    }
+
+    #[getter]
+    pub fn get_gains(&self) -> Vec<Vec<f64>> {
+        self.0.lock().expect("poisoned").gains.clone()
+    }
+    #[setter]
+    pub fn set_gains(&self, gains: Vec<Vec<f64>>) {
+        self.0.lock().expect("poisoned").gains = gains;
+    }
+}
@@ -0,0 +1,32 @@
+use crate::Node;
+use libdaw::nodes::channels::Mono as Inner;
+use pyo3::{pyclass, pymethods, PyClassInitializer};
+use std::sync::{Arc, Mutex};
+
+/// Sums all channels of each input stream into a single channel.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.channels")]
+#[derive(Debug, Clone)]
+pub struct Mono(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Mono {
+    #[new]
+    #[pyo3(signature = (average = true))]
+    pub fn new(average: bool) -> PyClassInitializer<Self> {
+        let inner = Arc::new(Mutex::new(Inner { average }));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (bool,) {
+        (self.get_average(),)
+    }
+
+    #[getter]
+    pub fn get_average(&self) -> bool {
+        self.0.lock().expect("poisoned").average
+    }
+    #[setter]
+    pub fn set_average(&self, average: bool) {
+        self.0.lock().expect("poisoned").average = average;
+    }
+}
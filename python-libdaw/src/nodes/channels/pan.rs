@@ -0,0 +1,95 @@
+use crate::Node;
+use libdaw::nodes::channels::{pan, Pan as Inner};
+use pyo3::{exceptions::PyValueError, pyclass, pymethods, PyClassInitializer, PyResult};
+use std::sync::{Arc, Mutex};
+
+#[pyclass(module = "libdaw.nodes.channels", eq, eq_int)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanLaw {
+    Linear,
+    ConstantPower,
+    Compromise,
+}
+
+#[pymethods]
+impl PanLaw {
+    #[new]
+    pub fn new(name: &str) -> PyResult<Self> {
+        match name.to_lowercase().as_str() {
+            "linear" => Ok(Self::Linear),
+            "constant_power" | "constantpower" => Ok(Self::ConstantPower),
+            "compromise" => Ok(Self::Compromise),
+            name => Err(PyValueError::new_err(format!("Unknown name {name}"))),
+        }
+    }
+
+    /// The left and right gains for a position from -1 (left) to 1 (right).
+    pub fn gains(&self, position: f64) -> (f64, f64) {
+        pan::PanLaw::from(*self).gains(position)
+    }
+
+    pub fn __getnewargs__(&self) -> (&str,) {
+        match self {
+            PanLaw::Linear => ("linear",),
+            PanLaw::ConstantPower => ("constant_power",),
+            PanLaw::Compromise => ("compromise",),
+        }
+    }
+}
+
+impl From<PanLaw> for pan::PanLaw {
+    fn from(value: PanLaw) -> Self {
+        match value {
+            PanLaw::Linear => pan::PanLaw::Linear,
+            PanLaw::ConstantPower => pan::PanLaw::ConstantPower,
+            PanLaw::Compromise => pan::PanLaw::Compromise,
+        }
+    }
+}
+
+impl From<pan::PanLaw> for PanLaw {
+    fn from(value: pan::PanLaw) -> Self {
+        match value {
+            pan::PanLaw::Linear => PanLaw::Linear,
+            pan::PanLaw::ConstantPower => PanLaw::ConstantPower,
+            pan::PanLaw::Compromise => PanLaw::Compromise,
+        }
+    }
+}
+
+/// Pans the first input stream.  The second input stream, if present,
+/// overrides the position.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.channels")]
+#[derive(Debug, Clone)]
+pub struct Pan(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Pan {
+    #[new]
+    #[pyo3(signature = (position = 0.0, law = PanLaw::ConstantPower))]
+    pub fn new(position: f64, law: PanLaw) -> PyClassInitializer<Self> {
+        let inner = Arc::new(Mutex::new(Inner::new(position, law.into())));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (f64, PanLaw) {
+        (self.get_position(), self.get_law())
+    }
+
+    #[getter]
+    pub fn get_position(&self) -> f64 {
+        self.0.lock().expect("poisoned").position
+    }
+    #[setter]
+    pub fn set_position(&self, position: f64) {
+        self.0.lock().expect("poisoned").position = position;
+    }
+    #[getter]
+    pub fn get_law(&self) -> PanLaw {
+        self.0.lock().expect("poisoned").law.into()
+    }
+    #[setter]
+    pub fn set_law(&self, law: PanLaw) {
+        self.0.lock().expect("poisoned").law = law.into();
+    }
+}
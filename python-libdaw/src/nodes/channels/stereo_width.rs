@@ -0,0 +1,41 @@
+use crate::Node;
+use libdaw::nodes::channels::StereoWidth as Inner;
+use pyo3::{pyclass, pymethods, PyClassInitializer};
+use std::sync::{Arc, Mutex};
+
+/// Adjusts the width and balance of a stereo signal.  The second and third
+/// input streams, if present, override the balance and width.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.channels")]
+#[derive(Debug, Clone)]
+pub struct StereoWidth(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl StereoWidth {
+    #[new]
+    #[pyo3(signature = (width = 1.0, balance = 0.0))]
+    pub fn new(width: f64, balance: f64) -> PyClassInitializer<Self> {
+        let inner = Arc::new(Mutex::new(Inner::new(width, balance)));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (f64, f64) {
+        (self.get_width(), self.get_balance())
+    }
+
+    #[getter]
+    pub fn get_width(&self) -> f64 {
+        self.0.lock().expect("poisoned").width
+    }
+    #[setter]
+    pub fn set_width(&self, width: f64) {
+        self.0.lock().expect("poisoned").width = width;
+    }
+    #[getter]
+    pub fn get_balance(&self) -> f64 {
+        self.0.lock().expect("poisoned").balance
+    }
+    #[setter]
+    pub fn set_balance(&self, balance: f64) {
+        self.0.lock().expect("poisoned").balance = balance;
+    }
+}
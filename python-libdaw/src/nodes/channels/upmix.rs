@@ -0,0 +1,32 @@
+use crate::Node;
+use libdaw::nodes::channels::Upmix as Inner;
+use pyo3::{pyclass, pymethods, PyClassInitializer};
+use std::sync::{Arc, Mutex};
+
+/// Expands each input stream to a fixed number of channels.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.channels")]
+#[derive(Debug, Clone)]
+pub struct Upmix(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Upmix {
+    #[new]
+    #[pyo3(signature = (channels = 2))]
+    pub fn new(channels: usize) -> PyClassInitializer<Self> {
+        let inner = Arc::new(Mutex::new(Inner::new(channels)));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (usize,) {
+        (self.get_channels(),)
+    }
+
+    #[getter]
+    pub fn get_channels(&self) -> usize {
+        self.0.lock().expect("poisoned").channels
+    }
+    #[setter]
+    pub fn set_channels(&self, channels: usize) {
+        self.0.lock().expect("poisoned").channels = channels;
+    }
+}
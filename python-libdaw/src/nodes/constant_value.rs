@@ -15,4 +15,8 @@ impl ConstantValue {
         let inner = Arc::new(Mutex::new(Inner::new(value)));
         PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
     }
+
+    pub fn __getnewargs__(&self) -> (f64,) {
+        (self.0.lock().expect("poisoned").value,)
+    }
 }
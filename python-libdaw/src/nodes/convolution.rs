@@ -0,0 +1,66 @@
+use crate::{Node, Sample};
+use libdaw::nodes::Convolution as Inner;
+use pyo3::{pyclass, pymethods, Py, PyClassInitializer, Python};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+/// Convolves its input with an impulse response.
+///
+/// A 1-channel impulse response is applied to every input channel, a
+/// 2-channel one is applied per side, and a 4-channel one is true stereo.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
+#[derive(Debug, Clone)]
+pub struct Convolution(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Convolution {
+    #[new]
+    #[pyo3(signature = (impulse_response, block_size = 256))]
+    pub fn new(
+        impulse_response: Vec<Sample>,
+        block_size: usize,
+    ) -> crate::Result<PyClassInitializer<Self>> {
+        let impulse_response: Vec<_> = impulse_response
+            .into_iter()
+            .map(|sample| sample.0)
+            .collect();
+        let inner = Arc::new(Mutex::new(Inner::new(&impulse_response, block_size)?));
+        Ok(PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner)))
+    }
+
+    pub fn __getnewargs__(&self) -> (Vec<Sample>, usize) {
+        let inner = self.0.lock().expect("poisoned");
+        (
+            inner
+                .impulse_response()
+                .iter()
+                .cloned()
+                .map(Sample)
+                .collect(),
+            inner.block_size(),
+        )
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (path, block_size = 256, sample_rate = 48000))]
+    pub fn from_wav(
+        py: Python<'_>,
+        path: PathBuf,
+        block_size: usize,
+        sample_rate: u32,
+    ) -> crate::Result<Py<Self>> {
+        let inner = Arc::new(Mutex::new(Inner::from_wav(sample_rate, path, block_size)?));
+        Ok(Py::new(
+            py,
+            PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner)),
+        )?)
+    }
+
+    /// The delay added by the convolution, in samples.
+    #[getter]
+    pub fn get_latency(&self) -> usize {
+        self.0.lock().expect("poisoned").latency()
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{Node, Sample};
 use libdaw::Node as DawNode;
 use pyo3::{
-    exceptions::PyRuntimeError, pyclass, pymethods, types::PyAnyMethods as _, Bound, IntoPy, Py,
-    PyAny, PyClassInitializer, PyResult, PyTraverseError, PyVisit, Python,
+    exceptions::PyRuntimeError,
+    intern, pyclass, pymethods,
+    types::{PyAnyMethods as _, PyDict},
+    Bound, IntoPy, Py, PyAny, PyClassInitializer, PyResult, PyTraverseError, PyVisit, Python,
 };
 use std::sync::{Arc, Mutex};
 
@@ -69,6 +71,39 @@ impl Custom {
         }
     }
 
+    /// A subclass that is its own callable is rebuilt without calling
+    /// `__init__`, so the callable is restored here along with the instance
+    /// dictionary.
+    pub fn __getnewargs__(slf: &Bound<'_, Self>) -> (Option<Py<PyAny>>,) {
+        let callable = slf.borrow().0.lock().expect("poisoned").callable.clone();
+        (callable.filter(|callable| !callable.is(slf)),)
+    }
+
+    pub fn __getstate__(slf: &Bound<'_, Self>) -> PyResult<(Option<Py<PyAny>>,)> {
+        let dict = if slf.hasattr(intern!(slf.py(), "__dict__"))? {
+            Some(slf.getattr(intern!(slf.py(), "__dict__"))?.unbind())
+        } else {
+            None
+        };
+        Ok((dict,))
+    }
+
+    pub fn __setstate__(
+        slf: &Bound<'_, Self>,
+        state: (Option<Bound<'_, PyDict>>,),
+    ) -> PyResult<()> {
+        if let Some(dict) = state.0 {
+            slf.getattr(intern!(slf.py(), "__dict__"))?
+                .call_method1(intern!(slf.py(), "update"), (dict,))?;
+        }
+        let inner = slf.borrow().0.clone();
+        let mut inner = inner.lock().expect("poisoned");
+        if inner.callable.is_none() {
+            inner.callable = Some(slf.clone().into_any().unbind());
+        }
+        Ok(())
+    }
+
     #[pyo3(signature = (callable = None))]
     pub fn __init__<'py>(self_: &Bound<'py, Self>, py: Python<'py>, callable: Option<Py<PyAny>>) {
         let inner = self_.borrow_mut().0.clone();
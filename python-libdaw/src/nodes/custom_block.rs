@@ -0,0 +1,236 @@
+use crate::{frames, frames::Frames, Node};
+use libdaw::{Node as DawNode, Sample as DawSample};
+use pyo3::{
+    buffer::PyBuffer,
+    exceptions::{PyRuntimeError, PyValueError},
+    intern, pyclass, pymethods,
+    types::{PyAnyMethods as _, PyDict, PyList},
+    Bound, IntoPy, Py, PyAny, PyClassInitializer, PyResult, PyTraverseError, PyVisit, Python,
+};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug)]
+struct Inner {
+    callable: Option<Py<PyAny>>,
+    block_size: usize,
+
+    /// The frames of each input stream in the block being gathered.
+    inputs: Vec<Vec<DawSample>>,
+
+    /// The frames of each output stream from the last processed block.
+    outputs: Vec<Vec<DawSample>>,
+
+    /// The frame within the current block.
+    position: usize,
+}
+
+impl Inner {
+    /// Hand the gathered block to the callable, keeping its output for the
+    /// next block.
+    fn run(&mut self) -> PyResult<()> {
+        let Some(callable) = &self.callable else {
+            return Err(PyRuntimeError::new_err(
+                "Can not run a custom block node without a callable",
+            ));
+        };
+        let inputs = std::mem::take(&mut self.inputs);
+        self.outputs = Python::with_gil(|py| {
+            let inputs = inputs
+                .iter()
+                .map(|frames| Frames::new(frames).into_array(py))
+                .collect::<PyResult<Vec<_>>>()?;
+            let result = callable.bind(py).call1((PyList::new_bound(py, inputs),))?;
+            let outputs = if PyBuffer::<f64>::get_bound(&result).is_ok() {
+                vec![frames::read(py, &result)?]
+            } else {
+                result
+                    .iter()?
+                    .map(|output| frames::read(py, &output?))
+                    .collect::<PyResult<Vec<_>>>()?
+            };
+            for output in &outputs {
+                if output.len() != self.block_size {
+                    return Err(PyValueError::new_err(format!(
+                        "output streams must have {} frames, not {}",
+                        self.block_size,
+                        output.len()
+                    )));
+                }
+            }
+            Ok(outputs)
+        })?;
+        Ok(())
+    }
+}
+
+impl DawNode for Inner {
+    fn process<'a, 'b, 'c>(
+        &'a mut self,
+        inputs: &'b [DawSample],
+        outputs: &'c mut Vec<DawSample>,
+    ) -> libdaw::Result<()> {
+        outputs.extend(
+            self.outputs
+                .iter()
+                .map(|stream| stream[self.position].clone()),
+        );
+        if inputs.len() > self.inputs.len() {
+            // Streams that appear partway through a block are silent before.
+            let position = self.position;
+            self.inputs
+                .resize_with(inputs.len(), || vec![DawSample::default(); position]);
+        }
+        for (stream, frames) in self.inputs.iter_mut().enumerate() {
+            frames.push(inputs.get(stream).cloned().unwrap_or_default());
+        }
+        self.position += 1;
+        if self.position == self.block_size {
+            self.position = 0;
+            self.run()?;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> libdaw::Result<()> {
+        self.inputs.clear();
+        self.outputs.clear();
+        self.position = 0;
+        Ok(())
+    }
+}
+
+/// A custom Node that processes blocks of frames at once.
+///
+/// Input frames are gathered into blocks of `block_size` frames, and the
+/// callable is called once per block with a list of NumPy arrays, one for
+/// each input stream, of frames by channels.  It returns an array, or a
+/// sequence of them, one for each output stream, each with `block_size`
+/// frames.  The output is played back over the next block, so it lags the
+/// input by `block_size` frames, and there are no output streams during the
+/// first block.
+///
+/// As with Custom, you can pass the callable into this, assign it to its
+/// `callable` property, or subclass this with a callable.  If you subclass
+/// this, you **must** call super().__init__()
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
+#[derive(Debug, Clone)]
+pub struct CustomBlock(Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl CustomBlock {
+    #[new]
+    #[pyo3(signature = (callable = None, block_size = 256))]
+    pub fn new(
+        callable: Option<Py<PyAny>>,
+        block_size: usize,
+    ) -> PyResult<PyClassInitializer<Self>> {
+        if block_size == 0 {
+            return Err(PyValueError::new_err("block_size must be positive"));
+        }
+        // A missing callable is filled in by __init__, as for Custom.
+        let inner = Arc::new(Mutex::new(Inner {
+            callable,
+            block_size,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            position: 0,
+        }));
+        Ok(PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner)))
+    }
+
+    /// A subclass's own arguments reach `__new__` first, so the block size
+    /// is taken from here.
+    #[pyo3(signature = (callable = None, block_size = 256))]
+    pub fn __init__<'py>(
+        self_: &Bound<'py, Self>,
+        py: Python<'py>,
+        callable: Option<Py<PyAny>>,
+        block_size: usize,
+    ) -> PyResult<()> {
+        if block_size == 0 {
+            return Err(PyValueError::new_err("block_size must be positive"));
+        }
+        let inner = self_.borrow_mut().0.clone();
+        let mut lock = inner.lock().expect("poisoned");
+        lock.block_size = block_size;
+        lock.inputs.clear();
+        lock.outputs.clear();
+        lock.position = 0;
+        match callable {
+            Some(callable) => {
+                lock.callable = Some(callable);
+            }
+            None => {
+                lock.callable = Some(self_.clone().unbind().into_py(py));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn __getnewargs__(slf: &Bound<'_, Self>) -> (Option<Py<PyAny>>, usize) {
+        let inner = slf.borrow().0.clone();
+        let inner = inner.lock().expect("poisoned");
+        let callable = inner.callable.clone().filter(|callable| !callable.is(slf));
+        (callable, inner.block_size)
+    }
+
+    pub fn __getstate__(slf: &Bound<'_, Self>) -> PyResult<(Option<Py<PyAny>>,)> {
+        let dict = if slf.hasattr(intern!(slf.py(), "__dict__"))? {
+            Some(slf.getattr(intern!(slf.py(), "__dict__"))?.unbind())
+        } else {
+            None
+        };
+        Ok((dict,))
+    }
+
+    pub fn __setstate__(
+        slf: &Bound<'_, Self>,
+        state: (Option<Bound<'_, PyDict>>,),
+    ) -> PyResult<()> {
+        if let Some(dict) = state.0 {
+            slf.getattr(intern!(slf.py(), "__dict__"))?
+                .call_method1(intern!(slf.py(), "update"), (dict,))?;
+        }
+        let inner = slf.borrow().0.clone();
+        let mut inner = inner.lock().expect("poisoned");
+        if inner.callable.is_none() {
+            inner.callable = Some(slf.clone().into_any().unbind());
+        }
+        Ok(())
+    }
+
+    #[getter]
+    fn get_callable(&self) -> PyResult<Py<PyAny>> {
+        let lock = self.0.lock().expect("poisoned");
+        if let Some(callable) = &lock.callable {
+            Ok(callable.clone())
+        } else {
+            Err(PyRuntimeError::new_err("Callable was None.  This probably means you forgot to set it or you forgot to call super().__init__() in your constructor.  Alternately, it could mean a bug in libdaw."))
+        }
+    }
+
+    #[setter]
+    fn set_callable(&self, callable: Py<PyAny>) {
+        self.0.lock().expect("poisoned").callable = Some(callable);
+    }
+
+    #[getter]
+    fn get_block_size(&self) -> usize {
+        self.0.lock().expect("poisoned").block_size
+    }
+
+    fn __traverse__(&self, visit: PyVisit<'_>) -> std::result::Result<(), PyTraverseError> {
+        self.0
+            .lock()
+            .expect("poisoned")
+            .callable
+            .as_ref()
+            .map(|callable| visit.call(callable))
+            .transpose()
+            .and(Ok(()))
+    }
+
+    fn __clear__(&mut self) {
+        self.0.lock().expect("poisoned").callable = None;
+    }
+}
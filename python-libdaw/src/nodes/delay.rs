@@ -15,4 +15,9 @@ impl Delay {
         let inner = Arc::new(Mutex::new(Inner::new(sample_rate, delay.0)));
         PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
     }
+
+    pub fn __getnewargs__(&self) -> (Duration, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (Duration(inner.delay()), inner.sample_rate())
+    }
 }
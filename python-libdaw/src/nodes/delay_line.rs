@@ -0,0 +1,61 @@
+use libdaw::nodes::delay_line;
+use pyo3::{
+    exceptions::PyValueError,
+    pyclass, pymethods,
+    types::{PyModule, PyModuleMethods as _},
+    Bound, PyResult,
+};
+
+#[pyclass(module = "libdaw.nodes.delay_line", eq, eq_int)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    Linear,
+    Cubic,
+    AllPass,
+}
+
+#[pymethods]
+impl Interpolation {
+    #[new]
+    pub fn new(name: &str) -> PyResult<Self> {
+        match name.to_lowercase().as_str() {
+            "linear" => Ok(Self::Linear),
+            "cubic" => Ok(Self::Cubic),
+            "allpass" | "all_pass" => Ok(Self::AllPass),
+            name => Err(PyValueError::new_err(format!("Unknown name {name}"))),
+        }
+    }
+
+    pub fn __getnewargs__(&self) -> (&str,) {
+        match self {
+            Interpolation::Linear => ("linear",),
+            Interpolation::Cubic => ("cubic",),
+            Interpolation::AllPass => ("allpass",),
+        }
+    }
+}
+
+impl From<Interpolation> for delay_line::Interpolation {
+    fn from(value: Interpolation) -> Self {
+        match value {
+            Interpolation::Linear => delay_line::Interpolation::Linear,
+            Interpolation::Cubic => delay_line::Interpolation::Cubic,
+            Interpolation::AllPass => delay_line::Interpolation::AllPass,
+        }
+    }
+}
+
+impl From<delay_line::Interpolation> for Interpolation {
+    fn from(value: delay_line::Interpolation) -> Self {
+        match value {
+            delay_line::Interpolation::Linear => Interpolation::Linear,
+            delay_line::Interpolation::Cubic => Interpolation::Cubic,
+            delay_line::Interpolation::AllPass => Interpolation::AllPass,
+        }
+    }
+}
+
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Interpolation>()?;
+    Ok(())
+}
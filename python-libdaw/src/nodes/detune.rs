@@ -16,6 +16,10 @@ impl Detune {
         PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
     }
 
+    pub fn __getnewargs__(&self) -> (f64,) {
+        (self.get_detune(),)
+    }
+
     #[getter]
     pub fn get_detune(&self) -> f64 {
         self.0.lock().expect("poisoned").detune
@@ -0,0 +1,67 @@
+pub mod compressor;
+pub mod gate;
+pub mod limiter;
+
+pub use compressor::Compressor;
+pub use gate::Gate;
+pub use limiter::Limiter;
+
+use libdaw::nodes::dynamics;
+use pyo3::{
+    exceptions::PyValueError,
+    pyclass, pymethods,
+    types::{PyModule, PyModuleMethods as _},
+    Bound, PyResult,
+};
+
+#[pyclass(module = "libdaw.nodes.dynamics", eq, eq_int)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detection {
+    Peak,
+    Rms,
+}
+
+#[pymethods]
+impl Detection {
+    #[new]
+    pub fn new(name: &str) -> PyResult<Self> {
+        match name.to_lowercase().as_str() {
+            "peak" => Ok(Self::Peak),
+            "rms" => Ok(Self::Rms),
+            name => Err(PyValueError::new_err(format!("Unknown name {name}"))),
+        }
+    }
+
+    pub fn __getnewargs__(&self) -> (&str,) {
+        match self {
+            Detection::Peak => ("peak",),
+            Detection::Rms => ("rms",),
+        }
+    }
+}
+
+impl From<Detection> for dynamics::Detection {
+    fn from(value: Detection) -> Self {
+        match value {
+            Detection::Peak => dynamics::Detection::Peak,
+            Detection::Rms => dynamics::Detection::Rms,
+        }
+    }
+}
+
+impl From<dynamics::Detection> for Detection {
+    fn from(value: dynamics::Detection) -> Self {
+        match value {
+            dynamics::Detection::Peak => Detection::Peak,
+            dynamics::Detection::Rms => Detection::Rms,
+        }
+    }
+}
+
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Detection>()?;
+    module.add_class::<Compressor>()?;
+    module.add_class::<Gate>()?;
+    module.add_class::<Limiter>()?;
+    Ok(())
+}
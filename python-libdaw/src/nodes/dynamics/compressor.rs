@@ -0,0 +1,121 @@
+use super::Detection;
+use crate::{time::Duration, Node};
+use libdaw::{nodes::dynamics::Compressor as Inner, time::Duration as DawDuration};
+use pyo3::{pyclass, pymethods, PyClassInitializer};
+use std::sync::{Arc, Mutex};
+
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.dynamics")]
+#[derive(Debug, Clone)]
+pub struct Compressor(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Compressor {
+    #[new]
+    #[pyo3(signature = (
+        threshold = -20.0,
+        ratio = 4.0,
+        knee = 6.0,
+        attack = Duration(DawDuration::from_seconds(0.01).expect("valid")),
+        release = Duration(DawDuration::from_seconds(0.1).expect("valid")),
+        makeup = 0.0,
+        detection = Detection::Rms,
+        sample_rate = 48000,
+    ))]
+    pub fn new(
+        threshold: f64,
+        ratio: f64,
+        knee: f64,
+        attack: Duration,
+        release: Duration,
+        makeup: f64,
+        detection: Detection,
+        sample_rate: u32,
+    ) -> PyClassInitializer<Self> {
+        let mut inner = Inner::new(sample_rate);
+        inner.threshold = threshold;
+        inner.ratio = ratio;
+        inner.knee = knee;
+        inner.attack = attack.0;
+        inner.release = release.0;
+        inner.makeup = makeup;
+        inner.detection = detection.into();
+        let inner = Arc::new(Mutex::new(inner));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (f64, f64, f64, Duration, Duration, f64, Detection, u32) {
+        let sample_rate = self.0.lock().expect("poisoned").sample_rate();
+        (
+            self.get_threshold(),
+            self.get_ratio(),
+            self.get_knee(),
+            self.get_attack(),
+            self.get_release(),
+            self.get_makeup(),
+            self.get_detection(),
+            sample_rate,
+        )
+    }
+
+    /// The static gain change for an input level, both in decibels.
+    pub fn gain(&self, level: f64) -> f64 {
+        self.0.lock().expect("poisoned").gain(level)
+    }
+
+    #[getter]
+    pub fn get_threshold(&self) -> f64 {
+        self.0.lock().expect("poisoned").threshold
+    }
+    #[setter]
+    pub fn set_threshold(&self, threshold: f64) {
+        self.0.lock().expect("poisoned").threshold = threshold;
+    }
+    #[getter]
+    pub fn get_ratio(&self) -> f64 {
+        self.0.lock().expect("poisoned").ratio
+    }
+    #[setter]
+    pub fn set_ratio(&self, ratio: f64) {
+        self.0.lock().expect("poisoned").ratio = ratio;
+    }
+    #[getter]
+    pub fn get_knee(&self) -> f64 {
+        self.0.lock().expect("poisoned").knee
+    }
+    #[setter]
+    pub fn set_knee(&self, knee: f64) {
+        self.0.lock().expect("poisoned").knee = knee;
+    }
+    #[getter]
+    pub fn get_attack(&self) -> Duration {
+        Duration(self.0.lock().expect("poisoned").attack)
+    }
+    #[setter]
+    pub fn set_attack(&self, attack: Duration) {
+        self.0.lock().expect("poisoned").attack = attack.0;
+    }
+    #[getter]
+    pub fn get_release(&self) -> Duration {
+        Duration(self.0.lock().expect("poisoned").release)
+    }
+    #[setter]
+    pub fn set_release(&self, release: Duration) {
+        self.0.lock().expect("poisoned").release = release.0;
+    }
+    #[getter]
+    pub fn get_makeup(&self) -> f64 {
+        self.0.lock().expect("poisoned").makeup
+    }
+    #[setter]
+    pub fn set_makeup(&self, makeup: f64) {
+        self.0.lock().expect("poisoned").makeup = makeup;
+    }
+    #[getter]
+    pub fn get_detection(&self) -> Detection {
+        self.0.lock().expect("poisoned").detection.into()
+    }
+    #[setter]
+    pub fn set_detection(&self, detection: Detection) {
+        self.0.lock().expect("poisoned").detection = detection.into();
+    }
+}
@@ -0,0 +1,122 @@
+use super::Detection;
+use crate::{time::Duration, Node};
+use libdaw::{nodes::dynamics::Gate as Inner, time::Duration as DawDuration};
+use pyo3::{pyclass, pymethods, PyClassInitializer};
+use std::sync::{Arc, Mutex};
+
+/// A noise gate, or a downward expander when given a finite ratio.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.dynamics")]
+#[derive(Debug, Clone)]
+pub struct Gate(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Gate {
+    #[new]
+    #[pyo3(signature = (
+        threshold = -40.0,
+        ratio = f64::INFINITY,
+        range = -80.0,
+        attack = Duration(DawDuration::from_seconds(0.001).expect("valid")),
+        hold = Duration(DawDuration::from_seconds(0.01).expect("valid")),
+        release = Duration(DawDuration::from_seconds(0.1).expect("valid")),
+        detection = Detection::Peak,
+        sample_rate = 48000,
+    ))]
+    pub fn new(
+        threshold: f64,
+        ratio: f64,
+        range: f64,
+        attack: Duration,
+        hold: Duration,
+        release: Duration,
+        detection: Detection,
+        sample_rate: u32,
+    ) -> PyClassInitializer<Self> {
+        let mut inner = Inner::new(sample_rate);
+        inner.threshold = threshold;
+        inner.ratio = ratio;
+        inner.range = range;
+        inner.attack = attack.0;
+        inner.hold = hold.0;
+        inner.release = release.0;
+        inner.detection = detection.into();
+        let inner = Arc::new(Mutex::new(inner));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (f64, f64, f64, Duration, Duration, Duration, Detection, u32) {
+        let sample_rate = self.0.lock().expect("poisoned").sample_rate();
+        (
+            self.get_threshold(),
+            self.get_ratio(),
+            self.get_range(),
+            self.get_attack(),
+            self.get_hold(),
+            self.get_release(),
+            self.get_detection(),
+            sample_rate,
+        )
+    }
+
+    /// The static gain change for an input level, both in decibels.
+    pub fn gain(&self, level: f64) -> f64 {
+        self.0.lock().expect("poisoned").gain(level)
+    }
+
+    #[getter]
+    pub fn get_threshold(&self) -> f64 {
+        self.0.lock().expect("poisoned").threshold
+    }
+    #[setter]
+    pub fn set_threshold(&self, threshold: f64) {
+        self.0.lock().expect("poisoned").threshold = threshold;
+    }
+    #[getter]
+    pub fn get_ratio(&self) -> f64 {
+        self.0.lock().expect("poisoned").ratio
+    }
+    #[setter]
+    pub fn set_ratio(&self, ratio: f64) {
+        self.0.lock().expect("poisoned").ratio = ratio;
+    }
+    #[getter]
+    pub fn get_range(&self) -> f64 {
+        self.0.lock().expect("poisoned").range
+    }
+    #[setter]
+    pub fn set_range(&self, range: f64) {
+        self.0.lock().expect("poisoned").range = range;
+    }
+    #[getter]
+    pub fn get_attack(&self) -> Duration {
+        Duration(self.0.lock().expect("poisoned").attack)
+    }
+    #[setter]
+    pub fn set_attack(&self, attack: Duration) {
+        self.0.lock().expect("poisoned").attack = attack.0;
+    }
+    #[getter]
+    pub fn get_hold(&self) -> Duration {
+        Duration(self.0.lock().expect("poisoned").hold)
+    }
+    #[setter]
+    pub fn set_hold(&self, hold: Duration) {
+        self.0.lock().expect("poisoned").hold = hold.0;
+    }
+    #[getter]
+    pub fn get_release(&self) -> Duration {
+        Duration(self.0.lock().expect("poisoned").release)
+    }
+    #[setter]
+    pub fn set_release(&self, release: Duration) {
+        self.0.lock().expect("poisoned").release = release.0;
+    }
+    #[getter]
+    pub fn get_detection(&self) -> Detection {
+        self.0.lock().expect("poisoned").detection.into()
+    }
+    #[setter]
+    pub fn set_detection(&self, detection: Detection) {
+        self.0.lock().expect("poisoned").detection = detection.into();
+    }
+}
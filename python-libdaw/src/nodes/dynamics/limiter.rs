@@ -0,0 +1,64 @@
+use crate::{time::Duration, Node};
+use libdaw::{nodes::dynamics::Limiter as Inner, time::Duration as DawDuration};
+use pyo3::{pyclass, pymethods, PyClassInitializer};
+use std::sync::{Arc, Mutex};
+
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.dynamics")]
+#[derive(Debug, Clone)]
+pub struct Limiter(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Limiter {
+    #[new]
+    #[pyo3(signature = (
+        lookahead = Duration(DawDuration::from_seconds(0.005).expect("valid")),
+        ceiling = -1.0,
+        release = Duration(DawDuration::from_seconds(0.05).expect("valid")),
+        sample_rate = 48000,
+    ))]
+    pub fn new(
+        lookahead: Duration,
+        ceiling: f64,
+        release: Duration,
+        sample_rate: u32,
+    ) -> PyClassInitializer<Self> {
+        let mut inner = Inner::new(sample_rate, lookahead.0);
+        inner.ceiling = ceiling;
+        inner.release = release.0;
+        let inner = Arc::new(Mutex::new(inner));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (Duration, f64, Duration, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (
+            Duration(inner.lookahead()),
+            inner.ceiling,
+            Duration(inner.release),
+            inner.sample_rate(),
+        )
+    }
+
+    /// The delay added by the look-ahead, in samples.
+    #[getter]
+    pub fn get_latency(&self) -> usize {
+        self.0.lock().expect("poisoned").latency()
+    }
+
+    #[getter]
+    pub fn get_ceiling(&self) -> f64 {
+        self.0.lock().expect("poisoned").ceiling
+    }
+    #[setter]
+    pub fn set_ceiling(&self, ceiling: f64) {
+        self.0.lock().expect("poisoned").ceiling = ceiling;
+    }
+    #[getter]
+    pub fn get_release(&self) -> Duration {
+        Duration(self.0.lock().expect("poisoned").release)
+    }
+    #[setter]
+    pub fn set_release(&self, release: Duration) {
+        self.0.lock().expect("poisoned").release = release.0;
+    }
+}
@@ -2,11 +2,11 @@ use crate::{
     time::{Duration, Time},
     Node,
 };
-use libdaw::nodes::envelope;
+use libdaw::{nodes::envelope, Node as _};
 use pyo3::{
     pyclass, pymethods,
     types::{PyAnyMethods as _, PyModule, PyModuleMethods as _},
-    Bound, FromPyObject, PyAny, PyClassInitializer, PyResult,
+    Bound, FromPyObject, IntoPy as _, PyAny, PyClassInitializer, PyObject, PyResult, Python,
 };
 use std::sync::{Arc, Mutex};
 
@@ -39,6 +39,14 @@ impl Point {
         })
     }
 
+    pub fn __getnewargs__(&self, py: Python<'_>) -> (f64, f64, PyObject) {
+        let offset = match self.0.offset {
+            envelope::Offset::Time(time) => Time(time).into_py(py),
+            envelope::Offset::Ratio(ratio) => ratio.into_py(py),
+        };
+        (self.0.whence, self.0.volume, offset)
+    }
+
     pub fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -64,6 +72,26 @@ impl Envelope {
         )));
         PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
     }
+
+    pub fn __getnewargs__(&self) -> (Duration, Vec<Point>, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (
+            Duration(inner.length()),
+            inner.points().iter().copied().map(Point).collect(),
+            inner.sample_rate(),
+        )
+    }
+
+    /// The sample the envelope has reached, which the restored envelope
+    /// seeks to.
+    pub fn __getstate__(&self) -> u64 {
+        self.0.lock().expect("poisoned").sample()
+    }
+
+    pub fn __setstate__(&self, sample: u64) -> crate::Result<()> {
+        self.0.lock().expect("poisoned").seek(sample)?;
+        Ok(())
+    }
 }
 
 pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -0,0 +1,144 @@
+use crate::{
+    metronome::{Beat, MaybeMetronome},
+    nodes::delay_line::Interpolation,
+    time::Duration,
+    Node,
+};
+use libdaw::nodes::FeedbackDelay as Inner;
+use pyo3::{pyclass, pymethods, Bound, Py, PyClassInitializer, PyTraverseError, PyVisit, Python};
+use std::sync::{Arc, Mutex};
+
+/// A delay line with feedback and a dry/wet mix.
+///
+/// The first input stream is the audio to be delayed.  The second input
+/// stream, if present, overrides the delay time in seconds.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
+#[derive(Debug, Clone)]
+pub struct FeedbackDelay {
+    inner: Arc<Mutex<Inner>>,
+    filter: Option<Py<Node>>,
+}
+
+#[pymethods]
+impl FeedbackDelay {
+    #[new]
+    #[pyo3(signature = (
+        max_delay,
+        delay,
+        feedback = 0.0,
+        mix = 0.5,
+        interpolation = Interpolation::Linear,
+        filter = None,
+        sample_rate = 48000,
+    ))]
+    pub fn new(
+        max_delay: Duration,
+        delay: Duration,
+        feedback: f64,
+        mix: f64,
+        interpolation: Interpolation,
+        filter: Option<Bound<'_, Node>>,
+        sample_rate: u32,
+    ) -> PyClassInitializer<Self> {
+        let mut inner = Inner::new(sample_rate, max_delay.0, delay.0);
+        inner.feedback = feedback;
+        inner.mix = mix;
+        inner.interpolation = interpolation.into();
+        inner.filter = filter.as_ref().map(|filter| filter.borrow().0.clone());
+        let inner = Arc::new(Mutex::new(inner));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self {
+            inner,
+            filter: filter.map(Bound::unbind),
+        })
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn __getnewargs__(
+        &self,
+        py: Python<'_>,
+    ) -> (
+        Duration,
+        Duration,
+        f64,
+        f64,
+        Interpolation,
+        Option<Py<Node>>,
+        u32,
+    ) {
+        let inner = self.inner.lock().expect("poisoned");
+        (
+            Duration(inner.max_delay()),
+            Duration(inner.delay),
+            inner.feedback,
+            inner.mix,
+            inner.interpolation.into(),
+            self.get_filter(py),
+            inner.sample_rate(),
+        )
+    }
+
+    /// Set the delay time to `length` beats, at the tempo in effect at
+    /// `beat`.
+    #[pyo3(signature = (length, beat = Beat(libdaw::metronome::Beat::ZERO), metronome = MaybeMetronome::default()))]
+    pub fn sync(&self, length: Beat, beat: Beat, metronome: MaybeMetronome) {
+        self.inner
+            .lock()
+            .expect("poisoned")
+            .sync(&metronome, beat.0, length.0);
+    }
+
+    #[getter]
+    pub fn get_delay(&self) -> Duration {
+        Duration(self.inner.lock().expect("poisoned").delay)
+    }
+    #[setter]
+    pub fn set_delay(&self, delay: Duration) {
+        self.inner.lock().expect("poisoned").delay = delay.0;
+    }
+    #[getter]
+    pub fn get_feedback(&self) -> f64 {
+        self.inner.lock().expect("poisoned").feedback
+    }
+    #[setter]
+    pub fn set_feedback(&self, feedback: f64) {
+        self.inner.lock().expect("poisoned").feedback = feedback;
+    }
+    #[getter]
+    pub fn get_mix(&self) -> f64 {
+        self.inner.lock().expect("poisoned").mix
+    }
+    #[setter]
+    pub fn set_mix(&self, mix: f64) {
+        self.inner.lock().expect("poisoned").mix = mix;
+    }
+    #[getter]
+    pub fn get_interpolation(&self) -> Interpolation {
+        self.inner.lock().expect("poisoned").interpolation.into()
+    }
+    #[setter]
+    pub fn set_interpolation(&self, interpolation: Interpolation) {
+        self.inner.lock().expect("poisoned").interpolation = interpolation.into();
+    }
+    #[getter]
+    pub fn get_filter(&self, py: Python<'_>) -> Option<Py<Node>> {
+        self.filter.as_ref().map(|filter| filter.clone_ref(py))
+    }
+    #[setter]
+    pub fn set_filter(&mut self, filter: Option<Bound<'_, Node>>) {
+        self.inner.lock().expect("poisoned").filter =
+            filter.as_ref().map(|filter| filter.borrow().0.clone());
+        self.filter = filter.map(Bound::unbind);
+    }
+
+    fn __traverse__(&self, visit: PyVisit<'_>) -> std::result::Result<(), PyTraverseError> {
+        if let Some(filter) = &self.filter {
+            visit.call(filter)?;
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        self.filter = None;
+        self.inner.lock().expect("poisoned").filter = None;
+    }
+}
@@ -19,4 +19,9 @@ impl HighPass {
         let inner = Arc::new(Mutex::new(Inner::new(sample_rate, order, frequency)?));
         Ok(PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner)))
     }
+
+    pub fn __getnewargs__(&self) -> (usize, f64, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (inner.order(), inner.frequency(), inner.sample_rate())
+    }
 }
@@ -27,4 +27,15 @@ impl BandStop {
         )?));
         Ok(PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner)))
     }
+
+    pub fn __getnewargs__(&self) -> (usize, f64, f64, f64, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (
+            inner.n(),
+            inner.epsilon(),
+            inner.low_frequency(),
+            inner.high_frequency(),
+            inner.sample_rate(),
+        )
+    }
 }
@@ -20,4 +20,14 @@ impl HighPass {
         let inner = Arc::new(Mutex::new(Inner::new(sample_rate, n, epsilon, frequency)?));
         Ok(PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner)))
     }
+
+    pub fn __getnewargs__(&self) -> (usize, f64, f64, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (
+            inner.n(),
+            inner.epsilon(),
+            inner.frequency(),
+            inner.sample_rate(),
+        )
+    }
 }
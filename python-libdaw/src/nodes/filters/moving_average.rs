@@ -15,4 +15,9 @@ impl MovingAverage {
         let inner = Arc::new(Mutex::new(Inner::new(sample_rate, window.0)));
         PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
     }
+
+    pub fn __getnewargs__(&self) -> (Duration, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (Duration(inner.window()), inner.sample_rate())
+    }
 }
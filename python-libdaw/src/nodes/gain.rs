@@ -15,6 +15,10 @@ impl Gain {
         PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
     }
 
+    pub fn __getnewargs__(&self) -> (f64,) {
+        (self.get_gain(),)
+    }
+
     #[getter]
     pub fn get_gain(&self) -> f64 {
         self.0.lock().expect("poisoned").gain
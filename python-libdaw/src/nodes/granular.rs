@@ -0,0 +1,231 @@
+use crate::{time::Duration, Node, Sample};
+use libdaw::nodes::{granular, Granular as Inner};
+use pyo3::{
+    exceptions::PyValueError,
+    pyclass, pymethods,
+    types::{PyModule, PyModuleMethods as _},
+    Bound, Py, PyClassInitializer, PyResult, Python,
+};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+#[pyclass(module = "libdaw.nodes.granular", eq, eq_int)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    Rectangle,
+    Triangle,
+    Hann,
+    Tukey,
+}
+
+#[pymethods]
+impl Window {
+    #[new]
+    pub fn new(name: &str) -> PyResult<Self> {
+        match name.to_lowercase().as_str() {
+            "rectangle" => Ok(Self::Rectangle),
+            "triangle" => Ok(Self::Triangle),
+            "hann" => Ok(Self::Hann),
+            "tukey" => Ok(Self::Tukey),
+            name => Err(PyValueError::new_err(format!("Unknown name {name}"))),
+        }
+    }
+
+    pub fn __getnewargs__(&self) -> (&str,) {
+        match self {
+            Window::Rectangle => ("rectangle",),
+            Window::Triangle => ("triangle",),
+            Window::Hann => ("hann",),
+            Window::Tukey => ("tukey",),
+        }
+    }
+}
+
+impl From<Window> for granular::Window {
+    fn from(value: Window) -> Self {
+        match value {
+            Window::Rectangle => granular::Window::Rectangle,
+            Window::Triangle => granular::Window::Triangle,
+            Window::Hann => granular::Window::Hann,
+            Window::Tukey => granular::Window::Tukey,
+        }
+    }
+}
+
+impl From<granular::Window> for Window {
+    fn from(value: granular::Window) -> Self {
+        match value {
+            granular::Window::Rectangle => Window::Rectangle,
+            granular::Window::Triangle => Window::Triangle,
+            granular::Window::Hann => Window::Hann,
+            granular::Window::Tukey => Window::Tukey,
+        }
+    }
+}
+
+/// A granular engine, spawning grains from an in-memory buffer.
+///
+/// Input streams 0 through 4 control the density, grain size, position,
+/// jitter, and pitch respectively, and stream 5 is captured into the buffer
+/// while recording.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
+#[derive(Debug, Clone)]
+pub struct Granular(pub Arc<Mutex<Inner>>);
+
+impl Granular {
+    fn from_inner(py: Python<'_>, inner: Inner) -> PyResult<Py<Self>> {
+        let inner = Arc::new(Mutex::new(inner));
+        Py::new(
+            py,
+            PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner)),
+        )
+    }
+}
+
+#[pymethods]
+impl Granular {
+    #[new]
+    #[pyo3(signature = (buffer, buffer_sample_rate = None, sample_rate = 48000))]
+    pub fn new(
+        buffer: Vec<Sample>,
+        buffer_sample_rate: Option<u32>,
+        sample_rate: u32,
+    ) -> PyClassInitializer<Self> {
+        let inner = Arc::new(Mutex::new(Inner::new(
+            sample_rate,
+            buffer.into_iter().map(|sample| sample.0).collect(),
+            buffer_sample_rate.unwrap_or(sample_rate),
+        )));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (Vec<Sample>, Option<u32>, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (
+            inner.buffer().iter().cloned().map(Sample).collect(),
+            Some(inner.buffer_sample_rate()),
+            inner.sample_rate(),
+        )
+    }
+
+    /// The grain parameters, which are not constructor arguments.
+    pub fn __getstate__(&self) -> (f64, Duration, f64, f64, f64, Window, bool) {
+        (
+            self.get_density(),
+            self.get_grain_size(),
+            self.get_position(),
+            self.get_jitter(),
+            self.get_pitch(),
+            self.get_window(),
+            self.get_recording(),
+        )
+    }
+
+    pub fn __setstate__(&self, state: (f64, Duration, f64, f64, f64, Window, bool)) {
+        let (density, grain_size, position, jitter, pitch, window, recording) = state;
+        self.set_density(density);
+        self.set_grain_size(grain_size);
+        self.set_position(position);
+        self.set_jitter(jitter);
+        self.set_pitch(pitch);
+        self.set_window(window);
+        self.set_recording(recording);
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (path, sample_rate = 48000))]
+    pub fn from_wav(py: Python<'_>, path: PathBuf, sample_rate: u32) -> crate::Result<Py<Self>> {
+        Ok(Self::from_inner(py, Inner::from_wav(sample_rate, path)?)?)
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (length, channels = 1, sample_rate = 48000))]
+    pub fn capture(
+        py: Python<'_>,
+        length: Duration,
+        channels: usize,
+        sample_rate: u32,
+    ) -> PyResult<Py<Self>> {
+        Self::from_inner(py, Inner::capture(sample_rate, length.0, channels))
+    }
+
+    pub fn seed(&self, seed: u64) {
+        self.0.lock().expect("poisoned").seed(seed);
+    }
+
+    #[getter]
+    pub fn get_buffer(&self) -> Vec<Sample> {
+        self.0
+            .lock()
+            .expect("poisoned")
+            .buffer()
+            .iter()
+            .cloned()
+            .map(Sample)
+            .collect()
+    }
+
+    #[getter]
+    pub fn get_density(&self) -> f64 {
+        self.0.lock().expect("poisoned").density
+    }
+    #[setter]
+    pub fn set_density(&self, density: f64) {
+        self.0.lock().expect("poisoned").density = density;
+    }
+    #[getter]
+    pub fn get_grain_size(&self) -> Duration {
+        Duration(self.0.lock().expect("poisoned").grain_size)
+    }
+    #[setter]
+    pub fn set_grain_size(&self, grain_size: Duration) {
+        self.0.lock().expect("poisoned").grain_size = grain_size.0;
+    }
+    #[getter]
+    pub fn get_position(&self) -> f64 {
+        self.0.lock().expect("poisoned").position
+    }
+    #[setter]
+    pub fn set_position(&self, position: f64) {
+        self.0.lock().expect("poisoned").position = position;
+    }
+    #[getter]
+    pub fn get_jitter(&self) -> f64 {
+        self.0.lock().expect("poisoned").jitter
+    }
+    #[setter]
+    pub fn set_jitter(&self, jitter: f64) {
+        self.0.lock().expect("poisoned").jitter = jitter;
+    }
+    #[getter]
+    pub fn get_pitch(&self) -> f64 {
+        self.0.lock().expect("poisoned").pitch
+    }
+    #[setter]
+    pub fn set_pitch(&self, pitch: f64) {
+        self.0.lock().expect("poisoned").pitch = pitch;
+    }
+    #[getter]
+    pub fn get_window(&self) -> Window {
+        self.0.lock().expect("poisoned").window.into()
+    }
+    #[setter]
+    pub fn set_window(&self, window: Window) {
+        self.0.lock().expect("poisoned").window = window.into();
+    }
+    #[getter]
+    pub fn get_recording(&self) -> bool {
+        self.0.lock().expect("poisoned").recording
+    }
+    #[setter]
+    pub fn set_recording(&self, recording: bool) {
+        self.0.lock().expect("poisoned").recording = recording;
+    }
+}
+
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Window>()?;
+    Ok(())
+}
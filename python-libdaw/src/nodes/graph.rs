@@ -1,12 +1,69 @@
 use crate::Node;
-use libdaw::nodes::graph::Graph as Inner;
-use pyo3::{pyclass, pymethods, Bound, PyClassInitializer};
-use std::sync::{Arc, Mutex};
+use libdaw::{nodes::graph::Graph as Inner, Node as DawNode};
+use pyo3::{
+    exceptions::PyTypeError, pyclass, pymethods, types::PyTuple, Bound, Py, PyClassInitializer,
+    PyResult, PyTraverseError, PyVisit, Python,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
+/// A `(source, destination, stream)` connection, where `None` is the graph's
+/// own input or output.
+type Connection = (Option<Py<Node>>, Option<Py<Node>>, Option<usize>);
+
+fn key(node: &Arc<Mutex<dyn DawNode>>) -> usize {
+    Arc::as_ptr(node) as *const () as usize
+}
+
+/// A graph of nodes.
+///
+/// Pickling keeps the topology, and pickles every node in the graph along
+/// with it.
 #[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
 #[derive(Debug, Clone)]
 pub struct Graph {
-    inner: Arc<Mutex<Inner>>,
+    pub inner: Arc<Mutex<Inner>>,
+
+    /// The Python objects of the nodes in the graph, by address.
+    nodes: HashMap<usize, Py<Node>>,
+}
+
+impl Graph {
+    fn track(&mut self, node: &Bound<'_, Node>) {
+        self.nodes
+            .entry(key(&node.borrow().0))
+            .or_insert_with(|| node.clone().unbind());
+    }
+
+    /// Forget the nodes that are no longer connected to anything.
+    fn prune(&mut self) {
+        let inner = self.inner.lock().expect("poisoned");
+        let connected: Vec<_> = inner
+            .connections()
+            .flat_map(|connection| [connection.source, connection.destination])
+            .flatten()
+            .map(|node| key(&node))
+            .collect();
+        self.nodes.retain(|key, _| connected.contains(key));
+    }
+
+    fn node(
+        &self,
+        py: Python<'_>,
+        node: Option<Arc<Mutex<dyn DawNode>>>,
+    ) -> PyResult<Option<Py<Node>>> {
+        node.map(|node| {
+            self.nodes
+                .get(&key(&node))
+                .map(|node| node.clone_ref(py))
+                .ok_or_else(|| {
+                    PyTypeError::new_err("can not pickle a node added outside of Python")
+                })
+        })
+        .transpose()
+    }
 }
 
 #[pymethods]
@@ -14,88 +71,158 @@ impl Graph {
     #[new]
     pub fn new() -> PyClassInitializer<Self> {
         let inner = Arc::new(Mutex::new(Inner::default()));
-        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self { inner })
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self {
+            inner,
+            nodes: HashMap::new(),
+        })
+    }
+
+    pub fn __getnewargs__<'py>(&self, py: Python<'py>) -> Bound<'py, PyTuple> {
+        PyTuple::empty_bound(py)
+    }
+
+    pub fn __getstate__(&self, py: Python<'_>) -> PyResult<Vec<Connection>> {
+        let inner = self.inner.lock().expect("poisoned");
+        inner
+            .connections()
+            .map(|connection| {
+                Ok((
+                    self.node(py, connection.source)?,
+                    self.node(py, connection.destination)?,
+                    connection.stream,
+                ))
+            })
+            .collect()
+    }
+
+    pub fn __setstate__(&mut self, py: Python<'_>, state: Vec<Connection>) -> crate::Result<()> {
+        for (source, destination, stream) in state {
+            let source = source.map(|node| node.into_bound(py));
+            let destination = destination.map(|node| node.into_bound(py));
+            match (source, destination) {
+                (Some(source), Some(destination)) => self.connect(source, destination, stream)?,
+                (None, Some(destination)) => self.input(destination, stream)?,
+                (Some(source), None) => self.output(source, stream)?,
+                (None, None) => (),
+            }
+        }
+        Ok(())
     }
 
     pub fn remove(&mut self, node: Bound<'_, Node>) -> bool {
         let node = node.borrow().0.clone();
-        self.inner.lock().expect("poisoned").remove(node)
+        let removed = self.inner.lock().expect("poisoned").remove(node);
+        self.prune();
+        removed
     }
 
     /// Connect the given output of the source to the destination.  The same
     /// output may be attached  multiple times. `None` will attach all outputs.
+    /// Nodes new to the graph are prepared with its context, if it has been
+    /// prepared.
     #[pyo3(signature = (source, destination, stream=None))]
     pub fn connect(
-        &self,
+        &mut self,
         source: Bound<'_, Node>,
         destination: Bound<'_, Node>,
         stream: Option<usize>,
-    ) {
-        let source = source.borrow().0.clone();
-        let destination = destination.borrow().0.clone();
-        self.inner
-            .lock()
-            .expect("poisoned")
-            .connect(source, destination, stream);
+    ) -> crate::Result<()> {
+        self.inner.lock().expect("poisoned").connect(
+            source.borrow().0.clone(),
+            destination.borrow().0.clone(),
+            stream,
+        )?;
+        self.track(&source);
+        self.track(&destination);
+        Ok(())
     }
 
     /// Disconnect the last-added matching connection, returning a boolean
     /// indicating if anything was disconnected.
     #[pyo3(signature = (source, destination, stream=None))]
     pub fn disconnect(
-        &self,
+        &mut self,
         source: Bound<'_, Node>,
         destination: Bound<'_, Node>,
         stream: Option<usize>,
     ) -> bool {
         let source = source.borrow().0.clone();
         let destination = destination.borrow().0.clone();
-        self.inner
-            .lock()
-            .expect("poisoned")
-            .disconnect(source, destination, stream)
+        let disconnected =
+            self.inner
+                .lock()
+                .expect("poisoned")
+                .disconnect(source, destination, stream);
+        self.prune();
+        disconnected
     }
 
     /// Connect the given output of the initial input to the destination.  The
     /// same output may be attached multiple times. `None` will attach all
     /// outputs.
     #[pyo3(signature = (destination, stream=None))]
-    pub fn input(&self, destination: Bound<'_, Node>, stream: Option<usize>) {
-        let destination = destination.borrow().0.clone();
+    pub fn input(
+        &mut self,
+        destination: Bound<'_, Node>,
+        stream: Option<usize>,
+    ) -> crate::Result<()> {
         self.inner
             .lock()
             .expect("poisoned")
-            .input(destination, stream);
+            .input(destination.borrow().0.clone(), stream)?;
+        self.track(&destination);
+        Ok(())
     }
 
     /// Disconnect the last-added matching connection from the destination,
     /// returning a boolean indicating if anything was disconnected.
     #[pyo3(signature = (destination, stream=None))]
-    pub fn remove_input(&self, destination: Bound<'_, Node>, stream: Option<usize>) -> bool {
+    pub fn remove_input(&mut self, destination: Bound<'_, Node>, stream: Option<usize>) -> bool {
         let destination = destination.borrow().0.clone();
-        self.inner
+        let removed = self
+            .inner
             .lock()
             .expect("poisoned")
-            .remove_input(destination, stream)
+            .remove_input(destination, stream);
+        self.prune();
+        removed
     }
 
     /// Connect the given output of the source to the final destinaton.  The
     /// same output may be attached multiple times. `None` will attach all
     /// outputs.
     #[pyo3(signature = (source, stream=None))]
-    pub fn output(&self, source: Bound<'_, Node>, stream: Option<usize>) {
-        let source = source.borrow().0.clone();
-        self.inner.lock().expect("poisoned").output(source, stream);
+    pub fn output(&mut self, source: Bound<'_, Node>, stream: Option<usize>) -> crate::Result<()> {
+        self.inner
+            .lock()
+            .expect("poisoned")
+            .output(source.borrow().0.clone(), stream)?;
+        self.track(&source);
+        Ok(())
     }
 
     /// Disconnect the last-added matching connection from the source, returning
     /// a boolean indicating if anything was disconnected.
     #[pyo3(signature = (source, stream=None))]
-    pub fn remove_output(&self, source: Bound<'_, Node>, stream: Option<usize>) -> bool {
+    pub fn remove_output(&mut self, source: Bound<'_, Node>, stream: Option<usize>) -> bool {
         let source = source.borrow().0.clone();
-        self.inner
+        let removed = self
+            .inner
             .lock()
             .expect("poisoned")
-            .remove_output(source, stream)
+            .remove_output(source, stream);
+        self.prune();
+        removed
+    }
+
+    fn __traverse__(&self, visit: PyVisit<'_>) -> std::result::Result<(), PyTraverseError> {
+        for node in self.nodes.values() {
+            visit.call(node)?;
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        self.nodes.clear();
     }
 }
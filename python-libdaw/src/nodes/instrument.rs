@@ -2,14 +2,14 @@ use crate::{
     time::{Duration, Timestamp},
     Node, Result,
 };
-use libdaw::nodes::instrument;
+use libdaw::{nodes::instrument, Node as _};
 use pyo3::{
     conversion::FromPyObject,
     pyclass, pymethods,
     types::{PyAny, PyAnyMethods as _, PyModule, PyModuleMethods as _},
     Bound, PyClassInitializer, PyObject, PyResult, PyTraverseError, PyVisit, Python,
 };
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
 
 #[pyclass(module = "libdaw.nodes.instrument")]
 #[derive(Debug, Clone, Copy)]
@@ -26,6 +26,14 @@ impl Tone {
         })
     }
 
+    pub fn __getnewargs__(&self) -> (Timestamp, Duration, f64) {
+        (
+            Timestamp(self.0.start),
+            Duration(self.0.length),
+            self.0.frequency,
+        )
+    }
+
     pub fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -60,22 +68,7 @@ impl Instrument {
             return Err("factory must be a callable".into());
         }
         let factory = Arc::new(factory.unbind());
-        let inner = {
-            let factory = Arc::downgrade(&factory);
-            Arc::new(Mutex::new(instrument::Instrument::new(
-                sample_rate,
-                move |tone| {
-                    if let Some(factory) = factory.upgrade() {
-                        Python::with_gil(|py| {
-                            let factory = factory.bind(py);
-                            Ok(Node::extract_bound(&factory.call1((Tone(tone),))?)?.0)
-                        })
-                    } else {
-                        Err("factory no longer exists".into())
-                    }
-                },
-            )))
-        };
+        let inner = Arc::new(Mutex::new(build(Arc::downgrade(&factory), sample_rate)));
         Ok(
             PyClassInitializer::from(Node(inner.clone())).add_subclass(Self {
                 inner,
@@ -84,8 +77,42 @@ impl Instrument {
         )
     }
 
-    pub fn add_tone(&self, tone: Tone) {
-        self.inner.lock().expect("poisoned").add_tone(tone.0);
+    pub fn __getnewargs__(&self, py: Python<'_>) -> (Option<PyObject>, u32) {
+        (
+            self.factory.as_ref().map(|factory| factory.clone_ref(py)),
+            self.with_inner(py, |inner| inner.sample_rate()),
+        )
+    }
+
+    /// Every tone ever added, including finished ones, and the sample the
+    /// instrument has reached.  The restored instrument seeks to that
+    /// sample, so voices are rebuilt from the factory and sounding ones are
+    /// sought to where they were, but anything else they kept, like filter
+    /// memory, starts fresh.
+    pub fn __getstate__(&self, py: Python<'_>) -> (Vec<Tone>, u64) {
+        self.with_inner(py, |inner| {
+            (
+                inner.tones().iter().copied().map(Tone).collect(),
+                inner.sample(),
+            )
+        })
+    }
+
+    pub fn __setstate__(&self, py: Python<'_>, state: (Vec<Tone>, u64)) -> Result<()> {
+        let (tones, sample) = state;
+        self.with_inner(py, |inner| {
+            for tone in tones {
+                inner.add_tone(tone.0);
+            }
+            inner.seek(sample)
+        })?;
+        Ok(())
+    }
+
+    /// Add a tone.  The factory is called for its voice shortly before the
+    /// tone starts playing.
+    pub fn add_tone(&self, py: Python<'_>, tone: Tone) {
+        self.with_inner(py, |inner| inner.add_tone(tone.0));
     }
 
     fn __traverse__(&self, visit: PyVisit<'_>) -> std::result::Result<(), PyTraverseError> {
@@ -101,6 +128,43 @@ impl Instrument {
     }
 }
 
+impl Instrument {
+    /// A new instrument with no tones, whose voices come from the same
+    /// factory.
+    pub fn empty(&self, sample_rate: u32) -> Result<instrument::Instrument> {
+        let factory = self
+            .factory
+            .as_ref()
+            .ok_or("the instrument's factory has been cleared")?;
+        Ok(build(Arc::downgrade(factory), sample_rate))
+    }
+
+    /// Lock the instrument without holding the GIL, since whatever is
+    /// processing it may be waiting on the factory, which needs the GIL.
+    fn with_inner<T: Send>(
+        &self,
+        py: Python<'_>,
+        function: impl FnOnce(&mut instrument::Instrument) -> T + Send,
+    ) -> T {
+        py.allow_threads(|| function(&mut self.inner.lock().expect("poisoned")))
+    }
+}
+
+/// An instrument that calls the factory for its voices for as long as the
+/// factory is kept alive elsewhere.
+fn build(factory: Weak<PyObject>, sample_rate: u32) -> instrument::Instrument {
+    instrument::Instrument::new(sample_rate, move |tone| {
+        if let Some(factory) = factory.upgrade() {
+            Python::with_gil(|py| {
+                let factory = factory.bind(py);
+                Ok(Node::extract_bound(&factory.call1((Tone(tone),))?)?.0)
+            })
+        } else {
+            Err("factory no longer exists".into())
+        }
+    })
+}
+
 pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_class::<Tone>()?;
     Ok(())
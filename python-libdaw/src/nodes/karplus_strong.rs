@@ -0,0 +1,88 @@
+use crate::Node;
+use libdaw::nodes::KarplusStrong as Inner;
+use pyo3::{pyclass, pymethods, PyClassInitializer};
+use std::sync::{Arc, Mutex};
+
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
+#[derive(Debug, Clone)]
+pub struct KarplusStrong(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl KarplusStrong {
+    #[new]
+    #[pyo3(signature = (
+        frequency = 0.0,
+        stretch = 0.5,
+        loss = 0.996,
+        pick_position = 0.0,
+        seed = None,
+        sample_rate = 48000,
+    ))]
+    pub fn new(
+        frequency: f64,
+        stretch: f64,
+        loss: f64,
+        pick_position: f64,
+        seed: Option<u64>,
+        sample_rate: u32,
+    ) -> PyClassInitializer<Self> {
+        let mut inner = Inner::new(sample_rate, frequency);
+        inner.stretch = stretch;
+        inner.loss = loss;
+        inner.pick_position = pick_position;
+        if let Some(seed) = seed {
+            inner.seed(seed);
+        }
+        let inner = Arc::new(Mutex::new(inner));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (f64, f64, f64, f64, Option<u64>, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (
+            inner.frequency,
+            inner.stretch,
+            inner.loss,
+            inner.pick_position,
+            None,
+            inner.sample_rate(),
+        )
+    }
+
+    pub fn pluck(&self) {
+        self.0.lock().expect("poisoned").pluck();
+    }
+
+    #[getter]
+    pub fn get_frequency(&self) -> f64 {
+        self.0.lock().expect("poisoned").frequency
+    }
+    #[setter]
+    pub fn set_frequency(&self, frequency: f64) {
+        self.0.lock().expect("poisoned").frequency = frequency;
+    }
+    #[getter]
+    pub fn get_stretch(&self) -> f64 {
+        self.0.lock().expect("poisoned").stretch
+    }
+    #[setter]
+    pub fn set_stretch(&self, stretch: f64) {
+        self.0.lock().expect("poisoned").stretch = stretch;
+    }
+    #[getter]
+    pub fn get_loss(&self) -> f64 {
+        self.0.lock().expect("poisoned").loss
+    }
+    #[setter]
+    pub fn set_loss(&self, loss: f64) {
+        self.0.lock().expect("poisoned").loss = loss;
+    }
+    #[getter]
+    pub fn get_pick_position(&self) -> f64 {
+        self.0.lock().expect("poisoned").pick_position
+    }
+    #[setter]
+    pub fn set_pick_position(&self, pick_position: f64) {
+        self.0.lock().expect("poisoned").pick_position = pick_position;
+    }
+}
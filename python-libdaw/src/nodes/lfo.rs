@@ -0,0 +1,220 @@
+use crate::{
+    metronome::{Beat, MaybeMetronome, Metronome},
+    Node,
+};
+use libdaw::{
+    nodes::{lfo, Lfo as Inner},
+    Node as _,
+};
+use pyo3::{
+    exceptions::PyValueError,
+    pyclass, pymethods,
+    types::{PyModule, PyModuleMethods as _},
+    Bound, PyClassInitializer, PyRef, PyResult,
+};
+use std::{
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
+
+#[pyclass(module = "libdaw.nodes.lfo", eq, eq_int)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Sine,
+    Triangle,
+    Saw,
+    Square,
+    SampleAndHold,
+    SmoothRandom,
+}
+
+#[pymethods]
+impl Shape {
+    #[new]
+    pub fn new(name: &str) -> PyResult<Self> {
+        match name.to_lowercase().as_str() {
+            "sine" => Ok(Self::Sine),
+            "triangle" => Ok(Self::Triangle),
+            "saw" => Ok(Self::Saw),
+            "square" => Ok(Self::Square),
+            "sample_and_hold" | "sampleandhold" => Ok(Self::SampleAndHold),
+            "smooth_random" | "smoothrandom" => Ok(Self::SmoothRandom),
+            name => Err(PyValueError::new_err(format!("Unknown name {name}"))),
+        }
+    }
+
+    pub fn __getnewargs__(&self) -> (&str,) {
+        match self {
+            Shape::Sine => ("sine",),
+            Shape::Triangle => ("triangle",),
+            Shape::Saw => ("saw",),
+            Shape::Square => ("square",),
+            Shape::SampleAndHold => ("sample_and_hold",),
+            Shape::SmoothRandom => ("smooth_random",),
+        }
+    }
+}
+
+impl From<Shape> for lfo::Shape {
+    fn from(value: Shape) -> Self {
+        match value {
+            Shape::Sine => lfo::Shape::Sine,
+            Shape::Triangle => lfo::Shape::Triangle,
+            Shape::Saw => lfo::Shape::Saw,
+            Shape::Square => lfo::Shape::Square,
+            Shape::SampleAndHold => lfo::Shape::SampleAndHold,
+            Shape::SmoothRandom => lfo::Shape::SmoothRandom,
+        }
+    }
+}
+
+impl From<lfo::Shape> for Shape {
+    fn from(value: lfo::Shape) -> Self {
+        match value {
+            lfo::Shape::Sine => Shape::Sine,
+            lfo::Shape::Triangle => Shape::Triangle,
+            lfo::Shape::Saw => Shape::Saw,
+            lfo::Shape::Square => Shape::Square,
+            lfo::Shape::SampleAndHold => Shape::SampleAndHold,
+            lfo::Shape::SmoothRandom => Shape::SmoothRandom,
+        }
+    }
+}
+
+/// A low frequency oscillator for control signals.
+///
+/// The first input stream, if present, overrides the rate in Hz.  The second
+/// input stream, if present, is a gate which restarts the cycle each time it
+/// rises above zero.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
+#[derive(Debug, Clone)]
+pub struct Lfo(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Lfo {
+    #[new]
+    #[pyo3(signature = (shape = Shape::Sine, frequency = 1.0, phase = 0.0, unipolar = false, sample_rate = 48000))]
+    pub fn new(
+        shape: Shape,
+        frequency: f64,
+        phase: f64,
+        unipolar: bool,
+        sample_rate: u32,
+    ) -> PyClassInitializer<Self> {
+        let mut inner = Inner::new(sample_rate, shape.into(), lfo::Rate::Hertz(frequency));
+        inner.phase = phase;
+        inner.unipolar = unipolar;
+        let inner = Arc::new(Mutex::new(inner));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (Shape, f64, f64, bool, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        let frequency = match inner.rate {
+            lfo::Rate::Hertz(frequency) => frequency,
+            lfo::Rate::Beats(_) => 1.0,
+        };
+        (
+            inner.shape.into(),
+            frequency,
+            inner.phase,
+            inner.unipolar,
+            inner.sample_rate(),
+        )
+    }
+
+    /// The beat sync, if the rate is in beats, and the sample the LFO has
+    /// reached, which the restored LFO seeks to.
+    pub fn __getstate__(&self) -> (Option<(Beat, Metronome)>, u64) {
+        let inner = self.0.lock().expect("poisoned");
+        let sync = match inner.rate {
+            lfo::Rate::Hertz(_) => None,
+            lfo::Rate::Beats(beats) => Some((Beat(beats), Metronome(inner.metronome.clone()))),
+        };
+        (sync, inner.sample())
+    }
+
+    pub fn __setstate__(
+        &self,
+        state: (Option<(Beat, PyRef<'_, Metronome>)>, u64),
+    ) -> crate::Result<()> {
+        let (sync, sample) = state;
+        let mut inner = self.0.lock().expect("poisoned");
+        if let Some((length, metronome)) = sync {
+            inner.rate = lfo::Rate::Beats(length.0);
+            inner.metronome = metronome.0.clone();
+        }
+        inner.seek(sample)?;
+        Ok(())
+    }
+
+    /// Run one cycle every `length` beats, following the metronome.
+    #[pyo3(signature = (length, metronome = MaybeMetronome::default()))]
+    pub fn sync(&self, length: Beat, metronome: MaybeMetronome) {
+        let mut inner = self.0.lock().expect("poisoned");
+        inner.rate = lfo::Rate::Beats(length.0);
+        inner.metronome = metronome.deref().clone();
+    }
+
+    /// Restart the cycle from the phase offset.
+    pub fn retrigger(&self) {
+        self.0.lock().expect("poisoned").retrigger();
+    }
+
+    /// Reseed the random shapes.
+    pub fn seed(&self, seed: u64) {
+        self.0.lock().expect("poisoned").seed(seed);
+    }
+
+    /// The rate in Hz, or None if the rate is in beats.
+    #[getter]
+    pub fn get_frequency(&self) -> Option<f64> {
+        match self.0.lock().expect("poisoned").rate {
+            lfo::Rate::Hertz(frequency) => Some(frequency),
+            lfo::Rate::Beats(_) => None,
+        }
+    }
+    #[setter]
+    pub fn set_frequency(&self, frequency: f64) {
+        self.0.lock().expect("poisoned").rate = lfo::Rate::Hertz(frequency);
+    }
+
+    /// The cycle length in beats, or None if the rate is in Hz.
+    #[getter]
+    pub fn get_beats(&self) -> Option<Beat> {
+        match self.0.lock().expect("poisoned").rate {
+            lfo::Rate::Hertz(_) => None,
+            lfo::Rate::Beats(beats) => Some(Beat(beats)),
+        }
+    }
+
+    #[getter]
+    pub fn get_shape(&self) -> Shape {
+        self.0.lock().expect("poisoned").shape.into()
+    }
+    #[setter]
+    pub fn set_shape(&self, shape: Shape) {
+        self.0.lock().expect("poisoned").shape = shape.into();
+    }
+    #[getter]
+    pub fn get_phase(&self) -> f64 {
+        self.0.lock().expect("poisoned").phase
+    }
+    #[setter]
+    pub fn set_phase(&self, phase: f64) {
+        self.0.lock().expect("poisoned").phase = phase;
+    }
+    #[getter]
+    pub fn get_unipolar(&self) -> bool {
+        self.0.lock().expect("poisoned").unipolar
+    }
+    #[setter]
+    pub fn set_unipolar(&self, unipolar: bool) {
+        self.0.lock().expect("poisoned").unipolar = unipolar;
+    }
+}
+
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Shape>()?;
+    Ok(())
+}
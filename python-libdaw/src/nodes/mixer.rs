@@ -0,0 +1,233 @@
+pub mod strip;
+
+pub use strip::{AuxSend, Strip};
+
+use strip::{Inserts, StripState};
+
+use crate::Node;
+use libdaw::nodes::{mixer, Mixer as Inner};
+use pyo3::{
+    pyclass, pymethods,
+    types::{PyModule, PyModuleMethods as _, PyTuple},
+    Bound, Py, PyClassInitializer, PyResult, PyTraverseError, PyVisit, Python,
+};
+use std::sync::{Arc, Mutex};
+
+/// A mixing console with channel strips, auxiliary buses, and a master bus.
+///
+/// Each input stream feeds the channel strip at the same index, in the order
+/// the channels were added.  The only output stream is the master bus.
+///
+/// Pickling keeps every strip's settings, inserts, and sends.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.mixer")]
+#[derive(Debug, Clone)]
+pub struct Mixer {
+    pub inner: Arc<Mutex<Inner>>,
+    inserts: Inserts,
+}
+
+/// The channels, buses, master, and `(channel, bus, level, pre_fader)` sends
+/// of a mixer.
+type MixerState = (
+    Vec<StripState>,
+    Vec<StripState>,
+    StripState,
+    Vec<(String, String, f64, bool)>,
+);
+
+impl Mixer {
+    fn strip(&self, py: Python<'_>, strip: Arc<Mutex<mixer::Strip>>) -> PyResult<Py<Strip>> {
+        Strip::from_inner(py, strip, self.inserts.clone())
+    }
+
+    fn forget(&self, strip: Option<Arc<Mutex<mixer::Strip>>>) {
+        if let Some(strip) = strip {
+            self.inserts
+                .lock()
+                .expect("poisoned")
+                .remove(&strip::key(&strip));
+        }
+    }
+}
+
+#[pymethods]
+impl Mixer {
+    #[new]
+    pub fn new() -> PyClassInitializer<Self> {
+        let inner = Arc::new(Mutex::new(Inner::default()));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self {
+            inner,
+            inserts: Default::default(),
+        })
+    }
+
+    pub fn __getnewargs__<'py>(&self, py: Python<'py>) -> Bound<'py, PyTuple> {
+        PyTuple::empty_bound(py)
+    }
+
+    pub fn __getstate__(&self, py: Python<'_>) -> MixerState {
+        let inner = self.inner.lock().expect("poisoned");
+        let channels = inner
+            .channel_names()
+            .filter_map(|name| inner.channel(name).map(|strip| (name, strip)))
+            .map(|(name, strip)| Strip::state(py, name.into(), &strip, &self.inserts))
+            .collect();
+        let buses = inner
+            .bus_names()
+            .filter_map(|name| inner.bus(name).map(|strip| (name, strip)))
+            .map(|(name, strip)| Strip::state(py, name.into(), &strip, &self.inserts))
+            .collect();
+        let master = Strip::state(py, String::new(), &inner.master(), &self.inserts);
+        let sends = inner
+            .channel_names()
+            .filter_map(|name| inner.channel(name).map(|strip| (name, strip)))
+            .flat_map(|(name, strip)| {
+                let strip = strip.lock().expect("poisoned");
+                strip
+                    .sends()
+                    .iter()
+                    .map(|send| {
+                        (
+                            name.to_string(),
+                            send.bus.clone(),
+                            send.level,
+                            send.pre_fader,
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        (channels, buses, master, sends)
+    }
+
+    pub fn __setstate__(&self, py: Python<'_>, state: MixerState) -> crate::Result<()> {
+        let (channels, buses, master, sends) = state;
+        let mut inner = self.inner.lock().expect("poisoned");
+        for state in buses {
+            let strip = inner.add_bus(state.0.clone())?;
+            Strip::restore(py, state, &strip, &self.inserts);
+        }
+        for state in channels {
+            let strip = inner.add_channel(state.0.clone())?;
+            Strip::restore(py, state, &strip, &self.inserts);
+        }
+        Strip::restore(py, master, &inner.master(), &self.inserts);
+        for (channel, bus, level, pre_fader) in sends {
+            inner.send(&channel, &bus, level, pre_fader)?;
+        }
+        Ok(())
+    }
+
+    /// Add a channel strip, fed by the next input stream.
+    pub fn add_channel(&self, py: Python<'_>, name: String) -> crate::Result<Py<Strip>> {
+        let strip = self.inner.lock().expect("poisoned").add_channel(name)?;
+        Ok(self.strip(py, strip)?)
+    }
+
+    /// Remove a channel strip.  Later channels move to the previous input
+    /// stream.
+    pub fn remove_channel(&self, name: &str) -> crate::Result<bool> {
+        let mut inner = self.inner.lock().expect("poisoned");
+        self.forget(inner.channel(name));
+        Ok(inner.remove_channel(name)?)
+    }
+
+    /// Add an auxiliary bus.
+    pub fn add_bus(&self, py: Python<'_>, name: String) -> crate::Result<Py<Strip>> {
+        let strip = self.inner.lock().expect("poisoned").add_bus(name)?;
+        Ok(self.strip(py, strip)?)
+    }
+
+    /// Remove an auxiliary bus, along with all sends to it.
+    pub fn remove_bus(&self, name: &str) -> crate::Result<bool> {
+        let mut inner = self.inner.lock().expect("poisoned");
+        self.forget(inner.bus(name));
+        Ok(inner.remove_bus(name)?)
+    }
+
+    pub fn channel(&self, py: Python<'_>, name: &str) -> PyResult<Option<Py<Strip>>> {
+        let strip = self.inner.lock().expect("poisoned").channel(name);
+        strip.map(|strip| self.strip(py, strip)).transpose()
+    }
+
+    pub fn bus(&self, py: Python<'_>, name: &str) -> PyResult<Option<Py<Strip>>> {
+        let strip = self.inner.lock().expect("poisoned").bus(name);
+        strip.map(|strip| self.strip(py, strip)).transpose()
+    }
+
+    #[getter]
+    pub fn get_master(&self, py: Python<'_>) -> PyResult<Py<Strip>> {
+        let strip = self.inner.lock().expect("poisoned").master();
+        self.strip(py, strip)
+    }
+
+    /// The channel names, in input stream order.
+    #[getter]
+    pub fn get_channels(&self) -> Vec<String> {
+        self.inner
+            .lock()
+            .expect("poisoned")
+            .channel_names()
+            .map(String::from)
+            .collect()
+    }
+
+    #[getter]
+    pub fn get_buses(&self) -> Vec<String> {
+        self.inner
+            .lock()
+            .expect("poisoned")
+            .bus_names()
+            .map(String::from)
+            .collect()
+    }
+
+    /// Send a channel to a bus, or update the existing send between them.
+    #[pyo3(signature = (channel, bus, level = 1.0, pre_fader = false))]
+    pub fn send(&self, channel: &str, bus: &str, level: f64, pre_fader: bool) -> crate::Result<()> {
+        Ok(self
+            .inner
+            .lock()
+            .expect("poisoned")
+            .send(channel, bus, level, pre_fader)?)
+    }
+
+    /// Remove the send from a channel to a bus.
+    pub fn remove_send(&self, channel: &str, bus: &str) -> crate::Result<bool> {
+        Ok(self
+            .inner
+            .lock()
+            .expect("poisoned")
+            .remove_send(channel, bus)?)
+    }
+
+    fn __traverse__(&self, visit: PyVisit<'_>) -> std::result::Result<(), PyTraverseError> {
+        for nodes in self.inserts.lock().expect("poisoned").values() {
+            for node in nodes {
+                visit.call(node)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        let mut inserts = self.inserts.lock().expect("poisoned");
+        let inner = self.inner.lock().expect("poisoned");
+        let strips = inner
+            .channel_names()
+            .filter_map(|name| inner.channel(name))
+            .chain(inner.bus_names().filter_map(|name| inner.bus(name)))
+            .chain([inner.master()]);
+        for strip in strips {
+            strip.lock().expect("poisoned").inserts.clear();
+        }
+        inserts.clear();
+    }
+}
+
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<AuxSend>()?;
+    module.add_class::<Mixer>()?;
+    module.add_class::<Strip>()?;
+    Ok(())
+}
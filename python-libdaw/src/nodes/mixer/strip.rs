@@ -0,0 +1,200 @@
+use crate::{nodes::channels::PanLaw, Node};
+use libdaw::nodes::mixer::{self, Strip as Inner};
+use pyo3::{
+    exceptions::PyTypeError, pyclass, pymethods, Bound, Py, PyClassInitializer, PyResult, Python,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// The Python insert nodes of every strip in a mixer, keyed by the strip's
+/// address.
+pub type Inserts = Arc<Mutex<HashMap<usize, Vec<Py<Node>>>>>;
+
+/// A strip's name, gain, pan, law, mute, solo, and inserts, as pickled by
+/// the mixer.
+pub type StripState = (String, f64, f64, PanLaw, bool, bool, Vec<Py<Node>>);
+
+pub fn key(strip: &Arc<Mutex<Inner>>) -> usize {
+    Arc::as_ptr(strip) as *const () as usize
+}
+
+/// A send from a channel strip to an auxiliary bus.
+#[pyclass(module = "libdaw.nodes.mixer")]
+#[derive(Debug, Clone)]
+pub struct AuxSend(pub mixer::AuxSend);
+
+#[pymethods]
+impl AuxSend {
+    #[getter]
+    pub fn get_bus(&self) -> String {
+        self.0.bus.clone()
+    }
+    #[getter]
+    pub fn get_level(&self) -> f64 {
+        self.0.level
+    }
+    #[getter]
+    pub fn get_pre_fader(&self) -> bool {
+        self.0.pre_fader
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// A mixer channel strip, used for channels, buses, and the master bus.
+///
+/// All input streams are summed and run through the inserts in order.  The
+/// first output stream is the signal after gain and pan, followed by one
+/// stream per send.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.mixer")]
+#[derive(Debug, Clone)]
+pub struct Strip {
+    pub inner: Arc<Mutex<Inner>>,
+    inserts: Inserts,
+}
+
+impl Strip {
+    pub fn from_inner(
+        py: Python<'_>,
+        inner: Arc<Mutex<Inner>>,
+        inserts: Inserts,
+    ) -> PyResult<Py<Self>> {
+        Py::new(
+            py,
+            PyClassInitializer::from(Node(inner.clone())).add_subclass(Self { inner, inserts }),
+        )
+    }
+
+    pub fn state(
+        py: Python<'_>,
+        name: String,
+        inner: &Arc<Mutex<Inner>>,
+        inserts: &Inserts,
+    ) -> StripState {
+        let nodes = inserts
+            .lock()
+            .expect("poisoned")
+            .get(&key(inner))
+            .map(|nodes| nodes.iter().map(|node| node.clone_ref(py)).collect())
+            .unwrap_or_default();
+        let inner = inner.lock().expect("poisoned");
+        (
+            name,
+            inner.gain,
+            inner.pan,
+            inner.law.into(),
+            inner.mute,
+            inner.solo,
+            nodes,
+        )
+    }
+
+    pub fn restore(
+        py: Python<'_>,
+        state: StripState,
+        inner: &Arc<Mutex<Inner>>,
+        inserts: &Inserts,
+    ) {
+        let (_, gain, pan, law, mute, solo, nodes) = state;
+        {
+            let mut inner = inner.lock().expect("poisoned");
+            inner.gain = gain;
+            inner.pan = pan;
+            inner.law = law.into();
+            inner.mute = mute;
+            inner.solo = solo;
+            inner.inserts = nodes.iter().map(|node| node.borrow(py).0.clone()).collect();
+        }
+        inserts.lock().expect("poisoned").insert(key(inner), nodes);
+    }
+}
+
+#[pymethods]
+impl Strip {
+    /// Strips belong to their mixer, which pickles them along with itself.
+    pub fn __reduce__(&self) -> PyResult<()> {
+        Err(PyTypeError::new_err(
+            "can not pickle a mixer strip on its own; pickle its mixer instead",
+        ))
+    }
+
+    /// Append a node to the insert chain.
+    pub fn add_insert(&self, node: Bound<'_, Node>) {
+        self.inner
+            .lock()
+            .expect("poisoned")
+            .inserts
+            .push(node.borrow().0.clone());
+        self.inserts
+            .lock()
+            .expect("poisoned")
+            .entry(key(&self.inner))
+            .or_default()
+            .push(node.unbind());
+    }
+
+    pub fn clear_inserts(&self) {
+        self.inner.lock().expect("poisoned").inserts.clear();
+        self.inserts
+            .lock()
+            .expect("poisoned")
+            .remove(&key(&self.inner));
+    }
+
+    #[getter]
+    pub fn get_sends(&self) -> Vec<AuxSend> {
+        self.inner
+            .lock()
+            .expect("poisoned")
+            .sends()
+            .iter()
+            .cloned()
+            .map(AuxSend)
+            .collect()
+    }
+
+    #[getter]
+    pub fn get_gain(&self) -> f64 {
+        self.inner.lock().expect("poisoned").gain
+    }
+    #[setter]
+    pub fn set_gain(&self, gain: f64) {
+        self.inner.lock().expect("poisoned").gain = gain;
+    }
+    #[getter]
+    pub fn get_pan(&self) -> f64 {
+        self.inner.lock().expect("poisoned").pan
+    }
+    #[setter]
+    pub fn set_pan(&self, pan: f64) {
+        self.inner.lock().expect("poisoned").pan = pan;
+    }
+    #[getter]
+    pub fn get_law(&self) -> PanLaw {
+        self.inner.lock().expect("poisoned").law.into()
+    }
+    #[setter]
+    pub fn set_law(&self, law: PanLaw) {
+        self.inner.lock().expect("poisoned").law = law.into();
+    }
+    #[getter]
+    pub fn get_mute(&self) -> bool {
+        self.inner.lock().expect("poisoned").mute
+    }
+    #[setter]
+    pub fn set_mute(&self, mute: bool) {
+        self.inner.lock().expect("poisoned").mute = mute;
+    }
+    #[getter]
+    pub fn get_solo(&self) -> bool {
+        self.inner.lock().expect("poisoned").solo
+    }
+    #[setter]
+    pub fn set_solo(&self, solo: bool) {
+        self.inner.lock().expect("poisoned").solo = solo;
+    }
+}
@@ -0,0 +1,19 @@
+pub mod chorus;
+pub mod flanger;
+pub mod phaser;
+
+pub use chorus::Chorus;
+pub use flanger::Flanger;
+pub use phaser::Phaser;
+
+use pyo3::{
+    types::{PyModule, PyModuleMethods as _},
+    Bound, PyResult,
+};
+
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Chorus>()?;
+    module.add_class::<Flanger>()?;
+    module.add_class::<Phaser>()?;
+    Ok(())
+}
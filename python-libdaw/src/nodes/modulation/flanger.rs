@@ -0,0 +1,103 @@
+use crate::{time::Duration, Node};
+use libdaw::{nodes::modulation::Flanger as Inner, time::Duration as DawDuration};
+use pyo3::{pyclass, pymethods, PyClassInitializer};
+use std::sync::{Arc, Mutex};
+
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.modulation")]
+#[derive(Debug, Clone)]
+pub struct Flanger(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Flanger {
+    #[new]
+    #[pyo3(signature = (
+        rate = 0.25,
+        delay = Duration(DawDuration::from_seconds(0.0025).expect("valid")),
+        depth = Duration(DawDuration::from_seconds(0.002).expect("valid")),
+        feedback = 0.5,
+        mix = 0.5,
+        stereo_phase = 0.25,
+        sample_rate = 48000,
+    ))]
+    pub fn new(
+        rate: f64,
+        delay: Duration,
+        depth: Duration,
+        feedback: f64,
+        mix: f64,
+        stereo_phase: f64,
+        sample_rate: u32,
+    ) -> PyClassInitializer<Self> {
+        let mut inner = Inner::new(sample_rate);
+        inner.rate = rate;
+        inner.delay = delay.0;
+        inner.depth = depth.0;
+        inner.feedback = feedback;
+        inner.mix = mix;
+        inner.stereo_phase = stereo_phase;
+        let inner = Arc::new(Mutex::new(inner));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (f64, Duration, Duration, f64, f64, f64, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (
+            inner.rate,
+            Duration(inner.delay),
+            Duration(inner.depth),
+            inner.feedback,
+            inner.mix,
+            inner.stereo_phase,
+            inner.sample_rate(),
+        )
+    }
+
+    #[getter]
+    pub fn get_rate(&self) -> f64 {
+        self.0.lock().expect("poisoned").rate
+    }
+    #[setter]
+    pub fn set_rate(&self, rate: f64) {
+        self.0.lock().expect("poisoned").rate = rate;
+    }
+    #[getter]
+    pub fn get_delay(&self) -> Duration {
+        Duration(self.0.lock().expect("poisoned").delay)
+    }
+    #[setter]
+    pub fn set_delay(&self, delay: Duration) {
+        self.0.lock().expect("poisoned").delay = delay.0;
+    }
+    #[getter]
+    pub fn get_depth(&self) -> Duration {
+        Duration(self.0.lock().expect("poisoned").depth)
+    }
+    #[setter]
+    pub fn set_depth(&self, depth: Duration) {
+        self.0.lock().expect("poisoned").depth = depth.0;
+    }
+    #[getter]
+    pub fn get_feedback(&self) -> f64 {
+        self.0.lock().expect("poisoned").feedback
+    }
+    #[setter]
+    pub fn set_feedback(&self, feedback: f64) {
+        self.0.lock().expect("poisoned").feedback = feedback;
+    }
+    #[getter]
+    pub fn get_mix(&self) -> f64 {
+        self.0.lock().expect("poisoned").mix
+    }
+    #[setter]
+    pub fn set_mix(&self, mix: f64) {
+        self.0.lock().expect("poisoned").mix = mix;
+    }
+    #[getter]
+    pub fn get_stereo_phase(&self) -> f64 {
+        self.0.lock().expect("poisoned").stereo_phase
+    }
+    #[setter]
+    pub fn set_stereo_phase(&self, stereo_phase: f64) {
+        self.0.lock().expect("poisoned").stereo_phase = stereo_phase;
+    }
+}
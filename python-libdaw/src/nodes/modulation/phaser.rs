@@ -0,0 +1,106 @@
+use crate::Node;
+use libdaw::nodes::modulation::Phaser as Inner;
+use pyo3::{pyclass, pymethods, PyClassInitializer};
+use std::sync::{Arc, Mutex};
+
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.modulation")]
+#[derive(Debug, Clone)]
+pub struct Phaser(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Phaser {
+    #[new]
+    #[pyo3(signature = (
+        stages = 4,
+        rate = 0.5,
+        min_frequency = 200.0,
+        max_frequency = 2000.0,
+        feedback = 0.5,
+        mix = 0.5,
+        stereo_phase = 0.25,
+        sample_rate = 48000,
+    ))]
+    pub fn new(
+        stages: usize,
+        rate: f64,
+        min_frequency: f64,
+        max_frequency: f64,
+        feedback: f64,
+        mix: f64,
+        stereo_phase: f64,
+        sample_rate: u32,
+    ) -> PyClassInitializer<Self> {
+        let mut inner = Inner::new(sample_rate, stages);
+        inner.rate = rate;
+        inner.min_frequency = min_frequency;
+        inner.max_frequency = max_frequency;
+        inner.feedback = feedback;
+        inner.mix = mix;
+        inner.stereo_phase = stereo_phase;
+        let inner = Arc::new(Mutex::new(inner));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (usize, f64, f64, f64, f64, f64, f64, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (
+            inner.stages(),
+            inner.rate,
+            inner.min_frequency,
+            inner.max_frequency,
+            inner.feedback,
+            inner.mix,
+            inner.stereo_phase,
+            inner.sample_rate(),
+        )
+    }
+
+    #[getter]
+    pub fn get_rate(&self) -> f64 {
+        self.0.lock().expect("poisoned").rate
+    }
+    #[setter]
+    pub fn set_rate(&self, rate: f64) {
+        self.0.lock().expect("poisoned").rate = rate;
+    }
+    #[getter]
+    pub fn get_min_frequency(&self) -> f64 {
+        self.0.lock().expect("poisoned").min_frequency
+    }
+    #[setter]
+    pub fn set_min_frequency(&self, min_frequency: f64) {
+        self.0.lock().expect("poisoned").min_frequency = min_frequency;
+    }
+    #[getter]
+    pub fn get_max_frequency(&self) -> f64 {
+        self.0.lock().expect("poisoned").max_frequency
+    }
+    #[setter]
+    pub fn set_max_frequency(&self, max_frequency: f64) {
+        self.0.lock().expect("poisoned").max_frequency = max_frequency;
+    }
+    #[getter]
+    pub fn get_feedback(&self) -> f64 {
+        self.0.lock().expect("poisoned").feedback
+    }
+    #[setter]
+    pub fn set_feedback(&self, feedback: f64) {
+        self.0.lock().expect("poisoned").feedback = feedback;
+    }
+    #[getter]
+    pub fn get_mix(&self) -> f64 {
+        self.0.lock().expect("poisoned").mix
+    }
+    #[setter]
+    pub fn set_mix(&self, mix: f64) {
+        self.0.lock().expect("poisoned").mix = mix;
+    }
+    #[getter]
+    pub fn get_stereo_phase(&self) -> f64 {
+        self.0.lock().expect("poisoned").stereo_phase
+    }
+    #[setter]
+    pub fn set_stereo_phase(&self, stereo_phase: f64) {
+        self.0.lock().expect("poisoned").stereo_phase = stereo_phase;
+    }
+}
@@ -1,8 +1,10 @@
+pub mod additive;
 pub mod sawtooth;
 pub mod sine;
 pub mod square;
 pub mod triangle;
 
+pub use additive::{Additive, Partial};
 pub use sawtooth::Sawtooth;
 pub use sine::Sine;
 pub use square::Square;
@@ -14,6 +16,8 @@ use pyo3::{
 };
 
 pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Additive>()?;
+    module.add_class::<Partial>()?;
     module.add_class::<Sawtooth>()?;
     module.add_class::<Sine>()?;
     module.add_class::<Square>()?;
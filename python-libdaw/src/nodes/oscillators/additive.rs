@@ -0,0 +1,119 @@
+use crate::{nodes::Envelope, Node};
+use libdaw::{
+    nodes::oscillators::{additive, Additive as Inner},
+    Node as _,
+};
+use pyo3::{pyclass, pymethods, Bound, Py, PyClassInitializer, PyResult, Python};
+use std::sync::{Arc, Mutex};
+
+#[pyclass(module = "libdaw.nodes.oscillators")]
+#[derive(Debug, Clone)]
+pub struct Partial(pub additive::Partial);
+
+#[pymethods]
+impl Partial {
+    #[new]
+    #[pyo3(signature = (ratio, amplitude = 1.0, phase = 0.0, envelope = None))]
+    pub fn new(
+        ratio: f64,
+        amplitude: f64,
+        phase: f64,
+        envelope: Option<Bound<'_, Envelope>>,
+    ) -> Self {
+        Self(additive::Partial {
+            ratio,
+            amplitude,
+            phase,
+            envelope: envelope
+                .map(|envelope| envelope.borrow().0.lock().expect("poisoned").clone()),
+        })
+    }
+
+    pub fn __getnewargs__(
+        &self,
+        py: Python<'_>,
+    ) -> PyResult<(f64, f64, f64, Option<Py<Envelope>>)> {
+        let envelope = self
+            .0
+            .envelope
+            .clone()
+            .map(|envelope| {
+                let inner = Arc::new(Mutex::new(envelope));
+                Py::new(
+                    py,
+                    PyClassInitializer::from(Node(inner.clone())).add_subclass(Envelope(inner)),
+                )
+            })
+            .transpose()?;
+        Ok((self.0.ratio, self.0.amplitude, self.0.phase, envelope))
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+
+    #[getter]
+    pub fn get_ratio(&self) -> f64 {
+        self.0.ratio
+    }
+    #[getter]
+    pub fn get_amplitude(&self) -> f64 {
+        self.0.amplitude
+    }
+    #[getter]
+    pub fn get_phase(&self) -> f64 {
+        self.0.phase
+    }
+}
+
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes.oscillators")]
+#[derive(Debug, Clone)]
+pub struct Additive(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Additive {
+    #[new]
+    #[pyo3(signature = (partials, sample_rate = 48000, frequency = 0.0))]
+    pub fn new(
+        partials: Vec<Partial>,
+        sample_rate: u32,
+        frequency: f64,
+    ) -> PyClassInitializer<Self> {
+        let inner = Arc::new(Mutex::new(Inner::new(
+            sample_rate,
+            frequency,
+            partials.into_iter().map(|partial| partial.0),
+        )));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (Vec<Partial>, u32, f64) {
+        let inner = self.0.lock().expect("poisoned");
+        (
+            inner.partials().cloned().map(Partial).collect(),
+            inner.sample_rate(),
+            inner.frequency,
+        )
+    }
+
+    /// The sample the oscillator has reached, which the restored oscillator
+    /// seeks to.
+    pub fn __getstate__(&self) -> u64 {
+        self.0.lock().expect("poisoned").sample()
+    }
+
+    pub fn __setstate__(&self, sample: u64) -> crate::Result<()> {
+        self.0.lock().expect("poisoned").seek(sample)?;
+        Ok(())
+    }
+
+    #[getter]
+    pub fn get_frequency(&self) -> f64 {
+        self.0.lock().expect("poisoned").frequency
+    }
+
+    #[setter]
+    pub fn set_frequency(&self, frequency: f64) {
+        self.0.lock().expect("poisoned").frequency = frequency;
+    }
+}
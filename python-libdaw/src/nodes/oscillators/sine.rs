@@ -1,5 +1,5 @@
 use crate::Node;
-use libdaw::nodes::oscillators::Sine as Inner;
+use libdaw::{nodes::oscillators::Sine as Inner, Node as _};
 use pyo3::{pyclass, pymethods, PyClassInitializer};
 use std::sync::{Arc, Mutex};
 
@@ -15,6 +15,22 @@ impl Sine {
         let inner = Arc::new(Mutex::new(Inner::new(sample_rate, frequency)));
         PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
     }
+
+    pub fn __getnewargs__(&self) -> (u32, f64) {
+        let inner = self.0.lock().expect("poisoned");
+        (inner.sample_rate(), inner.frequency)
+    }
+
+    /// The sample the oscillator has reached, which the restored oscillator
+    /// seeks to.
+    pub fn __getstate__(&self) -> u64 {
+        self.0.lock().expect("poisoned").sample()
+    }
+
+    pub fn __setstate__(&self, sample: u64) -> crate::Result<()> {
+        self.0.lock().expect("poisoned").seek(sample)?;
+        Ok(())
+    }
     #[getter]
     pub fn get_frequency(&self) -> f64 {
         self.0.lock().expect("poisoned").frequency
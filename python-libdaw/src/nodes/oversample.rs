@@ -0,0 +1,53 @@
+use crate::Node;
+use libdaw::nodes::Oversample as Inner;
+use pyo3::{pyclass, pymethods, Bound, Py, PyClassInitializer, PyTraverseError, PyVisit, Python};
+use std::sync::{Arc, Mutex};
+
+/// Runs a node, often a Graph, at an integer multiple of the outer sample
+/// rate, with anti-aliasing filters on every input and output stream.
+///
+/// The node must be constructed with the oversampled rate.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
+#[derive(Debug, Clone)]
+pub struct Oversample {
+    inner: Arc<Mutex<Inner>>,
+    node: Option<Py<Node>>,
+}
+
+#[pymethods]
+impl Oversample {
+    #[new]
+    #[pyo3(signature = (node, factor = 2))]
+    pub fn new(node: Bound<'_, Node>, factor: usize) -> PyClassInitializer<Self> {
+        let inner = Arc::new(Mutex::new(Inner::new(factor, node.borrow().0.clone())));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self {
+            inner,
+            node: Some(node.unbind()),
+        })
+    }
+
+    pub fn __getnewargs__(&self, py: Python<'_>) -> (Option<Py<Node>>, usize) {
+        (self.get_node(py), self.get_factor())
+    }
+
+    #[getter]
+    pub fn get_factor(&self) -> usize {
+        self.inner.lock().expect("poisoned").factor()
+    }
+
+    #[getter]
+    pub fn get_node(&self, py: Python<'_>) -> Option<Py<Node>> {
+        self.node.as_ref().map(|node| node.clone_ref(py))
+    }
+
+    fn __traverse__(&self, visit: PyVisit<'_>) -> std::result::Result<(), PyTraverseError> {
+        if let Some(node) = &self.node {
+            visit.call(node)?;
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        self.node = None;
+    }
+}
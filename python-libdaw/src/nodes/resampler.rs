@@ -0,0 +1,62 @@
+use crate::Node;
+use libdaw::nodes::Resampler as Inner;
+use pyo3::{pyclass, pymethods, Bound, Py, PyClassInitializer, PyTraverseError, PyVisit, Python};
+use std::sync::{Arc, Mutex};
+
+/// Runs a node at a different sample rate, converting its output streams
+/// with band-limited windowed-sinc interpolation.
+///
+/// The resampler's input streams are passed to the node unchanged each time
+/// it is processed.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    inner: Arc<Mutex<Inner>>,
+    node: Option<Py<Node>>,
+}
+
+#[pymethods]
+impl Resampler {
+    #[new]
+    #[pyo3(signature = (node, node_sample_rate, sample_rate = 48000))]
+    pub fn new(
+        node: Bound<'_, Node>,
+        node_sample_rate: u32,
+        sample_rate: u32,
+    ) -> PyClassInitializer<Self> {
+        let inner = Arc::new(Mutex::new(Inner::new(
+            sample_rate,
+            node_sample_rate,
+            node.borrow().0.clone(),
+        )));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self {
+            inner,
+            node: Some(node.unbind()),
+        })
+    }
+
+    pub fn __getnewargs__(&self, py: Python<'_>) -> (Option<Py<Node>>, u32, u32) {
+        let inner = self.inner.lock().expect("poisoned");
+        (
+            self.get_node(py),
+            inner.node_sample_rate(),
+            inner.sample_rate(),
+        )
+    }
+
+    #[getter]
+    pub fn get_node(&self, py: Python<'_>) -> Option<Py<Node>> {
+        self.node.as_ref().map(|node| node.clone_ref(py))
+    }
+
+    fn __traverse__(&self, visit: PyVisit<'_>) -> std::result::Result<(), PyTraverseError> {
+        if let Some(node) = &self.node {
+            visit.call(node)?;
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        self.node = None;
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{time::Duration, Node};
+use libdaw::{nodes::Reverb as Inner, time::Duration as DawDuration};
+use pyo3::{pyclass, pymethods, PyClassInitializer};
+use std::sync::{Arc, Mutex};
+
+/// A Schroeder-Moorer style algorithmic reverb, after Freeverb.
+///
+/// All channels of the input stream are summed into the reverb, and the
+/// output is always two channels.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
+#[derive(Debug, Clone)]
+pub struct Reverb(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Reverb {
+    #[new]
+    #[pyo3(signature = (
+        room_size = 0.5,
+        damping = 0.5,
+        pre_delay = Duration(DawDuration::ZERO),
+        width = 1.0,
+        wet = 1.0 / 3.0,
+        dry = 1.0,
+        sample_rate = 48000,
+    ))]
+    pub fn new(
+        room_size: f64,
+        damping: f64,
+        pre_delay: Duration,
+        width: f64,
+        wet: f64,
+        dry: f64,
+        sample_rate: u32,
+    ) -> PyClassInitializer<Self> {
+        let mut inner = Inner::new(sample_rate);
+        inner.room_size = room_size;
+        inner.damping = damping;
+        inner.pre_delay = pre_delay.0;
+        inner.width = width;
+        inner.wet = wet;
+        inner.dry = dry;
+        let inner = Arc::new(Mutex::new(inner));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    pub fn __getnewargs__(&self) -> (f64, f64, Duration, f64, f64, f64, u32) {
+        let inner = self.0.lock().expect("poisoned");
+        (
+            inner.room_size,
+            inner.damping,
+            Duration(inner.pre_delay),
+            inner.width,
+            inner.wet,
+            inner.dry,
+            inner.sample_rate(),
+        )
+    }
+
+    #[getter]
+    pub fn get_room_size(&self) -> f64 {
+        self.0.lock().expect("poisoned").room_size
+    }
+    #[setter]
+    pub fn set_room_size(&self, room_size: f64) {
+        self.0.lock().expect("poisoned").room_size = room_size;
+    }
+    #[getter]
+    pub fn get_damping(&self) -> f64 {
+        self.0.lock().expect("poisoned").damping
+    }
+    #[setter]
+    pub fn set_damping(&self, damping: f64) {
+        self.0.lock().expect("poisoned").damping = damping;
+    }
+    #[getter]
+    pub fn get_pre_delay(&self) -> Duration {
+        Duration(self.0.lock().expect("poisoned").pre_delay)
+    }
+    #[setter]
+    pub fn set_pre_delay(&self, pre_delay: Duration) {
+        self.0.lock().expect("poisoned").pre_delay = pre_delay.0;
+    }
+    #[getter]
+    pub fn get_width(&self) -> f64 {
+        self.0.lock().expect("poisoned").width
+    }
+    #[setter]
+    pub fn set_width(&self, width: f64) {
+        self.0.lock().expect("poisoned").width = width;
+    }
+    #[getter]
+    pub fn get_wet(&self) -> f64 {
+        self.0.lock().expect("poisoned").wet
+    }
+    #[setter]
+    pub fn set_wet(&self, wet: f64) {
+        self.0.lock().expect("poisoned").wet = wet;
+    }
+    #[getter]
+    pub fn get_dry(&self) -> f64 {
+        self.0.lock().expect("poisoned").dry
+    }
+    #[setter]
+    pub fn set_dry(&self, dry: f64) {
+        self.0.lock().expect("poisoned").dry = dry;
+    }
+}
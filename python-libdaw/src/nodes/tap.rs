@@ -0,0 +1,112 @@
+use crate::{Node, Sample};
+use libdaw::nodes::Tap as Inner;
+use pyo3::{exceptions::PyTypeError, pyclass, pymethods, PyClassInitializer, PyResult};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+/// Passes its input through unchanged, while recording it into memory or to
+/// a WAV file whenever it is armed.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
+#[derive(Debug, Clone)]
+pub struct Tap(pub Arc<Mutex<Inner>>);
+
+#[pymethods]
+impl Tap {
+    #[new]
+    #[pyo3(signature = (path = None, channels = 2))]
+    pub fn new(path: Option<PathBuf>, channels: u16) -> PyClassInitializer<Self> {
+        let inner = match path {
+            Some(path) => Inner::to_wav(path, channels),
+            None => Inner::new(),
+        };
+        let inner = Arc::new(Mutex::new(inner));
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self(inner))
+    }
+
+    /// Only taps recording into memory can be pickled.
+    pub fn __getstate__(&self) -> PyResult<(bool, bool, Vec<Sample>)> {
+        let inner = self.0.lock().expect("poisoned");
+        if inner.is_to_wav() {
+            return Err(PyTypeError::new_err(
+                "can not pickle a tap that records to a file",
+            ));
+        }
+        Ok((
+            inner.armed,
+            inner.is_finished(),
+            inner.captured().iter().cloned().map(Sample).collect(),
+        ))
+    }
+
+    pub fn __setstate__(&self, state: (bool, bool, Vec<Sample>)) -> crate::Result<()> {
+        let (armed, finished, captured) = state;
+        let mut inner = Inner::resume(captured.into_iter().map(|sample| sample.0).collect());
+        if finished {
+            inner.finish()?;
+        }
+        inner.armed = armed;
+        *self.0.lock().expect("poisoned") = inner;
+        Ok(())
+    }
+
+    /// Arm the tap, which fails once it is finished.
+    pub fn arm(&self) -> crate::Result<()> {
+        self.0.lock().expect("poisoned").arm()?;
+        Ok(())
+    }
+
+    pub fn disarm(&self) {
+        self.0.lock().expect("poisoned").disarm();
+    }
+
+    /// Take the audio recorded into memory, leaving the tap empty.
+    pub fn take(&self) -> Vec<Sample> {
+        self.0
+            .lock()
+            .expect("poisoned")
+            .take()
+            .into_iter()
+            .map(Sample)
+            .collect()
+    }
+
+    /// Disarm the tap for good and complete its WAV file, if it has one.
+    pub fn finish(&self) -> crate::Result<()> {
+        self.0.lock().expect("poisoned").finish()?;
+        Ok(())
+    }
+
+    #[getter]
+    pub fn get_captured(&self) -> Vec<Sample> {
+        self.0
+            .lock()
+            .expect("poisoned")
+            .captured()
+            .iter()
+            .cloned()
+            .map(Sample)
+            .collect()
+    }
+
+    #[getter]
+    pub fn get_armed(&self) -> bool {
+        self.0.lock().expect("poisoned").armed
+    }
+    #[setter]
+    pub fn set_armed(&self, armed: bool) -> crate::Result<()> {
+        let mut inner = self.0.lock().expect("poisoned");
+        if armed {
+            inner.arm()?;
+        } else {
+            inner.disarm();
+        }
+        Ok(())
+    }
+
+    #[getter]
+    pub fn get_finished(&self) -> bool {
+        self.0.lock().expect("poisoned").is_finished()
+    }
+}
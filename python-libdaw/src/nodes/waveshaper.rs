@@ -0,0 +1,189 @@
+use crate::Node;
+use libdaw::nodes::{waveshaper, Waveshaper as Inner};
+use pyo3::{
+    exceptions::PyTypeError,
+    pyclass, pymethods,
+    types::{PyAnyMethods as _, PyModule, PyModuleMethods as _, PyTuple},
+    Bound, FromPyObject, IntoPy, Py, PyAny, PyClassInitializer, PyObject, PyResult,
+    PyTraverseError, PyVisit, Python,
+};
+use std::sync::{Arc, Mutex};
+
+/// A built-in waveshaper transfer function.
+#[pyclass(module = "libdaw.nodes.waveshaper")]
+#[derive(Debug, Clone)]
+pub struct Curve(pub waveshaper::Curve);
+
+#[pymethods]
+impl Curve {
+    /// Smooth saturation.
+    #[staticmethod]
+    pub fn tanh() -> Self {
+        Self(waveshaper::Curve::Tanh)
+    }
+
+    /// Clamps to the range -1 to 1.
+    #[staticmethod]
+    pub fn hard_clip() -> Self {
+        Self(waveshaper::Curve::HardClip)
+    }
+
+    /// Reflects anything beyond -1 or 1 back into range.
+    #[staticmethod]
+    pub fn foldback() -> Self {
+        Self(waveshaper::Curve::Foldback)
+    }
+
+    /// A polynomial with the given coefficients, starting with the constant
+    /// term.
+    #[staticmethod]
+    pub fn polynomial(coefficients: Vec<f64>) -> Self {
+        Self(waveshaper::Curve::Polynomial(coefficients))
+    }
+
+    /// A sum of Chebyshev polynomials, with the given harmonic amplitudes
+    /// starting at the fundamental.
+    #[staticmethod]
+    pub fn chebyshev(amplitudes: Vec<f64>) -> Self {
+        Self(waveshaper::Curve::Chebyshev(amplitudes))
+    }
+
+    pub fn __call__(&self, input: f64) -> crate::Result<f64> {
+        Ok(self.0.apply(input)?)
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("Curve<{:?}>", self.0)
+    }
+
+    /// Rebuilt through the static method that made it.
+    pub fn __reduce__<'py>(
+        self_: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyTuple>)> {
+        let py = self_.py();
+        let (name, args) = match &self_.borrow().0 {
+            waveshaper::Curve::Tanh => ("tanh", PyTuple::empty_bound(py)),
+            waveshaper::Curve::HardClip => ("hard_clip", PyTuple::empty_bound(py)),
+            waveshaper::Curve::Foldback => ("foldback", PyTuple::empty_bound(py)),
+            waveshaper::Curve::Polynomial(coefficients) => (
+                "polynomial",
+                PyTuple::new_bound(py, [coefficients.clone().into_py(py)]),
+            ),
+            waveshaper::Curve::Chebyshev(amplitudes) => (
+                "chebyshev",
+                PyTuple::new_bound(py, [amplitudes.clone().into_py(py)]),
+            ),
+            waveshaper::Curve::Function(_) => {
+                return Err(PyTypeError::new_err("can not pickle a function curve"))
+            }
+        };
+        Ok((self_.get_type().getattr(name)?, args))
+    }
+}
+
+/// Either a built-in curve or a Python callable taking and returning a float.
+#[derive(Debug, FromPyObject)]
+pub enum CurveArgument {
+    Curve(Curve),
+    Function(Py<PyAny>),
+}
+
+/// Distorts each input stream through a transfer function, optionally
+/// oversampled to reduce aliasing.
+#[pyclass(extends = Node, subclass, module = "libdaw.nodes")]
+#[derive(Debug, Clone)]
+pub struct Waveshaper {
+    inner: Arc<Mutex<Inner>>,
+    function: Option<Py<PyAny>>,
+}
+
+impl Waveshaper {
+    fn to_inner(py: Python<'_>, curve: &CurveArgument) -> waveshaper::Curve {
+        match curve {
+            CurveArgument::Curve(curve) => curve.0.clone(),
+            CurveArgument::Function(function) => {
+                let function = function.clone_ref(py);
+                waveshaper::Curve::Function(Arc::new(move |input| {
+                    Python::with_gil(|py| Ok(function.bind(py).call1((input,))?.extract()?))
+                }))
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl Waveshaper {
+    #[new]
+    #[pyo3(signature = (curve = CurveArgument::Curve(Curve::tanh()), drive = 1.0, oversampling = 1))]
+    pub fn new(
+        py: Python<'_>,
+        curve: CurveArgument,
+        drive: f64,
+        oversampling: usize,
+    ) -> PyClassInitializer<Self> {
+        let mut inner = Inner::new(Self::to_inner(py, &curve), oversampling);
+        inner.drive = drive;
+        let inner = Arc::new(Mutex::new(inner));
+        let function = match curve {
+            CurveArgument::Curve(_) => None,
+            CurveArgument::Function(function) => Some(function),
+        };
+        PyClassInitializer::from(Node(inner.clone())).add_subclass(Self { inner, function })
+    }
+
+    pub fn __getnewargs__(&self, py: Python<'_>) -> (PyObject, f64, usize) {
+        (
+            self.get_curve(py),
+            self.get_drive(),
+            self.get_oversampling(),
+        )
+    }
+
+    #[getter]
+    pub fn get_curve(&self, py: Python<'_>) -> PyObject {
+        match &self.function {
+            Some(function) => function.clone_ref(py),
+            None => Curve(self.inner.lock().expect("poisoned").curve.clone()).into_py(py),
+        }
+    }
+
+    #[setter]
+    pub fn set_curve(&mut self, py: Python<'_>, curve: CurveArgument) {
+        self.inner.lock().expect("poisoned").curve = Self::to_inner(py, &curve);
+        self.function = match curve {
+            CurveArgument::Curve(_) => None,
+            CurveArgument::Function(function) => Some(function),
+        };
+    }
+
+    #[getter]
+    pub fn get_drive(&self) -> f64 {
+        self.inner.lock().expect("poisoned").drive
+    }
+
+    #[setter]
+    pub fn set_drive(&self, drive: f64) {
+        self.inner.lock().expect("poisoned").drive = drive;
+    }
+
+    #[getter]
+    pub fn get_oversampling(&self) -> usize {
+        self.inner.lock().expect("poisoned").oversampling()
+    }
+
+    fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
+        if let Some(function) = &self.function {
+            visit.call(function)?
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        self.function = None;
+    }
+}
+
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Curve>()?;
+    Ok(())
+}
@@ -1,10 +1,12 @@
 use crate::{
+    audio::Audio,
     metronome::{Beat, MaybeMetronome},
-    nodes::instrument::Tone,
+    nodes::{instrument::Tone, Instrument},
     pitch::MaybePitchStandard,
 };
 use libdaw::notation::{Element as Inner, ItemElement as DawItemElement};
-use pyo3::{pyclass, pymethods, types::PyAnyMethods as _, Bound, Python};
+use libdaw::output::Format;
+use pyo3::{pyclass, pymethods, types::PyAnyMethods as _, Bound, PyResult, Python};
 use std::{
     ops::Deref,
     sync::{Arc, Mutex},
@@ -85,6 +87,70 @@ impl Element {
             .collect()
     }
 
+    /// Render the section offline on an instrument, or a plain sine voice
+    /// if there is none, until its last tone ends.  The tones are played on
+    /// a new instrument with the same factory, so the given instrument is
+    /// left untouched.
+    #[pyo3(
+        signature = (
+            instrument=None,
+            *,
+            metronome=MaybeMetronome::default(),
+            pitch_standard=MaybePitchStandard::default(),
+            sample_rate=48000,
+            channels=2,
+            waveform=true,
+            spectrogram=false,
+        )
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub fn audio(
+        &self,
+        py: Python<'_>,
+        instrument: Option<Bound<'_, Instrument>>,
+        metronome: MaybeMetronome,
+        pitch_standard: MaybePitchStandard,
+        sample_rate: u32,
+        channels: u16,
+        waveform: bool,
+        spectrogram: bool,
+    ) -> PyResult<Audio> {
+        let tones = self
+            .tones(metronome, pitch_standard)
+            .into_iter()
+            .map(|tone| tone.0)
+            .collect();
+        Audio::play_tones(
+            py,
+            tones,
+            instrument
+                .map(|instrument| instrument.borrow().empty(sample_rate))
+                .transpose()?,
+            Format {
+                sample_rate,
+                channels,
+            },
+            waveform,
+            spectrogram,
+        )
+    }
+
+    /// Display as an audio player in a Jupyter notebook, played with the
+    /// defaults of `audio`.
+    pub fn _repr_html_(&self, py: Python<'_>) -> PyResult<String> {
+        let audio = self.audio(
+            py,
+            None,
+            Default::default(),
+            Default::default(),
+            48000,
+            2,
+            true,
+            false,
+        )?;
+        Ok(audio._repr_html_()?)
+    }
+
     pub fn length_(&self) -> Beat {
         Beat(
             self.inner
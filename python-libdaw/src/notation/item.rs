@@ -1,6 +1,9 @@
 use std::sync::{Arc, Mutex};
 
 use super::Element;
+use crate::{
+    audio::Audio, metronome::MaybeMetronome, nodes::Instrument, pitch::MaybePitchStandard,
+};
 use libdaw::notation::Item as DawItem;
 use pyo3::{
     exceptions::PyTypeError,
@@ -56,6 +59,45 @@ impl Item {
         let item: DawItem = source.parse()?;
         Ok(Self::from_inner(py, Arc::new(Mutex::new(item))))
     }
+    /// Render the item's element offline, as `Element.audio` does.
+    #[pyo3(
+        signature = (
+            instrument=None,
+            *,
+            metronome=MaybeMetronome::default(),
+            pitch_standard=MaybePitchStandard::default(),
+            sample_rate=48000,
+            channels=2,
+            waveform=true,
+            spectrogram=false,
+        )
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub fn audio(
+        &self,
+        py: Python<'_>,
+        instrument: Option<Bound<'_, Instrument>>,
+        metronome: MaybeMetronome,
+        pitch_standard: MaybePitchStandard,
+        sample_rate: u32,
+        channels: u16,
+        waveform: bool,
+        spectrogram: bool,
+    ) -> PyResult<Audio> {
+        self.get_element().bind(py).borrow().audio(
+            py,
+            instrument,
+            metronome,
+            pitch_standard,
+            sample_rate,
+            channels,
+            waveform,
+            spectrogram,
+        )
+    }
+    pub fn _repr_html_(&self, py: Python<'_>) -> PyResult<String> {
+        self.get_element().bind(py).borrow()._repr_html_(py)
+    }
     pub fn __repr__(&self) -> String {
         format!("{:?}", self.inner.lock().expect("poisoned"))
     }
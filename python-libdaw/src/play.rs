@@ -1,92 +1,22 @@
 use crate::{time::Duration, Node};
-use libdaw::Sample;
+use libdaw::{
+    input::Cpal,
+    output::{self, Backend, Format, Rodio, Tee, WavFile},
+};
 use pyo3::{pyfunction, Bound, PyResult, Python};
-use rodio::{OutputStream, Sink};
-use std::sync::mpsc::{sync_channel, Receiver};
-
-#[derive(Debug)]
-enum Message {
-    Sample(Sample),
-    Done,
-}
-
-#[derive(Debug, Default)]
-enum SourceSample {
-    #[default]
-    NotStarted,
-    Sample(<Sample as IntoIterator>::IntoIter),
-    Done,
-}
-
-/// Rodio audio source
-#[derive(Debug)]
-pub struct Source {
-    sample_rate: u32,
-    channels: u16,
-    receiver: Receiver<Message>,
-    sample: SourceSample,
-    duration: Option<std::time::Duration>,
-}
-
-impl Source {
-    fn refresh(&mut self) {
-        match &mut self.sample {
-            SourceSample::Sample(source_sample) => {
-                if source_sample.len() > 0 {
-                    return;
-                }
-            }
-            SourceSample::NotStarted => (),
-            SourceSample::Done => return,
-        }
-        match self.receiver.recv() {
-            Ok(Message::Sample(message_sample)) => {
-                self.sample = SourceSample::Sample(message_sample.into_iter())
-            }
-            Ok(Message::Done) | Err(_) => self.sample = SourceSample::Done,
-        }
-    }
-}
-
-impl rodio::source::Source for Source {
-    fn current_frame_len(&self) -> Option<usize> {
-        match &self.sample {
-            SourceSample::Sample(sample) => match sample.len() {
-                0 => None,
-                len => Some(len),
-            },
-            SourceSample::Done => Some(0),
-            SourceSample::NotStarted => None,
-        }
-    }
-
-    fn channels(&self) -> u16 {
-        self.channels
-    }
-
-    fn sample_rate(&self) -> u32 {
-        self.sample_rate
-    }
-
-    fn total_duration(&self) -> Option<std::time::Duration> {
-        self.duration
-    }
-}
-impl Iterator for Source {
-    type Item = f32;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.refresh();
-        match &mut self.sample {
-            SourceSample::Sample(sample) => Some(sample.next().unwrap() as f32),
-            SourceSample::Done | SourceSample::NotStarted => None,
-        }
-    }
-}
+use std::path::PathBuf;
 
 /// Play a node to the default speakers of the system.
+///
+/// The node is prepared for the given sample rate and channel count before
+/// playing, so it does not matter what sample rate it was constructed with.
+/// With `input_channels`, the default input device is captured and fed to
+/// the node as its input stream, and with `record`, what is played is also
+/// written to a WAV file at that path.  Other Python threads keep running
+/// while it plays, and Ctrl-C stops it.
 #[pyfunction]
-#[pyo3(signature = (node, sample_rate = 48000, channels=1, duration=None, grace_sleep=true))]
+#[pyo3(signature = (node, sample_rate = 48000, channels=1, duration=None, grace_sleep=true, input_channels=None, record=None))]
+#[allow(clippy::too_many_arguments)]
 pub fn play(
     py: Python,
     node: &Bound<'_, Node>,
@@ -94,61 +24,57 @@ pub fn play(
     channels: u16,
     duration: Option<Duration>,
     grace_sleep: bool,
+    input_channels: Option<u16>,
+    record: Option<PathBuf>,
 ) -> PyResult<()> {
-    let start = std::time::Instant::now();
-    let (_stream, stream_handle) =
-        OutputStream::try_default().map_err(crate::ErrorWrapper::from)?;
-    let sink = Sink::try_new(&stream_handle).map_err(crate::ErrorWrapper::from)?;
-    let (sender, receiver) = sync_channel(sample_rate as usize * 10);
-    let duration = duration.map(|duration| duration.0);
-    let samples = duration
-        .map(|duration| (duration.seconds() * sample_rate as f64) as u64)
-        .unwrap_or(u64::MAX);
-    let duration = duration
-        .map(std::time::Duration::try_from)
-        .transpose()
-        .map_err(crate::ErrorWrapper::from)?;
-    sink.append(Source {
+    let format = Format {
         sample_rate,
         channels,
-        receiver,
-        sample: SourceSample::NotStarted,
-        duration,
-    });
-    let node = node.borrow();
-    let mut node = node.0.lock().expect("poisoned");
-    let mut outputs = Vec::new();
-    for _ in 0..samples {
-        py.check_signals()?;
-        outputs.clear();
-        node.process(&[], &mut outputs)
-            .map_err(crate::ErrorWrapper::from)?;
-        let mut sample = outputs
-            .iter()
-            .fold(None, move |acc, stream| match acc {
-                Some(acc) => Some(acc + stream),
-                None => Some(stream.clone()),
+    };
+    let length = duration.map(|duration| (duration.0.seconds() * sample_rate as f64) as u64);
+    let node = node.borrow().0.clone();
+    crate::render_without_gil(py, move |check| {
+        let mut backend = Rodio::open(format)?;
+        let mut input = input_channels
+            .map(|channels| {
+                Cpal::open(Format {
+                    sample_rate,
+                    channels,
+                })
             })
-            .unwrap_or_else(move || Sample::default());
-        sample.channels.resize(channels as usize, 0.0);
+            .transpose()?;
+        let mut recording = record
+            .map(|path| WavFile::create(path, format))
+            .transpose()?;
+        let mut node = node.lock().expect("poisoned");
+        let mut speakers_and_file;
+        let output: &mut dyn Backend = match &mut recording {
+            Some(recording) => {
+                speakers_and_file = Tee(&mut backend, recording);
+                &mut speakers_and_file
+            }
+            None => &mut backend,
+        };
+        match &mut input {
+            Some(input) => output::play_through(&mut *node, input, output, length, &mut *check)?,
+            None => output::play(&mut *node, output, length, &mut *check)?,
+        }
+        if let Some(recording) = &mut recording {
+            recording.finish()?;
+        }
 
-        sender
-            .send(Message::Sample(sample))
-            .map_err(crate::ErrorWrapper::from)?;
-    }
-    sender
-        .send(Message::Done)
-        .map_err(crate::ErrorWrapper::from)?;
-    while let Some(duration) = duration.and_then(|duration| duration.checked_sub(start.elapsed())) {
-        // Still have some time left, sleep in hundredths of seconds so we
-        // can check for ctrl-c still.
-        py.check_signals()?;
-        std::thread::sleep(duration.min(std::time::Duration::from_millis(10)));
-    }
+        backend.close();
+        while !backend.done() {
+            // Sleep in hundredths of seconds so we can check for ctrl-c still.
+            check()?;
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
 
-    // For some reason, rodio still gives an ugly cutoff if we don't do this
-    if grace_sleep {
-        std::thread::sleep(std::time::Duration::from_millis(100));
-    }
+        // For some reason, rodio still gives an ugly cutoff if we don't do this
+        if grace_sleep {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        Ok(())
+    })?;
     Ok(())
 }
@@ -0,0 +1,71 @@
+use crate::{nodes::Graph, time::Duration, Node};
+use libdaw::output::{self, Backend, Format, Null, WavFile};
+use pyo3::{exceptions::PyValueError, pyfunction, Bound, PyResult};
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::PathBuf,
+};
+
+/// Render a graph in a single pass, writing its output and the output of
+/// each named node within it to its own WAV file in `directory`.
+///
+/// Every file is named after its stem with `.wav` appended, and the graph's
+/// own output is written as `master`, unless `master` is None.  No files are
+/// created if any two would share a path.  The files are sample-aligned.
+/// Other Python threads keep running during the render, and Ctrl-C
+/// interrupts it.
+#[pyfunction]
+#[pyo3(signature = (graph, directory, stems, duration, sample_rate = 48000, channels = 2, master = Some("master".into())))]
+pub fn render_stems(
+    graph: &Bound<'_, Graph>,
+    directory: PathBuf,
+    stems: BTreeMap<String, Bound<'_, Node>>,
+    duration: Duration,
+    sample_rate: u32,
+    channels: u16,
+    master: Option<String>,
+) -> PyResult<()> {
+    let format = Format {
+        sample_rate,
+        channels,
+    };
+    let path = |name: &str| directory.join(format!("{name}.wav"));
+    let master_path = master.as_deref().map(path);
+    let stem_paths: Vec<_> = stems.keys().map(|name| path(name)).collect();
+    let mut seen = HashSet::new();
+    for path in master_path.iter().chain(&stem_paths) {
+        if !seen.insert(path) {
+            return Err(PyValueError::new_err(format!(
+                "more than one file would be written to {}",
+                path.display()
+            )));
+        }
+    }
+    let master = master_path
+        .map(|path| WavFile::create(path, format))
+        .transpose()
+        .map_err(crate::ErrorWrapper::from)?;
+    let mut files = stem_paths
+        .into_iter()
+        .map(|path| WavFile::create(path, format))
+        .collect::<libdaw::Result<Vec<_>>>()
+        .map_err(crate::ErrorWrapper::from)?;
+    let nodes: Vec<_> = stems.values().map(|node| node.borrow().0.clone()).collect();
+    let length = (duration.0.seconds() * sample_rate as f64) as u64;
+    let py = graph.py();
+    let graph = graph.borrow().inner.clone();
+    crate::render_without_gil(py, move |check| {
+        let mut master: Box<dyn Backend> = match master {
+            Some(file) => Box::new(file),
+            None => Box::new(Null::new(format)),
+        };
+        let mut stems: Vec<_> = nodes
+            .into_iter()
+            .zip(&mut files)
+            .map(|(node, file)| (node, file as &mut dyn Backend))
+            .collect();
+        let mut graph = graph.lock().expect("poisoned");
+        output::render_stems(&mut graph, length, &mut *master, &mut stems, check)
+    })?;
+    Ok(())
+}
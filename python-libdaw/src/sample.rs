@@ -1,7 +1,11 @@
-use crate::indexing::{IndexOrSlice, InsertIndex, ItemOrSequence, PopIndex};
+use crate::{
+    frames::Frames,
+    indexing::{IndexOrSlice, InsertIndex, ItemOrSequence, PopIndex},
+};
 use libdaw::Sample as DawStream;
 use pyo3::{
     exceptions::PyValueError, pyclass, pymethods, types::PyAnyMethods as _, Bound, PyAny, PyResult,
+    Python,
 };
 
 #[derive(Debug, Clone)]
@@ -82,6 +86,25 @@ impl Sample {
         Ok(())
     }
 
+    /// Convert to a one-dimensional NumPy array of the channels, which is
+    /// always a new array.
+    #[pyo3(signature = (dtype=None, copy=None))]
+    pub fn __array__<'py>(
+        &self,
+        py: Python<'py>,
+        dtype: Option<Bound<'py, PyAny>>,
+        copy: Option<bool>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let _ = copy;
+        let array = Frames::new(std::slice::from_ref(&self.0))
+            .into_array(py)?
+            .get_item(0)?;
+        match dtype {
+            Some(dtype) => array.call_method1("astype", (dtype,)),
+            None => Ok(array),
+        }
+    }
+
     pub fn __getnewargs__(&self) -> (Vec<f64>,) {
         (self.0.iter().copied().collect(),)
     }
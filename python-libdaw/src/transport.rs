@@ -0,0 +1,226 @@
+use crate::{
+    metronome::{Beat, MaybeMetronome, Metronome},
+    time::Timestamp,
+    Node,
+};
+use libdaw::{
+    metronome::Metronome as DawMetronome,
+    output::{Format, Rodio},
+    time::Timestamp as DawTimestamp,
+    transport::{Output, Transport as DawTransport},
+};
+use pyo3::{
+    exceptions::PyValueError, pyclass, pymethods, Bound, FromPyObject, Py, PyResult,
+    PyTraverseError, PyVisit, Python,
+};
+use std::ops::Deref as _;
+
+/// A position on the timeline, either in time or in beats.
+#[derive(Debug, FromPyObject)]
+pub enum Position {
+    Timestamp(Timestamp),
+    Beat(Beat),
+}
+
+/// A non-blocking transport for real-time playback.
+///
+/// Audio is rendered from the node to the system's speakers on a background
+/// thread that does not hold the GIL, while playback is controlled from
+/// Python.  The node is prepared for the transport's sample rate and channel
+/// count.  Rendering runs a few blocks ahead of the speakers, so controls
+/// take effect after a short delay.
+#[pyclass(module = "libdaw")]
+#[derive(Debug)]
+pub struct Transport {
+    inner: DawTransport,
+    node: Option<Py<Node>>,
+    metronome: DawMetronome,
+    output: Option<Output>,
+}
+
+impl Transport {
+    /// Run a function on the transport without holding the GIL, as the
+    /// output thread may be waiting on the GIL while it holds the playback
+    /// state.
+    fn with_inner<T: Send>(
+        &self,
+        py: Python<'_>,
+        function: impl FnOnce(&DawTransport) -> T + Send,
+    ) -> T {
+        let inner = self.inner.clone();
+        py.allow_threads(move || function(&inner))
+    }
+
+    /// Raise the last processing error, if there was one.
+    fn check(&self, py: Python<'_>) -> crate::Result<()> {
+        match self.with_inner(py, DawTransport::take_error) {
+            Some(error) => Err(error.into()),
+            None => Ok(()),
+        }
+    }
+
+    fn to_sample(&self, position: Position) -> u64 {
+        let timestamp = match position {
+            Position::Timestamp(timestamp) => timestamp.0,
+            Position::Beat(beat) => self.metronome.beat_to_time(beat.0),
+        };
+        (timestamp.seconds() * self.inner.format().sample_rate as f64) as u64
+    }
+
+    fn to_timestamp(&self, sample: u64) -> Timestamp {
+        Timestamp(
+            DawTimestamp::from_seconds(sample as f64 / self.inner.format().sample_rate as f64)
+                .expect("sample position is a valid timestamp"),
+        )
+    }
+}
+
+#[pymethods]
+impl Transport {
+    #[new]
+    #[pyo3(signature = (node, sample_rate = 48000, channels = 2, metronome = MaybeMetronome::default()))]
+    pub fn new(
+        node: Bound<'_, Node>,
+        sample_rate: u32,
+        channels: u16,
+        metronome: MaybeMetronome,
+    ) -> crate::Result<Self> {
+        let format = Format {
+            sample_rate,
+            channels,
+        };
+        let inner = DawTransport::new(node.borrow().0.clone(), format)?;
+        Ok(Self {
+            inner,
+            node: Some(node.unbind()),
+            metronome: metronome.deref().clone(),
+            output: None,
+        })
+    }
+
+    /// Start or resume playback, opening the audio output if needed.
+    pub fn start(&mut self, py: Python<'_>) -> crate::Result<()> {
+        self.check(py)?;
+        if self.output.is_none() {
+            self.output = Some(self.with_inner(py, |inner| inner.open(Rodio::open))?);
+        }
+        self.with_inner(py, DawTransport::play);
+        Ok(())
+    }
+
+    /// Pause playback, keeping the position.
+    pub fn pause(&self, py: Python<'_>) -> crate::Result<()> {
+        self.check(py)?;
+        self.with_inner(py, DawTransport::pause);
+        Ok(())
+    }
+
+    /// Stop playback, rewind to the start, and close the audio output.
+    pub fn stop(&mut self, py: Python<'_>) -> crate::Result<()> {
+        self.output = None;
+        self.with_inner(py, |inner| {
+            inner.pause();
+            inner.seek(0)
+        })?;
+        self.check(py)
+    }
+
+    /// Move playback to a timestamp or beat.
+    pub fn seek(&self, py: Python<'_>, position: Position) -> crate::Result<()> {
+        self.check(py)?;
+        let sample = self.to_sample(position);
+        self.with_inner(py, |inner| inner.seek(sample))?;
+        Ok(())
+    }
+
+    /// Loop playback between two positions.
+    #[pyo3(name = "loop")]
+    pub fn set_loop(&self, py: Python<'_>, start: Position, end: Position) -> PyResult<()> {
+        let start = self.to_sample(start);
+        let end = self.to_sample(end);
+        if end <= start {
+            return Err(PyValueError::new_err("loop end must be after its start"));
+        }
+        self.with_inner(py, |inner| inner.set_loop(Some((start, end))))
+            .expect("loop region was checked");
+        Ok(())
+    }
+
+    /// Stop looping, letting playback continue past the loop end.
+    pub fn clear_loop(&self, py: Python<'_>) {
+        self.with_inner(py, |inner| inner.set_loop(None))
+            .expect("clearing the loop can not fail");
+    }
+
+    #[getter]
+    pub fn get_loop_region(&self, py: Python<'_>) -> Option<(Timestamp, Timestamp)> {
+        self.with_inner(py, DawTransport::loop_region)
+            .map(|(start, end)| (self.to_timestamp(start), self.to_timestamp(end)))
+    }
+
+    #[getter]
+    pub fn get_playing(&self, py: Python<'_>) -> bool {
+        self.with_inner(py, DawTransport::playing)
+    }
+
+    /// The current position, in samples.
+    #[getter]
+    pub fn get_sample(&self, py: Python<'_>) -> u64 {
+        self.with_inner(py, DawTransport::sample)
+    }
+
+    #[getter]
+    pub fn get_position(&self, py: Python<'_>) -> Timestamp {
+        self.to_timestamp(self.get_sample(py))
+    }
+
+    #[getter]
+    pub fn get_beat(&self, py: Python<'_>) -> Beat {
+        Beat(self.metronome.time_to_beat(self.get_position(py).0))
+    }
+
+    #[getter]
+    pub fn get_sample_rate(&self) -> u32 {
+        self.inner.format().sample_rate
+    }
+
+    #[getter]
+    pub fn get_channels(&self) -> u16 {
+        self.inner.format().channels
+    }
+
+    #[getter]
+    pub fn get_metronome(&self) -> Metronome {
+        Metronome(self.metronome.clone())
+    }
+
+    #[setter]
+    pub fn set_metronome(&mut self, metronome: MaybeMetronome) {
+        self.metronome = metronome.deref().clone();
+    }
+
+    #[getter]
+    pub fn get_node(&self) -> Option<Py<Node>> {
+        self.node.clone()
+    }
+
+    /// Swap the root node, which picks up from the current position.
+    #[setter]
+    pub fn set_node(&mut self, py: Python<'_>, node: Bound<'_, Node>) -> crate::Result<()> {
+        let inner = node.borrow().0.clone();
+        self.with_inner(py, move |transport| transport.set_node(inner))?;
+        self.node = Some(node.unbind());
+        Ok(())
+    }
+
+    fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
+        if let Some(node) = &self.node {
+            visit.call(node)?
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        self.node = None;
+    }
+}
@@ -0,0 +1,23 @@
+import unittest
+from libdaw.nodes import Instrument
+from libdaw.nodes.instrument import Tone
+from libdaw.nodes.oscillators import Sine
+from libdaw.notation import Sequence
+from libdaw.time import Duration, Timestamp
+
+class TestAudio(unittest.TestCase):
+    def test_element_leaves_the_instrument_alone(self):
+        sequence = Sequence.loads('+(c d e)')
+        instrument = Instrument(lambda tone: Sine())
+        first = sequence.audio(instrument, sample_rate=8000).wav
+        self.assertEqual(sequence.audio(instrument, sample_rate=8000).wav, first)
+
+        instrument.add_tone(Tone(Timestamp(0), Duration(1), 880))
+        for _ in range(100):
+            instrument.process([])
+        state = instrument.__getstate__()
+        self.assertEqual(sequence.audio(instrument, sample_rate=8000).wav, first)
+        tones, sample = instrument.__getstate__()
+        self.assertEqual([tone.frequency for tone in tones], [tone.frequency for tone in state[0]])
+        self.assertEqual(sample, state[1])
+        self.assertEqual(sample, 100)
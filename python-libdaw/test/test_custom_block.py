@@ -0,0 +1,35 @@
+import unittest
+from libdaw import Sample
+from libdaw.nodes import CustomBlock
+
+class Double(CustomBlock):
+    def __init__(self):
+        super().__init__(block_size=4)
+
+    def __call__(self, inputs):
+        return [inputs[0] * 2]
+
+class TestCustomBlock(unittest.TestCase):
+    def test_block_latency(self):
+        node = Double()
+        outputs = [node.process([Sample([i, -i])]) for i in range(8)]
+        self.assertEqual(outputs[:4], [[]] * 4)
+        self.assertEqual(
+            [[list(sample) for sample in output] for output in outputs[4:]],
+            [[[2.0 * i, -2.0 * i]] for i in range(4)],
+        )
+
+    def test_output_length(self):
+        node = CustomBlock(lambda inputs: [inputs[0][:1]], block_size=2)
+        with self.assertRaisesRegex(ValueError, 'output streams must have 2 frames, not 1'):
+            for _ in range(2):
+                node.process([Sample([1.0])])
+
+    def test_single_array(self):
+        node = CustomBlock(lambda inputs: inputs[0] * 3, block_size=2)
+        outputs = [node.process([Sample([i])]) for i in range(4)]
+        self.assertEqual(outputs[:2], [[]] * 2)
+        self.assertEqual(
+            [[list(sample) for sample in output] for output in outputs[2:]],
+            [[[0.0]], [[3.0]]],
+        )
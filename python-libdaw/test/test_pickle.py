@@ -0,0 +1,67 @@
+import copy
+import pickle
+import unittest
+from libdaw import Sample
+from libdaw.nodes import Gain, Graph, Instrument, Lfo, Tap
+from libdaw.nodes.instrument import Tone
+from libdaw.nodes.mixer import Mixer
+from libdaw.nodes.oscillators import Sine
+from libdaw.time import Duration, Timestamp
+
+def render(node, frames=64, inputs=()):
+    return [repr(node.process(list(inputs))) for _ in range(frames)]
+
+def voice(tone):
+    return Sine(frequency=tone.frequency)
+
+class TestPickle(unittest.TestCase):
+    def test_node(self):
+        for node in (Sine(frequency=330), Lfo(frequency=5)):
+            render(node, 100)
+            restored = (pickle.loads(pickle.dumps(node)), copy.deepcopy(node))
+            for _ in range(64):
+                [expected] = node.process([])
+                for copied in restored:
+                    [actual] = copied.process([])
+                    self.assertAlmostEqual(actual[0], expected[0])
+
+    def test_graph(self):
+        graph = Graph()
+        gain = Gain(0.5)
+        graph.connect(Sine(frequency=220), gain)
+        graph.connect(Sine(frequency=330), gain)
+        graph.output(gain)
+        self.assertEqual(render(pickle.loads(pickle.dumps(graph))), render(graph))
+
+    def test_mixer(self):
+        mixer = Mixer()
+        channel = mixer.add_channel('lead')
+        channel.gain = 0.5
+        channel.add_insert(Gain(2.0))
+        mixer.add_bus('verb')
+        mixer.send('lead', 'verb', 0.25, False)
+        copied = copy.deepcopy(mixer)
+        self.assertEqual(copied.channels, ['lead'])
+        self.assertEqual(copied.buses, ['verb'])
+        self.assertEqual(copied.channel('lead').gain, 0.5)
+        inputs = [Sample([0.5])]
+        self.assertEqual(render(copied, inputs=inputs), render(mixer, inputs=inputs))
+        with self.assertRaises(TypeError):
+            pickle.dumps(channel)
+
+    def test_instrument(self):
+        instrument = Instrument(voice, sample_rate=8000)
+        instrument.add_tone(Tone(Timestamp(0), Duration(1), 440))
+        render(instrument, 100)
+        restored = pickle.loads(pickle.dumps(instrument))
+        self.assertEqual(restored.__getstate__()[1], 100)
+        for _ in range(64):
+            [expected], [actual] = instrument.process([]), restored.process([])
+            self.assertAlmostEqual(actual[0], expected[0])
+
+    def test_tap(self):
+        tap = Tap()
+        render(tap, 8, [Sample([0.5])])
+        self.assertEqual(len(pickle.loads(pickle.dumps(tap)).captured), 8)
+        tap.finish()
+        self.assertTrue(pickle.loads(pickle.dumps(tap)).finished)